@@ -30,6 +30,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     println!("  • dados.json");
     println!("  • dados.parquet");
     println!("  • dados.xml");
+    println!("  • dados.arrow");
+    println!("  • dados.arrows");
 
     Ok(())
 }
@@ -44,6 +46,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 100.0,
             area_da_torre: 300.0,
             area_de_lazer: None,
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Construtora 2".to_string(),
@@ -53,6 +56,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 25.0,
             area_da_torre: 100.0,
             area_de_lazer: None,
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Construtora 3".to_string(),
@@ -62,6 +66,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 20.0,
             area_da_torre: 200.0,
             area_de_lazer: Some(50.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Construtora 4".to_string(),
@@ -71,6 +76,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 22.0,
             area_da_torre: 280.0,
             area_de_lazer: None,
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Construtora 5".to_string(),
@@ -80,6 +86,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 29.0,
             area_da_torre: 250.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Construtora 6".to_string(),
@@ -89,6 +96,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 18.0,
             area_da_torre: 150.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Alpha".to_string(),
@@ -98,6 +106,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 20.0,
             area_da_torre: 50.0,
             area_de_lazer: None,
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Beta".to_string(),
@@ -107,6 +116,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 26.0,
             area_da_torre: 220.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Eta".to_string(),
@@ -116,6 +126,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 21.0,
             area_da_torre: 220.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Épsilon".to_string(),
@@ -125,6 +136,7 @@ fn mock_empreendimentos() -> Vec<Empreendimento> {
             altura_da_torre: 16.0,
             area_da_torre: 220.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         },
     ]
 }
@@ -0,0 +1,404 @@
+use crate::business_logic::indices::IndicesUrbanisticos;
+use crate::business_logic::validator::{ValidationResult, Violacao, regras_aplicaveis};
+use crate::models::arrow_converter;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::uso::Uso;
+use arrow::array::{
+    Array, ArrayRef, AsArray, BooleanArray, Float64Array, Int32Array, StringArray, UInt32Array,
+    UInt64Array,
+};
+use arrow::compute::kernels::numeric::{div, mul};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, is_not_null, nullif, take_record_batch};
+use arrow::datatypes::{DataType, Float64Type, Int32Type};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Colunas de um lote de empreendimentos no esquema de
+/// [`arrow_converter::schema`], entregues às regras com implementação vetorizada.
+pub struct Colunas {
+    batch: RecordBatch,
+    indices: OnceCell<IndicesColunares>,
+}
+
+/// Índices urbanísticos calculados coluna a coluna; nulos onde
+/// [`IndicesUrbanisticos::calcular`] devolveria `None`.
+pub struct IndicesColunares {
+    pub coeficiente_de_aproveitamento: Float64Array,
+    pub taxa_de_ocupacao: Float64Array,
+    pub taxa_de_permeabilidade: Float64Array,
+}
+
+impl Colunas {
+    /// `batch` deve estar normalizado com [`arrow_converter::normalizar`].
+    pub fn new(batch: RecordBatch) -> Self {
+        Self {
+            batch,
+            indices: OnceCell::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn reais(&self, nome: &str) -> &Float64Array {
+        self.coluna(nome).as_primitive::<Float64Type>()
+    }
+
+    pub fn inteiros(&self, nome: &str) -> &Int32Array {
+        self.coluna(nome).as_primitive::<Int32Type>()
+    }
+
+    pub fn textos(&self, nome: &str) -> &StringArray {
+        self.coluna(nome).as_string::<i32>()
+    }
+
+    fn coluna(&self, nome: &str) -> &ArrayRef {
+        self.batch
+            .column_by_name(nome)
+            .unwrap_or_else(|| panic!("Coluna '{}' fora do esquema", nome))
+    }
+
+    /// Resultado das regras que não se aplicam sem a coluna `nome`: sem
+    /// violações quando ela é toda nula; `None` (avaliar por linha) caso contrário.
+    pub fn sem_valores(&self, nome: &str) -> Option<Vec<Option<String>>> {
+        let coluna = self.coluna(nome);
+        (coluna.null_count() == coluna.len()).then(|| vec![None; coluna.len()])
+    }
+
+    /// Soma das áreas das torres (`numero_de_torres * area_da_torre`).
+    pub fn area_das_torres(&self) -> Float64Array {
+        let torres = cast(self.inteiros("numero_de_torres"), &DataType::Float64)
+            .expect("Int32 sempre converte para Float64");
+        mul(&torres, self.reais("area_da_torre"))
+            .expect("colunas do mesmo lote")
+            .as_primitive::<Float64Type>()
+            .clone()
+    }
+
+    /// Total de unidades como em [`Empreendimento::total_de_unidades`]: o
+    /// informado ou unidades por torre vezes torres, em `u64`.
+    pub fn total_de_unidades(&self) -> UInt64Array {
+        let informadas = self.inteiros("numero_de_unidades");
+        let por_torre = self.inteiros("unidades_por_torre");
+        let torres = self.inteiros("numero_de_torres");
+        (0..self.len())
+            .map(|i| {
+                if informadas.is_valid(i) {
+                    return Some(informadas.value(i) as u64);
+                }
+                let por_torre = por_torre.is_valid(i).then(|| por_torre.value(i) as u64)?;
+                Some(por_torre * torres.value(i) as u64)
+            })
+            .collect()
+    }
+
+    pub fn indices(&self) -> &IndicesColunares {
+        self.indices.get_or_init(|| {
+            let terreno = self.reais("area_do_terreno");
+            let sem_terreno = BooleanArray::from_unary(terreno, |area| area <= 0.0);
+            let razao = |area: &dyn Array| -> Float64Array {
+                let razao = div(&area, terreno).expect("colunas do mesmo lote");
+                nullif(&razao, &sem_terreno)
+                    .expect("colunas do mesmo lote")
+                    .as_primitive::<Float64Type>()
+                    .clone()
+            };
+
+            // Sem projeção informada, a ocupação é estimada pela soma das áreas das torres.
+            let projecao_informada = self.reais("area_de_projecao");
+            let projecao = zip(
+                &is_not_null(projecao_informada).expect("coluna válida"),
+                projecao_informada,
+                &self.area_das_torres(),
+            )
+            .expect("colunas do mesmo lote");
+
+            IndicesColunares {
+                coeficiente_de_aproveitamento: razao(self.reais("area_construida")),
+                taxa_de_ocupacao: razao(projecao.as_ref()),
+                taxa_de_permeabilidade: razao(self.reais("area_permeavel")),
+            }
+        })
+    }
+
+    fn indices_de(&self, linha: usize) -> IndicesUrbanisticos {
+        let valor = |array: &Float64Array| array.is_valid(linha).then(|| array.value(linha));
+        let indices = self.indices();
+        IndicesUrbanisticos {
+            coeficiente_de_aproveitamento: valor(&indices.coeficiente_de_aproveitamento),
+            taxa_de_ocupacao: valor(&indices.taxa_de_ocupacao),
+            taxa_de_permeabilidade: valor(&indices.taxa_de_permeabilidade),
+        }
+    }
+}
+
+/// Mensagens por linha a partir da máscara de violações; linhas nulas na
+/// máscara não violam a regra.
+pub fn mensagens(
+    violacoes: &BooleanArray,
+    mensagem: impl Fn(usize) -> String,
+) -> Vec<Option<String>> {
+    (0..violacoes.len())
+        .map(|i| (violacoes.is_valid(i) && violacoes.value(i)).then(|| mensagem(i)))
+        .collect()
+}
+
+/// Valida um `RecordBatch` de empreendimentos de forma colunar, com os mesmos
+/// resultados de [`crate::business_logic::validar_empreendimentos`].
+///
+/// As linhas são agrupadas pela combinação de cidade, zona, construtora e
+/// uso, que determina as regras aplicáveis; cada regra é avaliada sobre o
+/// grupo inteiro com kernels do Arrow quando implementa `validar_colunas`, e
+/// linha a linha caso contrário.
+pub fn validar_record_batch(batch: &RecordBatch) -> Result<Vec<ValidationResult>, Box<dyn Error>> {
+    validar_a_partir_da_linha(batch, 1)
+}
+
+/// Valida vários lotes em sequência, como os lidos de um arquivo Arrow IPC.
+pub fn validar_record_batches(
+    batches: &[RecordBatch],
+) -> Result<Vec<ValidationResult>, Box<dyn Error>> {
+    validar_record_batches_em_fluxo(batches.iter().cloned().map(Ok::<_, ArrowError>))
+}
+
+/// Valida os lotes à medida que são lidos, sem mantê-los todos na memória,
+/// como os de [`crate::file_reader::read_parquet_em_lotes`].
+pub fn validar_record_batches_em_fluxo<E: Into<Box<dyn Error>>>(
+    batches: impl IntoIterator<Item = Result<RecordBatch, E>>,
+) -> Result<Vec<ValidationResult>, Box<dyn Error>> {
+    let mut resultados = Vec::new();
+    for batch in batches {
+        let batch = batch.map_err(Into::into)?;
+        resultados.extend(validar_a_partir_da_linha(&batch, resultados.len() + 1)?);
+    }
+    Ok(resultados)
+}
+
+fn validar_a_partir_da_linha(
+    batch: &RecordBatch,
+    primeira_linha: usize,
+) -> Result<Vec<ValidationResult>, Box<dyn Error>> {
+    let colunas = Colunas::new(arrow_converter::normalizar(batch, primeira_linha)?);
+    let construtoras = colunas.textos("construtora");
+    let cidades = colunas.textos("cidade");
+    let zonas = colunas.textos("zona");
+    let usos = colunas.textos("uso");
+
+    // Linhas com a mesma combinação de cidade, zona, construtora e uso
+    // compartilham as regras e formam um grupo.
+    let mut grupo_por_chave = HashMap::new();
+    let mut grupos: Vec<(Vec<u32>, _)> = Vec::new();
+    for linha in 0..colunas.len() {
+        let zona = zonas.is_valid(linha).then(|| zonas.value(linha));
+        let chave = (
+            cidades.value(linha),
+            zona,
+            construtoras.value(linha),
+            usos.value(linha),
+        );
+        let grupo = match grupo_por_chave.get(&chave) {
+            Some(&grupo) => grupo,
+            None => {
+                let uso: Uso = chave.3.parse().map_err(|e| {
+                    format!("Linha {}, coluna 'uso': {}", primeira_linha + linha, e)
+                })?;
+                grupos.push((
+                    Vec::new(),
+                    regras_aplicaveis(chave.0, chave.1, chave.2, uso),
+                ));
+                grupo_por_chave.insert(chave, grupos.len() - 1);
+                grupos.len() - 1
+            }
+        };
+        grupos[grupo].0.push(linha as u32);
+    }
+
+    let mut violacoes_por_linha = vec![Vec::new(); colunas.len()];
+    for (linhas, regras) in grupos {
+        let linhas = UInt32Array::from(linhas);
+        let grupo = Colunas::new(take_record_batch(&colunas.batch, &linhas)?);
+        let mut empreendimentos: Option<Vec<Empreendimento>> = None;
+
+        for regra in regras {
+            let resultado = match regra.validar_colunas(&grupo) {
+                Some(resultado) => resultado,
+                None => {
+                    if empreendimentos.is_none() {
+                        empreendimentos =
+                            Some(arrow_converter::empreendimentos_de(&grupo.batch, |i| {
+                                primeira_linha + linhas.value(i) as usize
+                            })?);
+                    }
+                    empreendimentos
+                        .iter()
+                        .flatten()
+                        .map(|e| regra.validar(e))
+                        .collect()
+                }
+            };
+            for (linha, mensagem) in linhas.values().iter().zip(resultado) {
+                if let Some(mensagem) = mensagem {
+                    violacoes_por_linha[*linha as usize]
+                        .push((mensagem, Violacao::de(regra.as_ref())));
+                }
+            }
+        }
+    }
+
+    Ok(violacoes_por_linha
+        .into_iter()
+        .enumerate()
+        .map(|(linha, violacoes)| {
+            let (mensagens, violacoes): (Vec<_>, Vec<_>) = violacoes.into_iter().unzip();
+            ValidationResult {
+                empreendimento: construtoras.value(linha).to_string(),
+                regras_ok: mensagens.is_empty(),
+                mensagens,
+                violacoes,
+                zona: zonas
+                    .is_valid(linha)
+                    .then(|| zonas.value(linha).to_string()),
+                altura_permitida: None,
+                indices: colunas.indices_de(linha),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business_logic::validar_empreendimentos;
+    use crate::models::arrow_converter::para_record_batch;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use std::sync::Arc;
+
+    fn make_empreendimento() -> Empreendimento {
+        Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "Curitiba".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_indices_colunares() {
+        let mut sem_projecao = make_empreendimento();
+        sem_projecao.area_construida = Some(2500.0);
+        let mut com_projecao = make_empreendimento();
+        com_projecao.area_de_projecao = Some(550.0);
+        com_projecao.area_permeavel = Some(200.0);
+        let mut sem_terreno = make_empreendimento();
+        sem_terreno.area_do_terreno = 0.0;
+        let empreendimentos = vec![sem_projecao, com_projecao, sem_terreno];
+
+        let colunas = Colunas::new(para_record_batch(&empreendimentos).unwrap());
+        for (linha, e) in empreendimentos.iter().enumerate() {
+            assert_eq!(colunas.indices_de(linha), IndicesUrbanisticos::calcular(e));
+        }
+    }
+
+    #[test]
+    fn test_sem_valores() {
+        let mut com_vagas = make_empreendimento();
+        com_vagas.vagas_de_garagem = Some(10);
+        let todos_nulos = Colunas::new(para_record_batch(&[make_empreendimento()]).unwrap());
+        let algum_valor =
+            Colunas::new(para_record_batch(&[make_empreendimento(), com_vagas]).unwrap());
+
+        assert_eq!(
+            todos_nulos.sem_valores("vagas_de_garagem"),
+            Some(vec![None])
+        );
+        assert_eq!(algum_valor.sem_valores("vagas_de_garagem"), None);
+    }
+
+    #[test]
+    fn test_validar_record_batch_igual_ao_motor_por_linha() {
+        let mut alta = make_empreendimento();
+        alta.altura_da_torre = 40.0;
+        let mut alpha = make_empreendimento();
+        alpha.construtora = "Alpha".to_string();
+        alpha.area_de_lazer = Some(50.0);
+        let mut guaratingueta = make_empreendimento();
+        guaratingueta.cidade = "Guaratinguetá".to_string();
+        guaratingueta.numero_de_torres = 3;
+        let empreendimentos = vec![make_empreendimento(), alta, alpha, guaratingueta];
+
+        let colunares =
+            validar_record_batch(&para_record_batch(&empreendimentos).unwrap()).unwrap();
+        let por_linha = validar_empreendimentos(&empreendimentos);
+
+        assert_eq!(colunares.len(), por_linha.len());
+        for (colunar, linha) in colunares.iter().zip(&por_linha) {
+            assert_eq!(colunar.empreendimento, linha.empreendimento);
+            assert_eq!(colunar.mensagens, linha.mensagens);
+            assert_eq!(colunar.violacoes, linha.violacoes);
+            assert_eq!(colunar.regras_ok, linha.regras_ok);
+        }
+        assert!(!colunares[2].regras_ok, "Regra da Alpha avaliada por linha");
+    }
+
+    #[test]
+    fn test_validar_record_batch_normaliza_colunas() {
+        let schema = Schema::new(vec![
+            Field::new("construtora", DataType::Utf8, false),
+            Field::new("cidade", DataType::Utf8, false),
+            Field::new("area-do-terreno", DataType::Int64, false),
+            Field::new("numero-de-torres", DataType::Int64, false),
+            Field::new("altura-da-torre", DataType::Utf8, false),
+            Field::new("area-da-torre", DataType::Int64, false),
+            Field::new("uso", DataType::Utf8, false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec!["Teste"])),
+                Arc::new(StringArray::from(vec!["Curitiba"])),
+                Arc::new(Int64Array::from(vec![1000])),
+                Arc::new(Int64Array::from(vec![1])),
+                Arc::new(StringArray::from(vec!["32,5"])),
+                Arc::new(Int64Array::from(vec![300])),
+                Arc::new(StringArray::from(vec!["residencial"])),
+            ],
+        )
+        .unwrap();
+
+        let resultados = validar_record_batch(&batch).unwrap();
+        assert_eq!(
+            resultados[0].mensagens,
+            vec!["Altura da torre deve ser inferior a 30m."]
+        );
+        assert_eq!(resultados[0].indices.taxa_de_ocupacao, Some(0.3));
+    }
+
+    #[test]
+    fn test_validar_record_batches_numera_linhas_entre_lotes() {
+        let valido = para_record_batch(&[make_empreendimento()]).unwrap();
+        let schema = valido.schema();
+        let mut colunas = valido.columns().to_vec();
+        colunas[10] = Arc::new(StringArray::from(vec!["industrial"]));
+        let invalido = RecordBatch::try_new(schema, colunas).unwrap();
+
+        let erro = validar_record_batches(&[valido, invalido])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(erro.starts_with("Linha 2, coluna 'uso'"), "{}", erro);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::models::empreendimento::Empreendimento;
+
+/// Índices urbanísticos calculados sobre a área do terreno.
+///
+/// Cada índice é `None` quando o empreendimento não informa os dados
+/// necessários para calculá-lo.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct IndicesUrbanisticos {
+    /// Coeficiente de aproveitamento (CA): área construída / área do terreno.
+    pub coeficiente_de_aproveitamento: Option<f64>,
+    /// Taxa de ocupação (TO): projeção das edificações / área do terreno.
+    pub taxa_de_ocupacao: Option<f64>,
+    /// Taxa de permeabilidade (TP): área permeável / área do terreno.
+    pub taxa_de_permeabilidade: Option<f64>,
+}
+
+impl IndicesUrbanisticos {
+    pub fn calcular(e: &Empreendimento) -> Self {
+        if e.area_do_terreno <= 0.0 {
+            return Self::default();
+        }
+
+        // Sem projeção informada, a ocupação é estimada pela soma das áreas das torres.
+        let projecao = e
+            .area_de_projecao
+            .unwrap_or(e.numero_de_torres as f64 * e.area_da_torre);
+
+        Self {
+            coeficiente_de_aproveitamento: e.area_construida.map(|a| a / e.area_do_terreno),
+            taxa_de_ocupacao: Some(projecao / e.area_do_terreno),
+            taxa_de_permeabilidade: e.area_permeavel.map(|a| a / e.area_do_terreno),
+        }
+    }
+
+    pub fn algum_calculado(&self) -> bool {
+        self.coeficiente_de_aproveitamento.is_some()
+            || self.taxa_de_ocupacao.is_some()
+            || self.taxa_de_permeabilidade.is_some()
+    }
+}
+
+/// Densidade populacional em habitantes por hectare, estimada pelo total de
+/// unidades multiplicado pelo número de pessoas por unidade.
+pub fn densidade_populacional(e: &Empreendimento, pessoas_por_unidade: f64) -> Option<f64> {
+    if e.area_do_terreno <= 0.0 {
+        return None;
+    }
+    let habitantes = e.total_de_unidades()? as f64 * pessoas_por_unidade;
+    Some(habitantes / (e.area_do_terreno / 10_000.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_empreendimento() -> Empreendimento {
+        Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            area_construida: Some(2500.0),
+            area_permeavel: Some(200.0),
+            area_de_projecao: Some(550.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn calcula_todos_os_indices() {
+        let indices = IndicesUrbanisticos::calcular(&make_empreendimento());
+
+        assert_eq!(indices.coeficiente_de_aproveitamento, Some(2.5));
+        assert_eq!(indices.taxa_de_ocupacao, Some(0.55));
+        assert_eq!(indices.taxa_de_permeabilidade, Some(0.2));
+    }
+
+    #[test]
+    fn taxa_de_ocupacao_usa_area_das_torres_sem_projecao() {
+        let mut e = make_empreendimento();
+        e.area_de_projecao = None;
+
+        let indices = IndicesUrbanisticos::calcular(&e);
+        assert_eq!(indices.taxa_de_ocupacao, Some(0.6));
+    }
+
+    #[test]
+    fn indices_sem_dados_ficam_vazios() {
+        let mut e = make_empreendimento();
+        e.area_construida = None;
+        e.area_permeavel = None;
+
+        let indices = IndicesUrbanisticos::calcular(&e);
+        assert_eq!(indices.coeficiente_de_aproveitamento, None);
+        assert_eq!(indices.taxa_de_permeabilidade, None);
+        assert!(indices.algum_calculado());
+    }
+
+    #[test]
+    fn calcula_densidade_por_unidades_totais_ou_por_torre() {
+        let mut e = make_empreendimento();
+        e.numero_de_unidades = Some(50);
+
+        // 50 unidades * 3 pessoas em 0.1 ha
+        assert_eq!(densidade_populacional(&e, 3.0), Some(1500.0));
+
+        e.numero_de_unidades = None;
+        e.unidades_por_torre = Some(20);
+        assert_eq!(densidade_populacional(&e, 3.0), Some(1200.0));
+
+        e.unidades_por_torre = None;
+        assert_eq!(densidade_populacional(&e, 3.0), None);
+    }
+
+    #[test]
+    fn terreno_sem_area_nao_calcula_indices() {
+        let mut e = make_empreendimento();
+        e.area_do_terreno = 0.0;
+
+        assert!(!IndicesUrbanisticos::calcular(&e).algum_calculado());
+    }
+}
@@ -1,4 +1,12 @@
+pub mod colunar;
+pub mod indices;
 pub mod regras;
+pub mod restricoes_altura;
 pub mod validator;
+pub mod zoneamento;
 
-pub use validator::validar_empreendimentos;
+pub use colunar::{validar_record_batch, validar_record_batches, validar_record_batches_em_fluxo};
+pub use validator::{
+    CamadasGeograficas, Severidade, ValidationResult, Violacao, validar_empreendimentos,
+    validar_empreendimentos_com_camadas, validar_empreendimentos_com_zoneamento,
+};
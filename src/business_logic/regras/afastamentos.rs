@@ -0,0 +1,232 @@
+use super::padrao::RegraNegocio;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::torre::Torre;
+
+/// Distância mínima entre torres: o maior entre `H / divisor` (H da torre mais
+/// alta do par) e `minimo`, em metros.
+#[derive(Debug)]
+pub struct RegraAfastamentoEntreTorres {
+    pub divisor: f64,
+    pub minimo: f64,
+}
+
+/// Recuo mínimo de cada torre às divisas do lote: o maior entre
+/// `H / divisor` e `minimo`, em metros.
+#[derive(Debug)]
+pub struct RegraRecuoDivisas {
+    pub divisor: f64,
+    pub minimo: f64,
+}
+
+fn identificar(torre: &Torre, indice: usize) -> String {
+    torre
+        .nome
+        .clone()
+        .unwrap_or_else(|| format!("{}", indice + 1))
+}
+
+impl RegraNegocio for RegraAfastamentoEntreTorres {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let torres = e.torres.as_ref()?;
+        let mut violacoes = Vec::new();
+
+        for (i, a) in torres.iter().enumerate() {
+            for (j, b) in torres.iter().enumerate().skip(i + 1) {
+                let altura = e.altura_de(a).max(e.altura_de(b));
+                let exigida = (altura / self.divisor).max(self.minimo);
+                let distancia = e
+                    .projecao_em_metros(a)
+                    .distancia_ate(&e.projecao_em_metros(b));
+                if distancia < exigida {
+                    violacoes.push(format!(
+                        "Distância entre as torres {} e {} ({:.2}m) inferior ao mínimo exigido ({:.2}m)",
+                        identificar(a, i),
+                        identificar(b, j),
+                        distancia,
+                        exigida
+                    ));
+                }
+            }
+        }
+
+        if violacoes.is_empty() {
+            None
+        } else {
+            Some(violacoes.join("; "))
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["torres"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraAfastamentoEntreTorres"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["torres"]
+    }
+}
+
+impl RegraNegocio for RegraRecuoDivisas {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let torres = e.torres.as_ref()?;
+        let lote = e.lote.as_ref()?.contorno_em_metros();
+        let mut violacoes = Vec::new();
+
+        for (i, torre) in torres.iter().enumerate() {
+            let projecao = e.projecao_em_metros(torre);
+            if !lote.contem_poligono(&projecao) {
+                violacoes.push(format!(
+                    "Torre {} ultrapassa os limites do lote",
+                    identificar(torre, i)
+                ));
+                continue;
+            }
+
+            let exigido = (e.altura_de(torre) / self.divisor).max(self.minimo);
+            let recuo = lote.distancia_entre_bordas(&projecao);
+            if recuo < exigido {
+                violacoes.push(format!(
+                    "Recuo da torre {} às divisas ({:.2}m) inferior ao mínimo exigido ({:.2}m)",
+                    identificar(torre, i),
+                    recuo,
+                    exigido
+                ));
+            }
+        }
+
+        if violacoes.is_empty() {
+            None
+        } else {
+            Some(violacoes.join("; "))
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["torres", "lote"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraRecuoDivisas"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["torres", "lote"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+
+    fn retangulo(x: f64, y: f64, largura: f64, profundidade: f64) -> Poligono {
+        Poligono(vec![
+            [x, y],
+            [x + largura, y],
+            [x + largura, y + profundidade],
+            [x, y + profundidade],
+        ])
+    }
+
+    fn torre(nome: &str, x: f64, y: f64, altura: Option<f64>) -> Torre {
+        Torre {
+            nome: Some(nome.to_string()),
+            projecao: retangulo(x, y, 15.0, 15.0),
+            altura,
+        }
+    }
+
+    fn make_empreendimento() -> Empreendimento {
+        Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 3000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 30.0,
+            area_da_torre: 225.0,
+            area_de_lazer: Some(300.0),
+            torres: Some(vec![
+                torre("A", 6.0, 10.0, None),
+                torre("B", 27.0, 10.0, Some(60.0)),
+            ]),
+            lote: Some(Lote::local(retangulo(0.0, 0.0, 60.0, 50.0))),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_afastamento_entre_torres_usa_torre_mais_alta() {
+        let regra = RegraAfastamentoEntreTorres {
+            divisor: 6.0,
+            minimo: 3.0,
+        };
+        let mut e = make_empreendimento();
+
+        // distância 6m, exigido 60 / 6 = 10m
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Distância entre as torres A e B (6.00m) inferior ao mínimo exigido (10.00m)"
+        );
+
+        e.torres.as_mut().unwrap()[1].altura = None;
+        assert!(regra.validar(&e).is_none(), "30 / 6 = 5m atendido");
+    }
+
+    #[test]
+    fn test_afastamento_entre_torres_respeita_minimo() {
+        let regra = RegraAfastamentoEntreTorres {
+            divisor: 6.0,
+            minimo: 8.0,
+        };
+        let mut e = make_empreendimento();
+        e.torres.as_mut().unwrap()[1].altura = None;
+
+        assert!(
+            regra
+                .validar(&e)
+                .unwrap()
+                .contains("mínimo exigido (8.00m)")
+        );
+    }
+
+    #[test]
+    fn test_recuo_divisas() {
+        let regra = RegraRecuoDivisas {
+            divisor: 6.0,
+            minimo: 3.0,
+        };
+        let mut e = make_empreendimento();
+
+        // Torre B: recuo lateral de 60 - 42 = 18m, exigido 10m; torre A: 6m, exigido 5m
+        assert!(regra.validar(&e).is_none());
+
+        e.torres.as_mut().unwrap()[1].projecao = retangulo(40.0, 10.0, 15.0, 15.0);
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Recuo da torre B às divisas (5.00m) inferior ao mínimo exigido (10.00m)"
+        );
+
+        e.torres.as_mut().unwrap()[1].projecao = retangulo(50.0, 10.0, 15.0, 15.0);
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Torre B ultrapassa os limites do lote"
+        );
+    }
+
+    #[test]
+    fn test_sem_implantacao_nao_aplica() {
+        let afastamento = RegraAfastamentoEntreTorres {
+            divisor: 6.0,
+            minimo: 3.0,
+        };
+        let recuo = RegraRecuoDivisas {
+            divisor: 6.0,
+            minimo: 3.0,
+        };
+        let mut e = make_empreendimento();
+        e.lote = None;
+
+        assert!(recuo.validar(&e).is_none());
+
+        e.torres = None;
+        assert!(afastamento.validar(&e).is_none());
+    }
+}
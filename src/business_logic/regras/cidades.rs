@@ -1,5 +1,7 @@
 use super::padrao::RegraNegocio;
+use crate::business_logic::colunar::{Colunas, mensagens};
 use crate::models::empreendimento::Empreendimento;
+use arrow::array::BooleanArray;
 
 #[derive(Debug)]
 pub struct RegraMaxTorres(pub u32);
@@ -17,18 +19,28 @@ impl RegraNegocio for RegraMaxTorres {
             None
         }
     }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let torres = colunas.inteiros("numero_de_torres");
+        let violacoes = BooleanArray::from_unary(torres, |t| t as u32 > self.0);
+        Some(mensagens(&violacoes, |i| {
+            format!(
+                "Número de torres ({}) excede o máximo permitido ({})",
+                torres.value(i),
+                self.0
+            )
+        }))
+    }
     fn nome(&self) -> &'static str {
         "RegraMaxTorres"
     }
+    fn campos(&self) -> &'static [&'static str] {
+        &["numero_de_torres"]
+    }
 }
 
 impl RegraNegocio for RegraAlturaPorTorresGuaratingueta {
     fn validar(&self, e: &Empreendimento) -> Option<String> {
-        let limite = match e.numero_de_torres {
-            1..=2 => 25.0,
-            3 => 20.0,
-            _ => 15.0,
-        };
+        let limite = limite_guaratingueta(e.numero_de_torres);
         if e.altura_da_torre >= limite {
             Some(format!(
                 "Altura da torre ({}) excede o limite para {} torres ({})",
@@ -38,9 +50,35 @@ impl RegraNegocio for RegraAlturaPorTorresGuaratingueta {
             None
         }
     }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let torres = colunas.inteiros("numero_de_torres");
+        let alturas = colunas.reais("altura_da_torre");
+        let violacoes = BooleanArray::from_binary(torres, alturas, |torres, altura| {
+            altura >= limite_guaratingueta(torres as u32)
+        });
+        Some(mensagens(&violacoes, |i| {
+            format!(
+                "Altura da torre ({}) excede o limite para {} torres ({})",
+                alturas.value(i),
+                torres.value(i),
+                limite_guaratingueta(torres.value(i) as u32)
+            )
+        }))
+    }
     fn nome(&self) -> &'static str {
         "RegraAlturaPorTorresGuaratingueta"
     }
+    fn campos(&self) -> &'static [&'static str] {
+        &["numero_de_torres", "altura_da_torre"]
+    }
+}
+
+fn limite_guaratingueta(torres: u32) -> f64 {
+    match torres {
+        1..=2 => 25.0,
+        3 => 20.0,
+        _ => 15.0,
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +98,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: Some(150.0),
             cidade: "Guaratinguetá".to_string(),
+            ..Default::default()
         };
 
         let caso_falha = Empreendimento {
@@ -70,6 +109,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: Some(150.0),
             cidade: "Guaratinguetá".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(regra.validar(&caso_ok), None);
@@ -100,6 +140,7 @@ mod tests {
                 altura_da_torre: altura,
                 area_de_lazer: Some(150.0),
                 cidade: "Guaratinguetá".to_string(),
+                ..Default::default()
             };
 
             let resultado = regra.validar(&empreendimento);
@@ -21,6 +21,9 @@ impl RegraNegocio for RegraAreaLazerAlpha {
     fn nome(&self) -> &'static str {
         "RegraAreaLazerAlpha"
     }
+    fn campos(&self) -> &'static [&'static str] {
+        &["area_de_lazer", "area_do_terreno"]
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +42,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: Some(50.0),
             cidade: "São Paulo".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(regra.validar(&empreendimento), None);
@@ -55,6 +59,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: None,
             cidade: "São Paulo".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -74,6 +79,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: Some(100.0),
             cidade: "São Paulo".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(regra.validar(&empreendimento), None);
@@ -90,6 +96,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: Some(80.0),
             cidade: "São Paulo".to_string(),
+            ..Default::default()
         };
 
         let resultado = regra.validar(&empreendimento);
@@ -112,6 +119,7 @@ mod tests {
             altura_da_torre: 10.0,
             area_de_lazer: Some(150.0),
             cidade: "São Paulo".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(regra.validar(&empreendimento), None);
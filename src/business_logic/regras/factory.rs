@@ -1,7 +1,12 @@
+use super::afastamentos::*;
 use super::cidades::*;
 use super::construtoras::*;
+use super::indices::*;
+use super::lote::*;
 use super::padrao::RegraNegocio;
 use super::padrao::*;
+use super::vagas::*;
+use crate::models::uso::Uso;
 
 pub struct RegrasFactory;
 
@@ -11,6 +16,8 @@ impl RegrasFactory {
             Box::new(RegraAlturaMax(30.0)),
             Box::new(RegraAreaTorresMax(0.8)),
             Box::new(RegraAreaLazerMin(0.1)),
+            Box::new(RegraAreaDoTerrenoConsistente(0.05)),
+            Box::new(RegraTotalDeUnidadesConsistente),
         ]
     }
 
@@ -18,6 +25,93 @@ impl RegrasFactory {
         match cidade {
             "Boituva" => vec![Box::new(RegraMaxTorres(5))],
             "Guaratinguetá" => vec![Box::new(RegraAlturaPorTorresGuaratingueta)],
+            "São Paulo" => vec![
+                Box::new(RegraTestadaMin(5.0)),
+                Box::new(RegraLarguraLoteMin(5.0)),
+                Box::new(RegraAfastamentoEntreTorres {
+                    divisor: 6.0,
+                    minimo: 3.0,
+                }),
+                Box::new(RegraRecuoDivisas {
+                    divisor: 6.0,
+                    minimo: 3.0,
+                }),
+                Box::new(RegraVagasPorUnidadeMax(2.0)),
+                Box::new(RegraVagasAcessiveisMin(0.02)),
+                Box::new(RegraVagasMotosMin(0.05)),
+                Box::new(RegraVagasBicicletasMin(0.1)),
+            ],
+            "Rio de Janeiro" => vec![
+                Box::new(RegraTestadaMin(6.0)),
+                Box::new(RegraAfastamentoEntreTorres {
+                    divisor: 5.0,
+                    minimo: 5.0,
+                }),
+                Box::new(RegraRecuoDivisas {
+                    divisor: 5.0,
+                    minimo: 2.5,
+                }),
+                Box::new(RegraVagasPorUnidadeMin(1.0)),
+                Box::new(RegraVagasAcessiveisMin(0.02)),
+            ],
+            "Curitiba" => vec![
+                Box::new(RegraTestadaMin(12.0)),
+                Box::new(RegraLarguraLoteMin(10.0)),
+                Box::new(RegraVagasPorAreaMin(100.0)),
+                Box::new(RegraVagasAcessiveisMin(0.02)),
+            ],
+            _ => vec![],
+        }
+    }
+
+    pub fn por_zona(cidade: &str, zona: &str) -> Vec<Box<dyn RegraNegocio>> {
+        match (cidade, zona) {
+            ("São Paulo", "ZER") => vec![
+                Box::new(RegraCoeficienteAproveitamentoMax(1.0)),
+                Box::new(RegraTaxaOcupacaoMax(0.5)),
+                Box::new(RegraTaxaPermeabilidadeMin(0.3)),
+                Box::new(RegraDensidadeMax {
+                    habitantes_por_hectare: 300.0,
+                    pessoas_por_unidade: 3.0,
+                }),
+            ],
+            ("São Paulo", "ZM") => vec![
+                Box::new(RegraCoeficienteAproveitamentoMax(2.0)),
+                Box::new(RegraTaxaOcupacaoMax(0.7)),
+                Box::new(RegraTaxaPermeabilidadeMin(0.15)),
+                Box::new(RegraDensidadeMax {
+                    habitantes_por_hectare: 600.0,
+                    pessoas_por_unidade: 3.0,
+                }),
+            ],
+            ("São Paulo", "ZEU") => vec![
+                Box::new(RegraCoeficienteAproveitamentoMax(4.0)),
+                Box::new(RegraTaxaOcupacaoMax(0.7)),
+                Box::new(RegraTaxaPermeabilidadeMin(0.15)),
+                Box::new(RegraDensidadeMax {
+                    habitantes_por_hectare: 1200.0,
+                    pessoas_por_unidade: 3.0,
+                }),
+            ],
+            ("Rio de Janeiro", "ZR") => vec![
+                Box::new(RegraCoeficienteAproveitamentoMax(1.5)),
+                Box::new(RegraTaxaOcupacaoMax(0.5)),
+                Box::new(RegraTaxaPermeabilidadeMin(0.2)),
+                Box::new(RegraDensidadeMax {
+                    habitantes_por_hectare: 400.0,
+                    pessoas_por_unidade: 3.0,
+                }),
+            ],
+            _ => vec![],
+        }
+    }
+
+    /// Regras por uso substituem as regras de mesmo nome da cidade e do padrão.
+    pub fn por_uso(cidade: &str, uso: Uso) -> Vec<Box<dyn RegraNegocio>> {
+        match (cidade, uso) {
+            ("Rio de Janeiro", Uso::Comercial) => vec![Box::new(RegraAlturaMax(45.0))],
+            ("Rio de Janeiro", Uso::Misto) => vec![Box::new(RegraAlturaMax(36.0))],
+            ("Guaratinguetá", Uso::Comercial) => vec![Box::new(RegraMaxTorres(2))],
             _ => vec![],
         }
     }
@@ -39,11 +133,17 @@ mod tests {
     fn test_padrao_regras() {
         let regras = RegrasFactory::padrao();
 
-        assert_eq!(regras.len(), 3, "Deve retornar 3 regras padrão");
+        assert_eq!(regras.len(), 5, "Deve retornar 5 regras padrão");
 
         let mut tipos = regras.iter().map(|r| r.nome()).collect::<Vec<_>>();
         tipos.sort();
-        let esperado = vec!["RegraAlturaMax", "RegraAreaLazerMin", "RegraAreaTorresMax"];
+        let esperado = vec![
+            "RegraAlturaMax",
+            "RegraAreaDoTerrenoConsistente",
+            "RegraAreaLazerMin",
+            "RegraAreaTorresMax",
+            "RegraTotalDeUnidadesConsistente",
+        ];
         assert_eq!(
             tipos, esperado,
             "As regras padrão não correspondem ao esperado"
@@ -60,6 +160,14 @@ mod tests {
         assert_eq!(guaratingueta.len(), 1);
         assert_eq!(guaratingueta[0].nome(), "RegraAlturaPorTorresGuaratingueta");
 
+        let sao_paulo = RegrasFactory::por_cidade("São Paulo");
+        assert!(
+            sao_paulo
+                .iter()
+                .any(|r| r.nome() == "RegraVagasPorUnidadeMax"),
+            "São Paulo deve limitar vagas por unidade"
+        );
+
         let desconhecida = RegrasFactory::por_cidade("CidadeX");
         assert!(
             desconhecida.is_empty(),
@@ -80,6 +188,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_regras_por_zona() {
+        let zm = RegrasFactory::por_zona("São Paulo", "ZM");
+        let mut nomes = zm.iter().map(|r| r.nome()).collect::<Vec<_>>();
+        nomes.sort();
+        assert_eq!(
+            nomes,
+            vec![
+                "RegraCoeficienteAproveitamentoMax",
+                "RegraDensidadeMax",
+                "RegraTaxaOcupacaoMax",
+                "RegraTaxaPermeabilidadeMin"
+            ]
+        );
+
+        assert!(
+            RegrasFactory::por_zona("Boituva", "ZM").is_empty(),
+            "Zonas são definidas por cidade"
+        );
+        assert!(RegrasFactory::por_zona("São Paulo", "ZX").is_empty());
+    }
+
+    #[test]
+    fn test_regras_por_uso() {
+        let comercial = RegrasFactory::por_uso("Rio de Janeiro", Uso::Comercial);
+        assert_eq!(comercial.len(), 1);
+        assert_eq!(comercial[0].nome(), "RegraAlturaMax");
+
+        assert!(RegrasFactory::por_uso("Rio de Janeiro", Uso::Residencial).is_empty());
+        assert!(RegrasFactory::por_uso("CidadeX", Uso::Comercial).is_empty());
+    }
+
     #[test]
     fn test_combinacao_de_regras_em_empreendimento() {
         let e = Empreendimento {
@@ -90,6 +230,7 @@ mod tests {
             altura_da_torre: 20.0,
             area_de_lazer: Some(50.0),
             cidade: "Boituva".to_string(),
+            ..Default::default()
         };
 
         let todas: Vec<Box<dyn RegraNegocio>> = RegrasFactory::padrao()
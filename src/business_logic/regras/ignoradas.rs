@@ -1,3 +1,4 @@
+use crate::models::uso::Uso;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
@@ -8,6 +9,14 @@ lazy_static! {
         m.insert("São Paulo", vec!["RegraAlturaMax"]);
         m
     };
+    pub static ref REGRAS_IGNORADAS_POR_USO: HashMap<Uso, Vec<&'static str>> = {
+        let mut m = HashMap::new();
+        m.insert(
+            Uso::Comercial,
+            vec!["RegraAreaLazerMin", "RegraDensidadeMax"],
+        );
+        m
+    };
 }
 
 #[cfg(test)]
@@ -26,4 +35,13 @@ mod tests {
         );
         assert!(REGRAS_IGNORADAS_POR_CIDADE.get("CidadeX").is_none());
     }
+
+    #[test]
+    fn test_regras_ignoradas_por_uso() {
+        assert_eq!(
+            REGRAS_IGNORADAS_POR_USO.get(&Uso::Comercial).unwrap(),
+            &vec!["RegraAreaLazerMin", "RegraDensidadeMax"]
+        );
+        assert!(REGRAS_IGNORADAS_POR_USO.get(&Uso::Residencial).is_none());
+    }
 }
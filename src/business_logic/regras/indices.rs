@@ -0,0 +1,339 @@
+use super::padrao::RegraNegocio;
+use crate::business_logic::colunar::{Colunas, mensagens};
+use crate::business_logic::indices::{IndicesUrbanisticos, densidade_populacional};
+use crate::models::empreendimento::Empreendimento;
+use arrow::array::{Array, BooleanArray, Float64Array};
+
+#[derive(Debug)]
+pub struct RegraCoeficienteAproveitamentoMax(pub f64);
+#[derive(Debug)]
+pub struct RegraTaxaOcupacaoMax(pub f64);
+#[derive(Debug)]
+pub struct RegraTaxaPermeabilidadeMin(pub f64);
+
+/// Total de unidades informado igual às unidades por torre multiplicadas pelo
+/// número de torres, quando os dois são informados.
+#[derive(Debug)]
+pub struct RegraTotalDeUnidadesConsistente;
+
+/// Densidade populacional máxima, em habitantes por hectare.
+#[derive(Debug)]
+pub struct RegraDensidadeMax {
+    pub habitantes_por_hectare: f64,
+    pub pessoas_por_unidade: f64,
+}
+
+impl RegraNegocio for RegraTotalDeUnidadesConsistente {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let informado = u64::from(e.numero_de_unidades?);
+        let por_torre = e.unidades_por_torre?;
+        let calculado = u64::from(por_torre) * u64::from(e.numero_de_torres);
+        if informado != calculado {
+            Some(format!(
+                "Total de unidades informado ({}) difere de {} por torre em {} torres ({})",
+                informado, por_torre, e.numero_de_torres, calculado
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["numero_de_unidades", "unidades_por_torre"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraTotalDeUnidadesConsistente"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &[
+            "numero_de_unidades",
+            "unidades_por_torre",
+            "numero_de_torres",
+        ]
+    }
+}
+
+impl RegraNegocio for RegraCoeficienteAproveitamentoMax {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let ca = IndicesUrbanisticos::calcular(e).coeficiente_de_aproveitamento?;
+        if ca > self.0 {
+            Some(format!(
+                "Coeficiente de aproveitamento ({:.2}) excede o máximo permitido ({:.2})",
+                ca, self.0
+            ))
+        } else {
+            None
+        }
+    }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let ca = &colunas.indices().coeficiente_de_aproveitamento;
+        let violacoes = BooleanArray::from_unary(ca, |ca| ca > self.0);
+        Some(mensagens(&violacoes, |i| {
+            format!(
+                "Coeficiente de aproveitamento ({:.2}) excede o máximo permitido ({:.2})",
+                ca.value(i),
+                self.0
+            )
+        }))
+    }
+    fn nome(&self) -> &'static str {
+        "RegraCoeficienteAproveitamentoMax"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["area_construida", "area_do_terreno"]
+    }
+}
+
+impl RegraNegocio for RegraTaxaOcupacaoMax {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let to = IndicesUrbanisticos::calcular(e).taxa_de_ocupacao?;
+        if to > self.0 {
+            Some(format!(
+                "Taxa de ocupação ({:.0}%) excede o máximo permitido ({:.0}%)",
+                to * 100.0,
+                self.0 * 100.0
+            ))
+        } else {
+            None
+        }
+    }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let to = &colunas.indices().taxa_de_ocupacao;
+        let violacoes = BooleanArray::from_unary(to, |to| to > self.0);
+        Some(mensagens(&violacoes, |i| {
+            format!(
+                "Taxa de ocupação ({:.0}%) excede o máximo permitido ({:.0}%)",
+                to.value(i) * 100.0,
+                self.0 * 100.0
+            )
+        }))
+    }
+    fn nome(&self) -> &'static str {
+        "RegraTaxaOcupacaoMax"
+    }
+    /// Sem projeção informada, a ocupação vem das torres.
+    fn campos(&self) -> &'static [&'static str] {
+        &[
+            "area_de_projecao",
+            "area_do_terreno",
+            "numero_de_torres",
+            "area_da_torre",
+        ]
+    }
+}
+
+impl RegraNegocio for RegraTaxaPermeabilidadeMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let tp = IndicesUrbanisticos::calcular(e).taxa_de_permeabilidade?;
+        if tp < self.0 {
+            Some(format!(
+                "Taxa de permeabilidade ({:.0}%) abaixo do mínimo exigido ({:.0}%)",
+                tp * 100.0,
+                self.0 * 100.0
+            ))
+        } else {
+            None
+        }
+    }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let tp = &colunas.indices().taxa_de_permeabilidade;
+        let violacoes = BooleanArray::from_unary(tp, |tp| tp < self.0);
+        Some(mensagens(&violacoes, |i| {
+            format!(
+                "Taxa de permeabilidade ({:.0}%) abaixo do mínimo exigido ({:.0}%)",
+                tp.value(i) * 100.0,
+                self.0 * 100.0
+            )
+        }))
+    }
+    fn nome(&self) -> &'static str {
+        "RegraTaxaPermeabilidadeMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["area_permeavel", "area_do_terreno"]
+    }
+}
+
+impl RegraNegocio for RegraDensidadeMax {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let densidade = densidade_populacional(e, self.pessoas_por_unidade)?;
+        if densidade > self.habitantes_por_hectare {
+            Some(format!(
+                "Densidade populacional ({:.1} hab/ha) excede o máximo permitido ({:.1} hab/ha)",
+                densidade, self.habitantes_por_hectare
+            ))
+        } else {
+            None
+        }
+    }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let terreno = colunas.reais("area_do_terreno");
+        let unidades = colunas.total_de_unidades();
+        let densidade: Float64Array = (0..colunas.len())
+            .map(|i| {
+                let unidades = unidades.is_valid(i).then(|| unidades.value(i))?;
+                (terreno.value(i) > 0.0).then(|| {
+                    unidades as f64 * self.pessoas_por_unidade / (terreno.value(i) / 10_000.0)
+                })
+            })
+            .collect();
+        let violacoes = BooleanArray::from_unary(&densidade, |d| d > self.habitantes_por_hectare);
+        Some(mensagens(&violacoes, |i| {
+            format!(
+                "Densidade populacional ({:.1} hab/ha) excede o máximo permitido ({:.1} hab/ha)",
+                densidade.value(i),
+                self.habitantes_por_hectare
+            )
+        }))
+    }
+    fn nome(&self) -> &'static str {
+        "RegraDensidadeMax"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &[
+            "numero_de_unidades",
+            "unidades_por_torre",
+            "area_do_terreno",
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_empreendimento() -> Empreendimento {
+        Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            area_construida: Some(2500.0),
+            area_permeavel: Some(200.0),
+            area_de_projecao: Some(550.0),
+            zona: Some("ZM".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_regra_coeficiente_aproveitamento() {
+        let regra = RegraCoeficienteAproveitamentoMax(2.0);
+        let mut e = make_empreendimento();
+
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Coeficiente de aproveitamento (2.50) excede o máximo permitido (2.00)"
+        );
+
+        e.area_construida = Some(2000.0);
+        assert!(regra.validar(&e).is_none());
+
+        e.area_construida = None;
+        assert!(
+            regra.validar(&e).is_none(),
+            "Sem área construída não há CA a validar"
+        );
+    }
+
+    #[test]
+    fn test_regra_taxa_ocupacao() {
+        let regra = RegraTaxaOcupacaoMax(0.5);
+        let mut e = make_empreendimento();
+
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Taxa de ocupação (55%) excede o máximo permitido (50%)"
+        );
+
+        e.area_de_projecao = Some(500.0);
+        assert!(regra.validar(&e).is_none());
+
+        // Sem projeção, a ocupação vem das torres: 2 x 300m² em 1000m².
+        e.area_de_projecao = None;
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Taxa de ocupação (60%) excede o máximo permitido (50%)"
+        );
+        assert!(regra.campos().contains(&"numero_de_torres"));
+        assert!(regra.campos().contains(&"area_da_torre"));
+    }
+
+    #[test]
+    fn test_regra_taxa_permeabilidade() {
+        let regra = RegraTaxaPermeabilidadeMin(0.25);
+        let mut e = make_empreendimento();
+
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Taxa de permeabilidade (20%) abaixo do mínimo exigido (25%)"
+        );
+
+        e.area_permeavel = Some(250.0);
+        assert!(regra.validar(&e).is_none());
+
+        e.area_permeavel = None;
+        assert!(regra.validar(&e).is_none());
+    }
+
+    #[test]
+    fn test_regra_densidade_max() {
+        let regra = RegraDensidadeMax {
+            habitantes_por_hectare: 1000.0,
+            pessoas_por_unidade: 3.5,
+        };
+        let mut e = make_empreendimento();
+        e.numero_de_unidades = Some(40);
+
+        // 40 * 3.5 = 140 habitantes em 0.1 ha
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Densidade populacional (1400.0 hab/ha) excede o máximo permitido (1000.0 hab/ha)"
+        );
+
+        e.numero_de_unidades = Some(25);
+        assert!(regra.validar(&e).is_none());
+
+        e.numero_de_unidades = None;
+        assert!(regra.validar(&e).is_none(), "Sem unidades não há densidade");
+    }
+
+    #[test]
+    fn test_regra_total_de_unidades_consistente() {
+        let regra = RegraTotalDeUnidadesConsistente;
+        let mut e = make_empreendimento();
+        e.unidades_por_torre = Some(30);
+
+        assert_eq!(e.total_de_unidades(), Some(60));
+        assert!(
+            regra.validar(&e).is_none(),
+            "Sem total informado não há o que comparar"
+        );
+
+        e.numero_de_unidades = Some(60);
+        assert!(regra.validar(&e).is_none());
+
+        e.numero_de_unidades = Some(50);
+        assert_eq!(e.total_de_unidades(), Some(50));
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Total de unidades informado (50) difere de 30 por torre em 2 torres (60)"
+        );
+
+        e.unidades_por_torre = Some(u32::MAX);
+        e.numero_de_unidades = None;
+        assert_eq!(e.total_de_unidades(), Some(2 * u32::MAX as u64));
+        e.numero_de_unidades = Some(u32::MAX);
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            format!(
+                "Total de unidades informado ({}) difere de {} por torre em 2 torres ({})",
+                u32::MAX,
+                u32::MAX,
+                2 * u32::MAX as u64
+            )
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use super::padrao::RegraNegocio;
+use crate::models::empreendimento::Empreendimento;
+
+/// Diferença relativa máxima aceita entre a área declarada e a calculada do lote.
+#[derive(Debug)]
+pub struct RegraAreaDoTerrenoConsistente(pub f64);
+/// Testada mínima do lote, em metros.
+#[derive(Debug)]
+pub struct RegraTestadaMin(pub f64);
+/// Largura mínima do lote, em metros.
+#[derive(Debug)]
+pub struct RegraLarguraLoteMin(pub f64);
+
+impl RegraNegocio for RegraAreaDoTerrenoConsistente {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let calculada = e.area_calculada_do_lote()?;
+        if calculada <= 0.0 {
+            return Some("Contorno do lote não delimita nenhuma área.".to_string());
+        }
+        let diferenca = (e.area_do_terreno - calculada).abs() / calculada;
+        if diferenca > self.0 {
+            Some(format!(
+                "Área do terreno declarada ({:.2} m²) difere da área calculada do lote ({:.2} m²) em {:.1}%",
+                e.area_do_terreno,
+                calculada,
+                diferenca * 100.0
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["lote"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraAreaDoTerrenoConsistente"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["area_do_terreno", "lote"]
+    }
+}
+
+impl RegraNegocio for RegraTestadaMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let testada = e.lote.as_ref()?.testada()?;
+        if testada < self.0 {
+            Some(format!(
+                "Testada do lote ({:.2}m) inferior ao mínimo exigido ({:.2}m)",
+                testada, self.0
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["lote"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraTestadaMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["lote"]
+    }
+}
+
+impl RegraNegocio for RegraLarguraLoteMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let largura = e.lote.as_ref()?.largura_minima()?;
+        if largura < self.0 {
+            Some(format!(
+                "Largura do lote ({:.2}m) inferior ao mínimo exigido ({:.2}m)",
+                largura, self.0
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["lote"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraLarguraLoteMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["lote"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+
+    fn make_empreendimento(lote: Poligono) -> Empreendimento {
+        Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 1,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            lote: Some(Lote::local(lote)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_area_do_terreno_consistente() {
+        let regra = RegraAreaDoTerrenoConsistente(0.05);
+        let mut e = make_empreendimento(Poligono(vec![
+            [0.0, 0.0],
+            [40.0, 0.0],
+            [40.0, 25.0],
+            [0.0, 25.0],
+        ]));
+
+        assert!(regra.validar(&e).is_none());
+
+        e.area_do_terreno = 1040.0;
+        assert!(regra.validar(&e).is_none(), "4% está dentro da tolerância");
+
+        e.area_do_terreno = 1200.0;
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Área do terreno declarada (1200.00 m²) difere da área calculada do lote (1000.00 m²) em 20.0%"
+        );
+
+        e.lote = None;
+        assert!(
+            regra.validar(&e).is_none(),
+            "Sem contorno não há o que comparar"
+        );
+    }
+
+    #[test]
+    fn test_testada_e_largura_min() {
+        // Lote estreito: testada de 8m e 100m de profundidade
+        let e = make_empreendimento(Poligono(vec![
+            [0.0, 0.0],
+            [8.0, 0.0],
+            [8.0, 100.0],
+            [0.0, 100.0],
+        ]));
+
+        assert_eq!(
+            RegraTestadaMin(10.0).validar(&e).unwrap(),
+            "Testada do lote (8.00m) inferior ao mínimo exigido (10.00m)"
+        );
+        assert!(RegraTestadaMin(5.0).validar(&e).is_none());
+
+        assert_eq!(
+            RegraLarguraLoteMin(10.0).validar(&e).unwrap(),
+            "Largura do lote (8.00m) inferior ao mínimo exigido (10.00m)"
+        );
+        assert!(RegraLarguraLoteMin(8.0).validar(&e).is_none());
+    }
+}
@@ -1,9 +1,15 @@
+mod afastamentos;
 mod cidades;
 mod construtoras;
 mod factory;
 mod ignoradas;
+mod indices;
+mod lote;
 mod padrao;
+mod restricoes_altura;
+mod vagas;
 
 pub use factory::RegrasFactory;
-pub use ignoradas::REGRAS_IGNORADAS_POR_CIDADE;
+pub use ignoradas::{REGRAS_IGNORADAS_POR_CIDADE, REGRAS_IGNORADAS_POR_USO};
 pub use padrao::RegraNegocio;
+pub use restricoes_altura::RegraSuperficieRestricaoAltura;
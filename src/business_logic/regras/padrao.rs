@@ -1,9 +1,34 @@
+use crate::business_logic::colunar::{Colunas, mensagens};
 use crate::models::empreendimento::Empreendimento;
+use arrow::array::{Array, BooleanArray};
 use std::fmt::Debug;
 
 pub trait RegraNegocio: Debug {
     fn validar(&self, e: &Empreendimento) -> Option<String>;
     fn nome(&self) -> &'static str;
+
+    /// Campos do empreendimento lidos pela regra, destacados nos relatórios
+    /// quando ela é violada.
+    fn campos(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Campos sem os quais a regra não se aplica.
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Avalia a regra sobre todas as linhas de uma vez, com o mesmo resultado
+    /// de `validar` para cada linha. `None` quando a regra não tem versão
+    /// colunar e deve ser avaliada linha a linha.
+    ///
+    /// Por padrão não há violações quando algum dos [`Self::campos_exigidos`]
+    /// é todo nulo no lote; caso contrário, a regra é avaliada linha a linha.
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        self.campos_exigidos()
+            .iter()
+            .find_map(|campo| colunas.sem_valores(campo))
+    }
 }
 
 #[derive(Debug)]
@@ -21,9 +46,19 @@ impl RegraNegocio for RegraAlturaMax {
             None
         }
     }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let violacoes =
+            BooleanArray::from_unary(colunas.reais("altura_da_torre"), |altura| altura >= self.0);
+        Some(mensagens(&violacoes, |_| {
+            format!("Altura da torre deve ser inferior a {}m.", self.0)
+        }))
+    }
     fn nome(&self) -> &'static str {
         "RegraAlturaMax"
     }
+    fn campos(&self) -> &'static [&'static str] {
+        &["altura_da_torre"]
+    }
 }
 
 impl RegraNegocio for RegraAreaTorresMax {
@@ -38,9 +73,25 @@ impl RegraNegocio for RegraAreaTorresMax {
             None
         }
     }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let violacoes = BooleanArray::from_binary(
+            &colunas.area_das_torres(),
+            colunas.reais("area_do_terreno"),
+            |total, terreno| total >= terreno * self.0,
+        );
+        Some(mensagens(&violacoes, |_| {
+            format!(
+                "Área total das torres não pode exceder {:.0}% do terreno.",
+                self.0 * 100.0
+            )
+        }))
+    }
     fn nome(&self) -> &'static str {
         "RegraAreaTorresMax"
     }
+    fn campos(&self) -> &'static [&'static str] {
+        &["numero_de_torres", "area_da_torre", "area_do_terreno"]
+    }
 }
 
 impl RegraNegocio for RegraAreaLazerMin {
@@ -54,9 +105,33 @@ impl RegraNegocio for RegraAreaLazerMin {
             None => Some("Faltando campo 'area-de-lazer'.".to_string()),
         }
     }
+    fn validar_colunas(&self, colunas: &Colunas) -> Option<Vec<Option<String>>> {
+        let lazer = colunas.reais("area_de_lazer");
+        let exigida = BooleanArray::from_unary(colunas.inteiros("numero_de_torres"), |t| t > 1);
+        let suficiente =
+            BooleanArray::from_binary(lazer, colunas.reais("area_do_terreno"), |area, terreno| {
+                area >= terreno * self.0
+            });
+        Some(
+            (0..colunas.len())
+                .map(|i| match (exigida.value(i), lazer.is_valid(i)) {
+                    (false, _) => None,
+                    (true, false) => Some("Faltando campo 'area-de-lazer'.".to_string()),
+                    (true, true) if suficiente.value(i) => None,
+                    (true, true) => Some(format!(
+                        "Área de lazer insuficiente: {:.2} m².",
+                        lazer.value(i)
+                    )),
+                })
+                .collect(),
+        )
+    }
     fn nome(&self) -> &'static str {
         "RegraAreaLazerMin"
     }
+    fn campos(&self) -> &'static [&'static str] {
+        &["area_de_lazer", "area_do_terreno"]
+    }
 }
 
 #[cfg(test)]
@@ -73,6 +148,7 @@ mod tests {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         }
     }
 
@@ -0,0 +1,208 @@
+use super::padrao::RegraNegocio;
+use crate::business_logic::restricoes_altura::MapaRestricoesAltura;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::lote::SistemaDeCoordenadas;
+
+/// Altura das torres limitada pelas superfícies de restrição (aeródromos,
+/// patrimônio) no ponto de implantação. Com torres em coordenadas geográficas,
+/// cada torre é avaliada no seu centroide; caso contrário, na localização do
+/// empreendimento.
+///
+/// As superfícies limitam a altitude; sem `cota_do_terreno`, um empreendimento
+/// sob alguma superfície é reprovado por falta da cota.
+#[derive(Debug)]
+pub struct RegraSuperficieRestricaoAltura<'a>(pub &'a MapaRestricoesAltura);
+
+impl RegraNegocio for RegraSuperficieRestricaoAltura<'_> {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let geograficas = e
+            .lote
+            .as_ref()
+            .is_some_and(|lote| lote.sistema == SistemaDeCoordenadas::Geografico);
+
+        let Some(cota) = e.cota_do_terreno else {
+            let superficie = match (&e.torres, geograficas) {
+                (Some(torres), true) => torres
+                    .iter()
+                    .find_map(|torre| self.0.superficie_em(torre.projecao.centroide())),
+                _ => self.0.superficie_em(e.coordenadas()?),
+            }?;
+            return Some(format!(
+                "Faltando campo 'cota-do-terreno' para comparar a altura com a superfície '{}'.",
+                superficie.nome
+            ));
+        };
+
+        let violacoes: Vec<String> = match (&e.torres, geograficas) {
+            (Some(torres), true) => torres
+                .iter()
+                .enumerate()
+                .filter_map(|(i, torre)| {
+                    let permitida = self
+                        .0
+                        .altura_permitida_em(torre.projecao.centroide(), cota)?;
+                    let altura = e.altura_de(torre);
+                    (altura > permitida.altura).then(|| {
+                        format!(
+                            "Torre {} com {:.1}m excede a altura permitida ({:.1}m) pela superfície '{}'",
+                            torre.nome.clone().unwrap_or_else(|| (i + 1).to_string()),
+                            altura,
+                            permitida.altura,
+                            permitida.superficie.nome
+                        )
+                    })
+                })
+                .collect(),
+            _ => {
+                let permitida = self.0.altura_permitida(e)?;
+                let altura = e
+                    .torres
+                    .iter()
+                    .flatten()
+                    .map(|torre| e.altura_de(torre))
+                    .fold(e.altura_da_torre, f64::max);
+                (altura > permitida.altura)
+                    .then(|| {
+                        format!(
+                            "Altura da torre ({:.1}m) excede a altura permitida ({:.1}m) pela superfície '{}'",
+                            altura, permitida.altura, permitida.superficie.nome
+                        )
+                    })
+                    .into_iter()
+                    .collect()
+            }
+        };
+
+        if violacoes.is_empty() {
+            None
+        } else {
+            Some(violacoes.join("; "))
+        }
+    }
+
+    fn nome(&self) -> &'static str {
+        "RegraSuperficieRestricaoAltura"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["altura_da_torre", "torres", "cota_do_terreno"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+    use crate::models::restricao_altura::{FormaDaSuperficie, SuperficieDeRestricao};
+    use crate::models::torre::Torre;
+
+    fn mapa() -> MapaRestricoesAltura {
+        MapaRestricoesAltura::new(vec![SuperficieDeRestricao {
+            nome: "Cone de aproximação".to_string(),
+            altitude_maxima: 20.0,
+            forma: FormaDaSuperficie::Cone {
+                origem: [-46.65, -23.62],
+                inclinacao: 0.05,
+                raio: 4000.0,
+            },
+        }])
+    }
+
+    fn quadrado(x: f64, y: f64, lado: f64) -> Poligono {
+        Poligono(vec![
+            [x, y],
+            [x + lado, y],
+            [x + lado, y + lado],
+            [x, y + lado],
+        ])
+    }
+
+    #[test]
+    fn test_altura_na_localizacao_do_empreendimento() {
+        let mapa = mapa();
+        let regra = RegraSuperficieRestricaoAltura(&mapa);
+
+        let mut e = Empreendimento {
+            altura_da_torre: 25.0,
+            latitude: Some(-23.62),
+            longitude: Some(-46.65),
+            cota_do_terreno: Some(0.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            regra.validar(&e),
+            Some(
+                "Altura da torre (25.0m) excede a altura permitida (20.0m) pela superfície 'Cone de aproximação'"
+                    .to_string()
+            )
+        );
+
+        e.altura_da_torre = 18.0;
+        assert_eq!(regra.validar(&e), None);
+
+        e.latitude = None;
+        e.longitude = None;
+        assert_eq!(
+            regra.validar(&e),
+            None,
+            "Sem localização não há o que avaliar"
+        );
+    }
+
+    #[test]
+    fn test_cada_torre_avaliada_no_seu_centroide() {
+        let mapa = mapa();
+        let regra = RegraSuperficieRestricaoAltura(&mapa);
+
+        // Torre A junto à origem do cone (20m); torre B a ~1,1km (~75m)
+        let e = Empreendimento {
+            altura_da_torre: 40.0,
+            cota_do_terreno: Some(0.0),
+            lote: Some(Lote::geografico(quadrado(-46.651, -23.621, 0.012))),
+            torres: Some(vec![
+                Torre {
+                    nome: Some("A".to_string()),
+                    projecao: quadrado(-46.6501, -23.6201, 0.0002),
+                    altura: None,
+                },
+                Torre {
+                    nome: Some("B".to_string()),
+                    projecao: quadrado(-46.6501, -23.6101, 0.0002),
+                    altura: None,
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let mensagem = regra.validar(&e).unwrap();
+        assert!(mensagem.starts_with("Torre A com 40.0m"), "{}", mensagem);
+        assert!(!mensagem.contains("Torre B"), "{}", mensagem);
+    }
+
+    #[test]
+    fn test_sem_cota_do_terreno_sob_superficie() {
+        let mapa = mapa();
+        let regra = RegraSuperficieRestricaoAltura(&mapa);
+
+        let mut e = Empreendimento {
+            altura_da_torre: 5.0,
+            latitude: Some(-23.62),
+            longitude: Some(-46.65),
+            ..Default::default()
+        };
+        assert_eq!(
+            regra.validar(&e),
+            Some(
+                "Faltando campo 'cota-do-terreno' para comparar a altura com a superfície 'Cone de aproximação'."
+                    .to_string()
+            )
+        );
+
+        e.latitude = Some(-23.0);
+        assert_eq!(
+            regra.validar(&e),
+            None,
+            "Fora das superfícies a cota não é necessária"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use super::padrao::RegraNegocio;
+use crate::models::empreendimento::Empreendimento;
+
+/// Mínimo de vagas de automóveis por unidade.
+#[derive(Debug)]
+pub struct RegraVagasPorUnidadeMin(pub f64);
+/// Máximo de vagas de automóveis por unidade.
+#[derive(Debug)]
+pub struct RegraVagasPorUnidadeMax(pub f64);
+/// Mínimo de uma vaga de automóvel a cada tantos m² de área construída.
+#[derive(Debug)]
+pub struct RegraVagasPorAreaMin(pub f64);
+/// Percentual mínimo das vagas de automóveis reservado a pessoas com deficiência.
+#[derive(Debug)]
+pub struct RegraVagasAcessiveisMin(pub f64);
+/// Vagas de motos mínimas, como percentual das vagas de automóveis.
+#[derive(Debug)]
+pub struct RegraVagasMotosMin(pub f64);
+/// Vagas de bicicletas mínimas, como percentual das vagas de automóveis.
+#[derive(Debug)]
+pub struct RegraVagasBicicletasMin(pub f64);
+
+fn vagas_exigidas(base: f64, fator: f64) -> u32 {
+    (base * fator).ceil() as u32
+}
+
+impl RegraNegocio for RegraVagasPorUnidadeMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let unidades = e.total_de_unidades()?;
+        let vagas = e.vagas_de_garagem?;
+        let minimo = vagas_exigidas(unidades as f64, self.0);
+        if vagas < minimo {
+            Some(format!(
+                "Vagas de garagem ({}) abaixo do mínimo para {} unidades ({})",
+                vagas, unidades, minimo
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraVagasPorUnidadeMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &[
+            "vagas_de_garagem",
+            "numero_de_unidades",
+            "unidades_por_torre",
+        ]
+    }
+}
+
+impl RegraNegocio for RegraVagasPorUnidadeMax {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let unidades = e.total_de_unidades()?;
+        let vagas = e.vagas_de_garagem?;
+        let maximo = (unidades as f64 * self.0).floor() as u32;
+        if vagas > maximo {
+            Some(format!(
+                "Vagas de garagem ({}) excedem o máximo para {} unidades ({})",
+                vagas, unidades, maximo
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraVagasPorUnidadeMax"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &[
+            "vagas_de_garagem",
+            "numero_de_unidades",
+            "unidades_por_torre",
+        ]
+    }
+}
+
+impl RegraNegocio for RegraVagasPorAreaMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let area = e.area_construida?;
+        let vagas = e.vagas_de_garagem?;
+        let minimo = vagas_exigidas(area, 1.0 / self.0);
+        if vagas < minimo {
+            Some(format!(
+                "Vagas de garagem ({}) abaixo do mínimo de 1 a cada {} m² construídos ({})",
+                vagas, self.0, minimo
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraVagasPorAreaMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem", "area_construida"]
+    }
+}
+
+impl RegraNegocio for RegraVagasAcessiveisMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let vagas = e.vagas_de_garagem?;
+        let minimo = vagas_exigidas(vagas as f64, self.0);
+        let acessiveis = e.vagas_acessiveis.unwrap_or(0);
+        if acessiveis < minimo {
+            Some(format!(
+                "Vagas acessíveis ({}) abaixo do mínimo de {:.0}% das vagas ({})",
+                acessiveis,
+                self.0 * 100.0,
+                minimo
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraVagasAcessiveisMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["vagas_acessiveis", "vagas_de_garagem"]
+    }
+}
+
+impl RegraNegocio for RegraVagasMotosMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let vagas = e.vagas_de_garagem?;
+        let minimo = vagas_exigidas(vagas as f64, self.0);
+        let motos = e.vagas_de_motos.unwrap_or(0);
+        if motos < minimo {
+            Some(format!(
+                "Vagas de motos ({}) abaixo do mínimo de {:.0}% das vagas ({})",
+                motos,
+                self.0 * 100.0,
+                minimo
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraVagasMotosMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["vagas_de_motos", "vagas_de_garagem"]
+    }
+}
+
+impl RegraNegocio for RegraVagasBicicletasMin {
+    fn validar(&self, e: &Empreendimento) -> Option<String> {
+        let vagas = e.vagas_de_garagem?;
+        let minimo = vagas_exigidas(vagas as f64, self.0);
+        let bicicletas = e.vagas_de_bicicletas.unwrap_or(0);
+        if bicicletas < minimo {
+            Some(format!(
+                "Vagas de bicicletas ({}) abaixo do mínimo de {:.0}% das vagas ({})",
+                bicicletas,
+                self.0 * 100.0,
+                minimo
+            ))
+        } else {
+            None
+        }
+    }
+    fn campos_exigidos(&self) -> &'static [&'static str] {
+        &["vagas_de_garagem"]
+    }
+    fn nome(&self) -> &'static str {
+        "RegraVagasBicicletasMin"
+    }
+    fn campos(&self) -> &'static [&'static str] {
+        &["vagas_de_bicicletas", "vagas_de_garagem"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_empreendimento() -> Empreendimento {
+        Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            area_construida: Some(4000.0),
+            numero_de_unidades: Some(40),
+            vagas_de_garagem: Some(50),
+            vagas_acessiveis: Some(1),
+            vagas_de_motos: Some(3),
+            vagas_de_bicicletas: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_regra_vagas_por_unidade_min() {
+        let regra = RegraVagasPorUnidadeMin(1.5);
+        let mut e = make_empreendimento();
+
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Vagas de garagem (50) abaixo do mínimo para 40 unidades (60)"
+        );
+
+        e.vagas_de_garagem = Some(60);
+        assert!(regra.validar(&e).is_none());
+
+        e.numero_de_unidades = None;
+        assert!(
+            regra.validar(&e).is_none(),
+            "Sem unidades não há o que validar"
+        );
+    }
+
+    #[test]
+    fn test_regra_vagas_por_unidade_max() {
+        let regra = RegraVagasPorUnidadeMax(1.0);
+        let mut e = make_empreendimento();
+
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Vagas de garagem (50) excedem o máximo para 40 unidades (40)"
+        );
+
+        e.vagas_de_garagem = Some(40);
+        assert!(regra.validar(&e).is_none());
+    }
+
+    #[test]
+    fn test_regra_vagas_por_area_min() {
+        let regra = RegraVagasPorAreaMin(75.0);
+        let mut e = make_empreendimento();
+
+        // 4000 / 75 = 53.3 -> 54 vagas
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Vagas de garagem (50) abaixo do mínimo de 1 a cada 75 m² construídos (54)"
+        );
+
+        e.vagas_de_garagem = Some(54);
+        assert!(regra.validar(&e).is_none());
+
+        e.area_construida = None;
+        assert!(regra.validar(&e).is_none());
+    }
+
+    #[test]
+    fn test_regra_vagas_acessiveis_min() {
+        let regra = RegraVagasAcessiveisMin(0.02);
+        let mut e = make_empreendimento();
+
+        // 2% de 50 = 1 vaga
+        assert!(regra.validar(&e).is_none());
+
+        e.vagas_acessiveis = None;
+        assert_eq!(
+            regra.validar(&e).unwrap(),
+            "Vagas acessíveis (0) abaixo do mínimo de 2% das vagas (1)"
+        );
+    }
+
+    #[test]
+    fn test_regras_vagas_motos_e_bicicletas() {
+        let motos = RegraVagasMotosMin(0.1);
+        let bicicletas = RegraVagasBicicletasMin(0.1);
+        let mut e = make_empreendimento();
+
+        assert_eq!(
+            motos.validar(&e).unwrap(),
+            "Vagas de motos (3) abaixo do mínimo de 10% das vagas (5)"
+        );
+        assert!(bicicletas.validar(&e).is_none());
+
+        e.vagas_de_garagem = None;
+        assert!(motos.validar(&e).is_none());
+        assert!(bicicletas.validar(&e).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::file_reader::read_restricoes_altura;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::Coordenada;
+use crate::models::restricao_altura::SuperficieDeRestricao;
+use std::error::Error;
+
+/// Altura máxima permitida em um ponto e a superfície que a determina.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlturaPermitida<'a> {
+    pub altura: f64,
+    pub superficie: &'a SuperficieDeRestricao,
+}
+
+/// Conjunto de superfícies de restrição de altura de uma região.
+#[derive(Debug)]
+pub struct MapaRestricoesAltura {
+    superficies: Vec<SuperficieDeRestricao>,
+}
+
+impl MapaRestricoesAltura {
+    pub fn new(superficies: Vec<SuperficieDeRestricao>) -> Self {
+        Self { superficies }
+    }
+
+    /// Carrega as superfícies de uma `FeatureCollection` GeoJSON.
+    pub fn carregar(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(read_restricoes_altura(path)?))
+    }
+
+    /// Altura permitida acima do terreno no ponto `[longitude, latitude]`: a
+    /// menor altitude entre as superfícies que cobrem o ponto, descontada a
+    /// cota do terreno.
+    pub fn altura_permitida_em(
+        &self,
+        ponto: Coordenada,
+        cota_do_terreno: f64,
+    ) -> Option<AlturaPermitida<'_>> {
+        self.superficies
+            .iter()
+            .filter_map(|superficie| {
+                superficie
+                    .altitude_permitida_em(ponto)
+                    .map(|altitude| AlturaPermitida {
+                        altura: altitude - cota_do_terreno,
+                        superficie,
+                    })
+            })
+            .min_by(|a, b| a.altura.total_cmp(&b.altura))
+    }
+
+    /// Altura permitida na localização do empreendimento; `None` também sem
+    /// `cota_do_terreno`, sem a qual a altitude das superfícies não pode ser
+    /// convertida em altura acima do terreno.
+    pub fn altura_permitida(&self, e: &Empreendimento) -> Option<AlturaPermitida<'_>> {
+        self.altura_permitida_em(e.coordenadas()?, e.cota_do_terreno?)
+    }
+
+    /// Superfície de menor altitude que cobre o ponto, se houver.
+    pub fn superficie_em(&self, ponto: Coordenada) -> Option<&SuperficieDeRestricao> {
+        self.altura_permitida_em(ponto, 0.0)
+            .map(|permitida| permitida.superficie)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::geometria::Poligono;
+    use crate::models::restricao_altura::FormaDaSuperficie;
+
+    fn mapa() -> MapaRestricoesAltura {
+        MapaRestricoesAltura::new(vec![
+            SuperficieDeRestricao {
+                nome: "Horizontal interna".to_string(),
+                altitude_maxima: 845.0,
+                forma: FormaDaSuperficie::Area {
+                    contorno: Poligono(vec![
+                        [-46.7, -23.7],
+                        [-46.6, -23.7],
+                        [-46.6, -23.6],
+                        [-46.7, -23.6],
+                    ]),
+                    furos: Vec::new(),
+                },
+            },
+            SuperficieDeRestricao {
+                nome: "Aproximação".to_string(),
+                altitude_maxima: 800.0,
+                forma: FormaDaSuperficie::Cone {
+                    origem: [-46.65, -23.65],
+                    inclinacao: 0.02,
+                    raio: 3000.0,
+                },
+            },
+        ])
+    }
+
+    #[test]
+    fn test_menor_altura_entre_superficies() {
+        let mapa = mapa();
+
+        let na_origem = mapa.altura_permitida_em([-46.65, -23.65], 760.0).unwrap();
+        assert_eq!(na_origem.altura, 40.0);
+        assert_eq!(na_origem.superficie.nome, "Aproximação");
+
+        let fora_do_cone = mapa.altura_permitida_em([-46.69, -23.69], 760.0).unwrap();
+        assert_eq!(fora_do_cone.altura, 85.0);
+        assert_eq!(fora_do_cone.superficie.nome, "Horizontal interna");
+
+        assert!(mapa.altura_permitida_em([-46.5, -23.5], 760.0).is_none());
+    }
+
+    #[test]
+    fn test_altura_permitida_para_empreendimento() {
+        let mapa = mapa();
+
+        let e = Empreendimento {
+            latitude: Some(-23.65),
+            longitude: Some(-46.65),
+            cota_do_terreno: Some(770.0),
+            ..Default::default()
+        };
+        assert_eq!(mapa.altura_permitida(&e).unwrap().altura, 30.0);
+
+        let sem_localizacao = Empreendimento::default();
+        assert!(mapa.altura_permitida(&sem_localizacao).is_none());
+
+        let sem_cota = Empreendimento {
+            cota_do_terreno: None,
+            ..e
+        };
+        assert!(mapa.altura_permitida(&sem_cota).is_none());
+        assert_eq!(
+            mapa.superficie_em([-46.65, -23.65]).unwrap().nome,
+            "Aproximação"
+        );
+    }
+}
@@ -1,32 +1,103 @@
-use crate::business_logic::regras::REGRAS_IGNORADAS_POR_CIDADE;
-use crate::business_logic::regras::RegraNegocio;
+use crate::business_logic::indices::IndicesUrbanisticos;
 use crate::business_logic::regras::RegrasFactory;
+use crate::business_logic::regras::{REGRAS_IGNORADAS_POR_CIDADE, REGRAS_IGNORADAS_POR_USO};
+use crate::business_logic::regras::{RegraNegocio, RegraSuperficieRestricaoAltura};
+use crate::business_logic::restricoes_altura::MapaRestricoesAltura;
+use crate::business_logic::zoneamento::MapaZoneamento;
 use crate::models::empreendimento::Empreendimento;
+use crate::models::uso::Uso;
+use std::fmt;
 
 pub struct ValidationResult {
     pub empreendimento: String,
     pub regras_ok: bool,
     pub mensagens: Vec<String>,
+    /// Regra de cada mensagem, na mesma ordem de `mensagens`.
+    pub violacoes: Vec<Violacao>,
+    pub zona: Option<String>,
+    /// Altura máxima permitida pelas superfícies de restrição no local, quando avaliada.
+    pub altura_permitida: Option<f64>,
+    pub indices: IndicesUrbanisticos,
+}
+
+/// Regra violada e os campos que ela avalia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violacao {
+    pub regra: &'static str,
+    pub campos: &'static [&'static str],
+}
+
+impl Violacao {
+    pub fn de(regra: &dyn RegraNegocio) -> Self {
+        Violacao {
+            regra: regra.nome(),
+            campos: regra.campos(),
+        }
+    }
+}
+
+/// Gravidade do resultado pela quantidade de regras violadas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severidade {
+    Nenhuma,
+    /// Uma ou duas regras violadas, em geral ajustes pontuais de projeto.
+    Moderada,
+    /// Três ou mais regras violadas.
+    Grave,
+}
+
+impl Severidade {
+    pub fn como_str(&self) -> &'static str {
+        match self {
+            Severidade::Nenhuma => "nenhuma",
+            Severidade::Moderada => "moderada",
+            Severidade::Grave => "grave",
+        }
+    }
+}
+
+impl fmt::Display for Severidade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.como_str())
+    }
+}
+
+impl ValidationResult {
+    pub fn severidade(&self) -> Severidade {
+        match self.mensagens.len() {
+            0 => Severidade::Nenhuma,
+            1..=2 => Severidade::Moderada,
+            _ => Severidade::Grave,
+        }
+    }
+}
+
+/// Camadas geográficas opcionais consultadas pela localização do empreendimento.
+#[derive(Default, Clone, Copy)]
+pub struct CamadasGeograficas<'a> {
+    pub zoneamento: Option<&'a MapaZoneamento>,
+    pub restricoes_altura: Option<&'a MapaRestricoesAltura>,
 }
 
 pub fn validar_empreendimento(e: &Empreendimento) -> ValidationResult {
-    let mut mensagens = Vec::new();
+    validar_com_regras_adicionais(e, Vec::new())
+}
 
-    let ignoradas = REGRAS_IGNORADAS_POR_CIDADE
-        .get(e.cidade.as_str())
-        .map(|v| v.as_slice())
-        .unwrap_or(&[]);
+fn validar_com_regras_adicionais(
+    e: &Empreendimento,
+    adicionais: Vec<Box<dyn RegraNegocio + '_>>,
+) -> ValidationResult {
+    let mut mensagens = Vec::new();
+    let mut violacoes = Vec::new();
 
-    let todas: Vec<Box<dyn RegraNegocio>> = RegrasFactory::padrao()
-        .into_iter()
-        .chain(RegrasFactory::por_cidade(&e.cidade))
-        .chain(RegrasFactory::por_construtora(&e.construtora))
-        .filter(|r| !ignoradas.contains(&r.nome()))
-        .collect();
+    let mut todas: Vec<Box<dyn RegraNegocio + '_>> =
+        regras_aplicaveis(&e.cidade, e.zona.as_deref(), &e.construtora, e.uso);
+    todas.extend(adicionais);
 
     for regra in todas {
         if let Some(msg) = regra.validar(e) {
             mensagens.push(msg);
+            violacoes.push(Violacao::de(regra.as_ref()));
         }
     }
 
@@ -34,12 +105,154 @@ pub fn validar_empreendimento(e: &Empreendimento) -> ValidationResult {
         empreendimento: e.construtora.clone(),
         regras_ok: mensagens.is_empty(),
         mensagens,
+        violacoes,
+        zona: e.zona.clone(),
+        altura_permitida: None,
+        indices: IndicesUrbanisticos::calcular(e),
+    }
+}
+
+/// Regras que valem para a combinação de cidade, zona, construtora e uso,
+/// na ordem em que são avaliadas, já sem as regras ignoradas.
+pub(crate) fn regras_aplicaveis(
+    cidade: &str,
+    zona: Option<&str>,
+    construtora: &str,
+    uso: Uso,
+) -> Vec<Box<dyn RegraNegocio>> {
+    let ignoradas = REGRAS_IGNORADAS_POR_CIDADE
+        .get(cidade)
+        .map(|v| v.as_slice())
+        .unwrap_or(&[]);
+
+    let ignoradas_por_uso = REGRAS_IGNORADAS_POR_USO
+        .get(&uso)
+        .map(|v| v.as_slice())
+        .unwrap_or(&[]);
+
+    let todas: Vec<Box<dyn RegraNegocio>> = RegrasFactory::padrao()
+        .into_iter()
+        .chain(RegrasFactory::por_cidade(cidade))
+        .chain(
+            zona.map(|zona| RegrasFactory::por_zona(cidade, zona))
+                .unwrap_or_default(),
+        )
+        .chain(RegrasFactory::por_construtora(construtora))
+        .collect();
+
+    let ignoradas: Vec<&str> = ignoradas.iter().chain(ignoradas_por_uso).copied().collect();
+    sobrepor_e_ignorar(todas, RegrasFactory::por_uso(cidade, uso), &ignoradas)
+}
+
+/// Substitui as regras de mesmo nome pelas variantes do uso e só então
+/// descarta as ignoradas, para que uma variante não traga de volta uma regra
+/// que a cidade ou o uso ignoram.
+fn sobrepor_e_ignorar(
+    mut todas: Vec<Box<dyn RegraNegocio>>,
+    por_uso: Vec<Box<dyn RegraNegocio>>,
+    ignoradas: &[&str],
+) -> Vec<Box<dyn RegraNegocio>> {
+    for regra in por_uso {
+        todas.retain(|r| r.nome() != regra.nome());
+        todas.push(regra);
     }
+    todas.retain(|r| !ignoradas.contains(&r.nome()));
+    todas
 }
 
 pub fn validar_empreendimentos(empreendimentos: &[Empreendimento]) -> Vec<ValidationResult> {
+    empreendimentos.iter().map(validar_empreendimento).collect()
+}
+
+/// Valida usando a zona obtida da camada de zoneamento pela localização de cada
+/// empreendimento; sem localização ou fora da camada, vale a zona informada.
+pub fn validar_empreendimentos_com_zoneamento(
+    empreendimentos: &[Empreendimento],
+    mapa: &MapaZoneamento,
+) -> Vec<ValidationResult> {
+    validar_empreendimentos_com_camadas(
+        empreendimentos,
+        CamadasGeograficas {
+            zoneamento: Some(mapa),
+            ..Default::default()
+        },
+    )
+}
+
+/// Valida consultando as camadas geográficas informadas: a zona vem do
+/// zoneamento e a altura das torres é limitada pelas superfícies de restrição.
+pub fn validar_empreendimentos_com_camadas(
+    empreendimentos: &[Empreendimento],
+    camadas: CamadasGeograficas,
+) -> Vec<ValidationResult> {
     empreendimentos
         .iter()
-        .map(|e| validar_empreendimento(e))
+        .map(|e| {
+            let zona = camadas.zoneamento.and_then(|mapa| mapa.zona_de(e));
+            let e = match zona {
+                Some(zona) => &Empreendimento {
+                    zona: Some(zona.to_string()),
+                    ..e.clone()
+                },
+                None => e,
+            };
+
+            let mut adicionais: Vec<Box<dyn RegraNegocio>> = Vec::new();
+            if let Some(mapa) = camadas.restricoes_altura {
+                adicionais.push(Box::new(RegraSuperficieRestricaoAltura(mapa)));
+            }
+
+            let mut resultado = validar_com_regras_adicionais(e, adicionais);
+            resultado.altura_permitida = camadas
+                .restricoes_altura
+                .and_then(|mapa| mapa.altura_permitida(e))
+                .map(|permitida| permitida.altura);
+            resultado
+        })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nomes(regras: &[Box<dyn RegraNegocio>]) -> Vec<&'static str> {
+        regras.iter().map(|r| r.nome()).collect()
+    }
+
+    #[test]
+    fn test_variante_do_uso_substitui_a_regra() {
+        let regras = sobrepor_e_ignorar(
+            RegrasFactory::padrao(),
+            RegrasFactory::por_uso("Rio de Janeiro", Uso::Comercial),
+            &[],
+        );
+        assert_eq!(
+            nomes(&regras)
+                .iter()
+                .filter(|n| **n == "RegraAlturaMax")
+                .count(),
+            1
+        );
+        assert_eq!(nomes(&regras).last(), Some(&"RegraAlturaMax"));
+    }
+
+    #[test]
+    fn test_variante_do_uso_nao_traz_de_volta_regra_ignorada() {
+        let regras = sobrepor_e_ignorar(
+            RegrasFactory::padrao(),
+            RegrasFactory::por_uso("Rio de Janeiro", Uso::Comercial),
+            &["RegraAlturaMax"],
+        );
+        assert!(!nomes(&regras).contains(&"RegraAlturaMax"));
+        assert!(nomes(&regras).contains(&"RegraAreaLazerMin"));
+    }
+
+    #[test]
+    fn test_densidade_ignorada_no_uso_comercial() {
+        let regras = |uso| nomes(&regras_aplicaveis("São Paulo", Some("ZER"), "Alfa", uso));
+        assert!(regras(Uso::Residencial).contains(&"RegraDensidadeMax"));
+        assert!(regras(Uso::Misto).contains(&"RegraDensidadeMax"));
+        assert!(!regras(Uso::Comercial).contains(&"RegraDensidadeMax"));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::file_reader::read_zoneamento;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::Coordenada;
+use crate::models::zoneamento::AreaDeZoneamento;
+use rstar::{AABB, RTree, RTreeObject};
+use std::error::Error;
+
+/// Camada de zoneamento indexada espacialmente para consulta por ponto.
+pub struct MapaZoneamento {
+    indice: RTree<AreaIndexada>,
+}
+
+struct AreaIndexada {
+    area: AreaDeZoneamento,
+    area_do_contorno: f64,
+    envelope: AABB<Coordenada>,
+}
+
+impl RTreeObject for AreaIndexada {
+    type Envelope = AABB<Coordenada>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+impl MapaZoneamento {
+    pub fn new(areas: Vec<AreaDeZoneamento>) -> Self {
+        let areas = areas
+            .into_iter()
+            .map(|area| {
+                let (min, max) = area.limites();
+                AreaIndexada {
+                    area_do_contorno: area.contorno.area(),
+                    envelope: AABB::from_corners(min, max),
+                    area,
+                }
+            })
+            .collect();
+        Self {
+            indice: RTree::bulk_load(areas),
+        }
+    }
+
+    /// Carrega a camada de um GeoJSON ou Shapefile, com a zona no atributo `campo_zona`.
+    pub fn carregar(path: &str, campo_zona: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(read_zoneamento(path, campo_zona)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.indice.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Zona que contém o ponto `[longitude, latitude]`. Quando polígonos se
+    /// sobrepõem, prevalece o menor, que é o mais específico.
+    pub fn zona_em(&self, ponto: Coordenada) -> Option<&str> {
+        self.indice
+            .locate_in_envelope_intersecting(&AABB::from_point(ponto))
+            .filter(|indexada| indexada.area.contem(ponto))
+            .min_by(|a, b| a.area_do_contorno.total_cmp(&b.area_do_contorno))
+            .map(|indexada| indexada.area.zona.as_str())
+    }
+
+    /// Zona do empreendimento pela sua localização ou pelo centroide do lote.
+    pub fn zona_de(&self, e: &Empreendimento) -> Option<&str> {
+        e.coordenadas().and_then(|ponto| self.zona_em(ponto))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+
+    fn quadrado(zona: &str, x: f64, y: f64, lado: f64) -> AreaDeZoneamento {
+        AreaDeZoneamento {
+            zona: zona.to_string(),
+            contorno: Poligono(vec![
+                [x, y],
+                [x + lado, y],
+                [x + lado, y + lado],
+                [x, y + lado],
+            ]),
+            furos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_zona_em_ponto() {
+        let mut com_furo = quadrado("ZM", 0.0, 0.0, 10.0);
+        com_furo.furos.push(quadrado("", 4.0, 4.0, 2.0).contorno);
+        let mapa = MapaZoneamento::new(vec![com_furo, quadrado("ZEU", 10.0, 0.0, 10.0)]);
+
+        assert_eq!(mapa.zona_em([2.0, 2.0]), Some("ZM"));
+        assert_eq!(mapa.zona_em([15.0, 5.0]), Some("ZEU"));
+        assert_eq!(mapa.zona_em([5.0, 5.0]), None, "Ponto no furo");
+        assert_eq!(mapa.zona_em([50.0, 50.0]), None);
+    }
+
+    #[test]
+    fn test_zona_mais_especifica_prevalece() {
+        let mapa = MapaZoneamento::new(vec![
+            quadrado("ZM", 0.0, 0.0, 100.0),
+            quadrado("ZER", 10.0, 10.0, 5.0),
+        ]);
+
+        assert_eq!(mapa.zona_em([12.0, 12.0]), Some("ZER"));
+        assert_eq!(mapa.zona_em([50.0, 50.0]), Some("ZM"));
+    }
+
+    #[test]
+    fn test_zona_de_empreendimento() {
+        let mapa = MapaZoneamento::new(vec![quadrado("ZR", -43.3, -23.0, 0.1)]);
+
+        let por_coordenadas = Empreendimento {
+            latitude: Some(-22.95),
+            longitude: Some(-43.25),
+            ..Default::default()
+        };
+        assert_eq!(mapa.zona_de(&por_coordenadas), Some("ZR"));
+
+        let por_lote = Empreendimento {
+            lote: Some(Lote::geografico(Poligono(vec![
+                [-43.251, -22.951],
+                [-43.249, -22.951],
+                [-43.249, -22.949],
+                [-43.251, -22.949],
+            ]))),
+            ..Default::default()
+        };
+        assert_eq!(mapa.zona_de(&por_lote), Some("ZR"));
+
+        let lote_local = Empreendimento {
+            lote: Some(Lote::local(Poligono(vec![
+                [0.0, 0.0],
+                [10.0, 0.0],
+                [10.0, 10.0],
+            ]))),
+            ..Default::default()
+        };
+        assert_eq!(mapa.zona_de(&lote_local), None);
+    }
+
+    #[test]
+    fn test_grade_com_milhares_de_zonas() {
+        let lado = 100;
+        let areas = (0..lado * lado)
+            .map(|i| {
+                let (x, y) = ((i % lado) as f64, (i / lado) as f64);
+                quadrado(&format!("Z{}", i), x, y, 1.0)
+            })
+            .collect();
+        let mapa = MapaZoneamento::new(areas);
+        assert_eq!(mapa.len(), 10_000);
+
+        for i in (0..lado * lado).step_by(7) {
+            let (x, y) = ((i % lado) as f64 + 0.5, (i / lado) as f64 + 0.5);
+            assert_eq!(mapa.zona_em([x, y]), Some(format!("Z{}", i).as_str()));
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use super::Saida;
+use crate::models::arrow_converter;
+use crate::models::empreendimento::Empreendimento;
+use arrow::ipc::writer::{FileWriter, StreamWriter};
+use std::error::Error;
+
+/// Formato do Arrow IPC: arquivo com rodapé e acesso aleatório (`.arrow`,
+/// Feather v2) ou fluxo sequencial de mensagens (`.arrows`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormatoIpc {
+    #[default]
+    Arquivo,
+    Fluxo,
+}
+
+/// Grava um arquivo Arrow IPC (Feather v2) com o esquema de
+/// [`arrow_converter::schema`], legível por `read_arrow`, pandas e Polars.
+pub fn write_arrow<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+) -> Result<(), Box<dyn Error>> {
+    write_arrow_com_formato(saida, empreendimentos, FormatoIpc::Arquivo)
+}
+
+pub fn write_arrow_com_formato<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    formato: FormatoIpc,
+) -> Result<(), Box<dyn Error>> {
+    let batch = arrow_converter::para_record_batch(empreendimentos)?;
+    let destino = saida.into().abrir()?;
+
+    let destino = match formato {
+        FormatoIpc::Arquivo => {
+            let mut writer = FileWriter::try_new(destino, batch.schema_ref())?;
+            writer.write(&batch)?;
+            writer.finish()?;
+            writer.into_inner()?
+        }
+        FormatoIpc::Fluxo => {
+            let mut writer = StreamWriter::try_new(destino, batch.schema_ref())?;
+            writer.write(&batch)?;
+            writer.finish()?;
+            writer.into_inner()?
+        }
+    };
+    destino.concluir()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_reader::read_arrow;
+    use crate::models::uso::Uso;
+    use tempfile::NamedTempFile;
+
+    fn empreendimentos() -> Vec<Empreendimento> {
+        vec![
+            Empreendimento {
+                construtora: "Teste".to_string(),
+                cidade: "São Paulo".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 2,
+                altura_da_torre: 25.0,
+                area_da_torre: 300.0,
+                area_de_lazer: Some(150.0),
+                uso: Uso::Misto,
+                zona: Some("ZM".to_string()),
+                vagas_de_garagem: Some(30),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Sem lazer".to_string(),
+                cidade: "Boituva".to_string(),
+                area_do_terreno: 800.0,
+                numero_de_torres: 1,
+                altura_da_torre: 12.0,
+                area_da_torre: 200.0,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_arrow_round_trip_nos_dois_formatos() {
+        let originais = empreendimentos();
+        for formato in [FormatoIpc::Arquivo, FormatoIpc::Fluxo] {
+            let temp_file = NamedTempFile::new().unwrap();
+            let path = temp_file.path().to_str().unwrap();
+
+            write_arrow_com_formato(path, &originais, formato).unwrap();
+            let lidos = read_arrow(path).unwrap();
+
+            assert_eq!(
+                serde_json::to_value(&lidos).unwrap(),
+                serde_json::to_value(&originais).unwrap(),
+                "{:?}",
+                formato
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_arrow_grava_formato_de_arquivo() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        write_arrow(path, &empreendimentos()).unwrap();
+
+        let bytes = std::fs::read(path).unwrap();
+        assert!(bytes.starts_with(b"ARROW1"));
+        assert!(bytes.ends_with(b"ARROW1"));
+    }
+}
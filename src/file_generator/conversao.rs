@@ -0,0 +1,619 @@
+use super::registro::{CAMPOS, EstiloNomes};
+use super::{
+    FormatoIpc, OpcoesGravacaoCsv, OpcoesXml, Saida, write_arrow_com_formato, write_csv_com_opcoes,
+    write_json, write_parquet, write_xml_com_opcoes,
+};
+use crate::file_reader::arrow_reader::ler_lotes;
+use crate::file_reader::{
+    Compressao, Entrada, FileType, OpcoesCsv, detectar_tipo, detectar_tipo_do_conteudo, read_csv,
+    read_file, read_json, read_xml,
+};
+use crate::models::arrow_converter;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::field_converter::normalize_field_name;
+use arrow::datatypes::{Field, Schema};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use bytes::Bytes;
+use csv::{ReaderBuilder, WriterBuilder};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use quick_xml::events::{BytesEnd, Event};
+use quick_xml::{Reader, Writer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Cursor;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Formatos gravados pela conversão.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatoSaida {
+    Csv,
+    Json,
+    Xml,
+    Parquet,
+    Arrow(FormatoIpc),
+}
+
+impl FormatoSaida {
+    /// Formato pela extensão do caminho: `.arrows` grava o fluxo Arrow IPC, e
+    /// `.arrow`, `.feather` e `.ipc`, o formato de arquivo. A extensão de
+    /// compressão é ignorada: `dados.json.zst` é JSON compactado.
+    pub fn pela_extensao(path: &str) -> Option<FormatoSaida> {
+        let extensao = std::path::Path::new(Compressao::sem_extensao(path))
+            .extension()
+            .and_then(|ext| ext.to_str())?
+            .to_lowercase();
+        match extensao.as_str() {
+            "feather" | "ipc" => Some(FormatoSaida::Arrow(FormatoIpc::Arquivo)),
+            outra => outra.parse().ok(),
+        }
+    }
+}
+
+impl FromStr for FormatoSaida {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        match texto.to_lowercase().as_str() {
+            "csv" => Ok(FormatoSaida::Csv),
+            "json" => Ok(FormatoSaida::Json),
+            "xml" => Ok(FormatoSaida::Xml),
+            "parquet" => Ok(FormatoSaida::Parquet),
+            "arrow" => Ok(FormatoSaida::Arrow(FormatoIpc::Arquivo)),
+            "arrows" => Ok(FormatoSaida::Arrow(FormatoIpc::Fluxo)),
+            _ => Err(format!(
+                "Formato de saída '{}' inválido. Use csv, json, xml, parquet, arrow ou arrows.",
+                texto
+            )),
+        }
+    }
+}
+
+/// Ajustes da conversão. O formato da entrada não informado é detectado pelo
+/// conteúdo e pela extensão, e o da saída, pela extensão; gravando em um
+/// `Write`, sem extensão, o formato da saída é obrigatório.
+#[derive(Debug, Clone, Default)]
+pub struct OpcoesConversao {
+    pub de: Option<FileType>,
+    pub para: Option<FormatoSaida>,
+    /// Campos opcionais mantidos, em `snake_case` ou `kebab-case`; os demais
+    /// são gravados como ausentes. Os campos obrigatórios são sempre mantidos.
+    pub campos: Option<Vec<String>>,
+    /// Estilo dos nomes das colunas do CSV e dos elementos do XML.
+    pub nomes: EstiloNomes,
+    /// Colunas da entrada renomeadas antes da leitura, da coluna para o
+    /// campo, como `{"altura": "altura-da-torre"}`.
+    pub mapa: HashMap<String, String>,
+}
+
+/// Lê `entrada` em qualquer formato suportado e grava os empreendimentos em
+/// `saida`. Devolve o número de empreendimentos convertidos.
+pub fn converter<'a, 'b>(
+    entrada: impl Into<Entrada<'a>>,
+    saida: impl Into<Saida<'b>>,
+    opcoes: &OpcoesConversao,
+) -> Result<usize, Box<dyn Error>> {
+    let saida = saida.into();
+    let para = match (opcoes.para, saida.caminho()) {
+        (Some(para), _) => para,
+        (None, Some(path)) => FormatoSaida::pela_extensao(path).ok_or_else(|| {
+            format!(
+                "Não foi possível inferir o formato de '{}' pela extensão; informe-o com --para",
+                path
+            )
+        })?,
+        (None, None) => return Err("Informe o formato da saída com --para".into()),
+    };
+
+    let (de, entrada) = match (opcoes.de, entrada.into()) {
+        (Some(de), entrada) => (de, entrada),
+        (None, Entrada::Caminho(path)) => (detectar_tipo(path)?, Entrada::Caminho(path)),
+        (None, leitor) => {
+            let bytes = leitor.bytes()?;
+            (
+                detectar_tipo_do_conteudo(&bytes)?,
+                Entrada::leitor(Cursor::new(bytes)),
+            )
+        }
+    };
+
+    let mut empreendimentos = if opcoes.mapa.is_empty() {
+        read_file(de, entrada)?
+    } else {
+        ler_com_mapa(de, entrada, &opcoes.mapa)?
+    };
+    if let Some(campos) = &opcoes.campos {
+        empreendimentos = manter_campos(&empreendimentos, campos)?;
+    }
+
+    match para {
+        FormatoSaida::Csv => {
+            let opcoes_csv = OpcoesGravacaoCsv {
+                nomes: opcoes.nomes,
+                ..Default::default()
+            };
+            write_csv_com_opcoes(saida, &empreendimentos, &opcoes_csv)?
+        }
+        FormatoSaida::Json => write_json(saida, &empreendimentos)?,
+        FormatoSaida::Xml => write_xml_com_opcoes(
+            saida,
+            &empreendimentos,
+            &OpcoesXml {
+                nomes: opcoes.nomes,
+            },
+        )?,
+        FormatoSaida::Parquet => write_parquet(saida, &empreendimentos)?,
+        FormatoSaida::Arrow(formato) => write_arrow_com_formato(saida, &empreendimentos, formato)?,
+    }
+    Ok(empreendimentos.len())
+}
+
+/// Nome `snake_case` do campo, informado em `snake_case` ou `kebab-case`.
+fn nome_do_campo(campo: &str) -> Result<&'static str, Box<dyn Error>> {
+    let nome = normalize_field_name(campo.trim());
+    CAMPOS
+        .iter()
+        .map(|(snake, _)| *snake)
+        .find(|snake| *snake == nome)
+        .ok_or_else(|| {
+            let validos: Vec<&str> = CAMPOS.iter().map(|(snake, _)| *snake).collect();
+            format!(
+                "Campo '{}' desconhecido; os campos são: {}",
+                campo,
+                validos.join(", ")
+            )
+            .into()
+        })
+}
+
+/// Lê a entrada renomeando as colunas do `mapa` para os campos correspondentes:
+/// o cabeçalho do CSV, as chaves dos objetos do JSON, os elementos de cada
+/// empreendimento no XML e as colunas do Parquet e do Arrow IPC.
+fn ler_com_mapa(
+    de: FileType,
+    entrada: Entrada,
+    mapa: &HashMap<String, String>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let mapa = mapa
+        .iter()
+        .map(|(coluna, campo)| Ok((coluna.trim(), nome_do_campo(campo)?)))
+        .collect::<Result<HashMap<&str, &str>, Box<dyn Error>>>()?;
+
+    let lotes = match de {
+        FileType::Csv => {
+            return read_csv(Entrada::leitor(Cursor::new(renomear_csv(
+                &entrada.bytes()?,
+                &mapa,
+            )?)));
+        }
+        FileType::Json => {
+            return read_json(Entrada::leitor(Cursor::new(renomear_json(
+                &entrada.bytes()?,
+                &mapa,
+            )?)));
+        }
+        FileType::Xml => {
+            return read_xml(Entrada::leitor(Cursor::new(renomear_xml(
+                &entrada.bytes()?,
+                &mapa,
+            )?)));
+        }
+        FileType::Parquet => {
+            ParquetRecordBatchReaderBuilder::try_new(Bytes::from(entrada.bytes()?))?
+                .build()?
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        FileType::Arrow => ler_lotes(Cursor::new(entrada.bytes()?))?
+            .ok_or("A entrada não é um arquivo Arrow IPC")?,
+        outro => {
+            return Err(format!(
+                "O mapeamento de colunas não se aplica a {:?}; use-o com CSV, JSON, XML, Parquet ou Arrow IPC",
+                outro
+            )
+            .into());
+        }
+    };
+
+    let mut empreendimentos = Vec::new();
+    for lote in lotes {
+        empreendimentos.extend(arrow_converter::de_record_batch(
+            &renomear_lote(&lote, &mapa)?,
+            empreendimentos.len() + 1,
+        )?);
+    }
+    Ok(empreendimentos)
+}
+
+/// CSV com o cabeçalho renomeado, no dialeto e na codificação do original.
+fn renomear_csv(bytes: &[u8], mapa: &HashMap<&str, &str>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let dialeto = OpcoesCsv::default().dialeto_para(bytes);
+    let texto = dialeto.decodificar(bytes);
+    let mut leitor = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(dialeto.delimitador)
+        .from_reader(texto.as_bytes());
+    let mut escritor = WriterBuilder::new()
+        .flexible(true)
+        .delimiter(dialeto.delimitador)
+        .from_writer(Vec::new());
+
+    for (indice, registro) in leitor.records().enumerate() {
+        let registro = registro?;
+        if indice == 0 {
+            escritor.write_record(
+                registro
+                    .iter()
+                    .map(|coluna| mapa.get(coluna.trim()).copied().unwrap_or(coluna)),
+            )?;
+        } else {
+            escritor.write_record(&registro)?;
+        }
+    }
+    dialeto.codificar(&String::from_utf8(escritor.into_inner()?)?)
+}
+
+/// JSON com as chaves de cada empreendimento renomeadas.
+fn renomear_json(bytes: &[u8], mapa: &HashMap<&str, &str>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut valor: Value = serde_json::from_slice(bytes)?;
+    if let Value::Array(empreendimentos) = &mut valor {
+        for empreendimento in empreendimentos {
+            if let Value::Object(campos) = empreendimento {
+                for (coluna, campo) in mapa {
+                    if let Some(valor) = campos.remove(*coluna) {
+                        campos.insert(campo.to_string(), valor);
+                    }
+                }
+            }
+        }
+    }
+    Ok(serde_json::to_vec(&valor)?)
+}
+
+/// XML com os elementos filhos de cada `<empreendimento>` renomeados; os
+/// elementos mais internos, como os de campos estruturados, são mantidos.
+fn renomear_xml(bytes: &[u8], mapa: &HashMap<&str, &str>) -> Result<Vec<u8>, Box<dyn Error>> {
+    // Profundidade dos campos: a raiz é 0, cada empreendimento, 1.
+    const CAMPO: usize = 2;
+    let renomeado = |nome: &[u8]| {
+        std::str::from_utf8(nome)
+            .ok()
+            .and_then(|nome| mapa.get(nome).copied())
+    };
+
+    let mut leitor = Reader::from_reader(bytes);
+    let mut escritor = Writer::new(Vec::new());
+    let mut profundidade = 0;
+    loop {
+        let evento = match leitor.read_event()? {
+            Event::Eof => break,
+            Event::Start(mut elemento) => {
+                if profundidade == CAMPO
+                    && let Some(campo) = renomeado(elemento.name().as_ref())
+                {
+                    elemento.set_name(campo.as_bytes());
+                }
+                profundidade += 1;
+                Event::Start(elemento)
+            }
+            Event::Empty(mut elemento) => {
+                if profundidade == CAMPO
+                    && let Some(campo) = renomeado(elemento.name().as_ref())
+                {
+                    elemento.set_name(campo.as_bytes());
+                }
+                Event::Empty(elemento)
+            }
+            Event::End(elemento) => {
+                profundidade -= 1;
+                match renomeado(elemento.name().as_ref()) {
+                    Some(campo) if profundidade == CAMPO => Event::End(BytesEnd::new(campo)),
+                    _ => Event::End(elemento),
+                }
+            }
+            outro => outro,
+        };
+        escritor.write_event(evento)?;
+    }
+    Ok(escritor.into_inner())
+}
+
+/// Lote com as colunas renomeadas.
+fn renomear_lote(
+    lote: &RecordBatch,
+    mapa: &HashMap<&str, &str>,
+) -> Result<RecordBatch, ArrowError> {
+    let campos: Vec<Field> = lote
+        .schema_ref()
+        .fields()
+        .iter()
+        .map(|campo| match mapa.get(campo.name().as_str()) {
+            Some(nome) => campo.as_ref().clone().with_name(*nome),
+            None => campo.as_ref().clone(),
+        })
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(campos)), lote.columns().to_vec())
+}
+
+/// Mantém apenas os campos opcionais listados em `campos`, além dos obrigatórios.
+fn manter_campos(
+    empreendimentos: &[Empreendimento],
+    campos: &[String],
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let mantidos = campos
+        .iter()
+        .map(|campo| nome_do_campo(campo))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let schema = arrow_converter::schema();
+    let descartados: Vec<&str> = CAMPOS
+        .iter()
+        .filter(|(snake, _)| {
+            !mantidos.contains(snake)
+                && schema
+                    .field_with_name(snake)
+                    .is_ok_and(|campo| campo.is_nullable())
+        })
+        .map(|(_, kebab)| *kebab)
+        .collect();
+
+    empreendimentos
+        .iter()
+        .map(|e| {
+            let mut valor = serde_json::to_value(e)?;
+            if let Value::Object(campos) = &mut valor {
+                for nome in &descartados {
+                    campos.remove(*nome);
+                }
+            }
+            Ok(serde_json::from_value(valor)?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_reader::{read_arrow, read_csv, read_json};
+    use tempfile::NamedTempFile;
+
+    fn empreendimentos() -> Vec<Empreendimento> {
+        vec![Empreendimento {
+            construtora: "Silva, Souza & Filhos".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            zona: Some("ZM".to_string()),
+            vagas_de_garagem: Some(30),
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn test_converter_infere_os_formatos_pela_extensao() {
+        let entrada = NamedTempFile::with_suffix(".json").unwrap();
+        let saida = NamedTempFile::with_suffix(".csv").unwrap();
+        let entrada = entrada.path().to_str().unwrap();
+        let saida = saida.path().to_str().unwrap();
+        write_json(entrada, &empreendimentos()).unwrap();
+
+        let convertidos = converter(entrada, saida, &OpcoesConversao::default()).unwrap();
+
+        assert_eq!(convertidos, 1);
+        assert_eq!(
+            serde_json::to_value(read_csv(saida).unwrap()).unwrap(),
+            serde_json::to_value(empreendimentos()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_converter_detecta_a_entrada_e_exige_o_formato_da_saida() {
+        let entrada = NamedTempFile::with_suffix(".txt").unwrap();
+        let saida = NamedTempFile::with_suffix(".dat").unwrap();
+        let entrada = entrada.path().to_str().unwrap();
+        let saida = saida.path().to_str().unwrap();
+        write_json(entrada, &empreendimentos()).unwrap();
+
+        let erro = converter(entrada, saida, &OpcoesConversao::default()).unwrap_err();
+        assert!(erro.to_string().contains("--para"), "{}", erro);
+
+        let opcoes = OpcoesConversao {
+            para: Some("arrows".parse().unwrap()),
+            ..Default::default()
+        };
+        converter(entrada, saida, &opcoes).unwrap();
+
+        assert!(!std::fs::read(saida).unwrap().starts_with(b"ARROW1"));
+        assert_eq!(read_arrow(saida).unwrap()[0].vagas_de_garagem, Some(30));
+    }
+
+    #[test]
+    fn test_converter_mantem_so_os_campos_escolhidos() {
+        let entrada = NamedTempFile::with_suffix(".json").unwrap();
+        let saida = NamedTempFile::with_suffix(".json").unwrap();
+        let entrada = entrada.path().to_str().unwrap();
+        let saida = saida.path().to_str().unwrap();
+        write_json(entrada, &empreendimentos()).unwrap();
+
+        let opcoes = OpcoesConversao {
+            campos: Some(vec!["area-de-lazer".to_string(), "zona".to_string()]),
+            ..Default::default()
+        };
+        converter(entrada, saida, &opcoes).unwrap();
+
+        let convertido = &read_json(saida).unwrap()[0];
+        assert_eq!(convertido.construtora, "Silva, Souza & Filhos");
+        assert_eq!(convertido.area_de_lazer, Some(150.0));
+        assert_eq!(convertido.zona.as_deref(), Some("ZM"));
+        assert_eq!(convertido.vagas_de_garagem, None);
+
+        let opcoes = OpcoesConversao {
+            campos: Some(vec!["altura".to_string()]),
+            ..Default::default()
+        };
+        let erro = converter(entrada, saida, &opcoes).unwrap_err();
+        assert!(
+            erro.to_string().starts_with("Campo 'altura' desconhecido"),
+            "{}",
+            erro
+        );
+    }
+
+    fn mapa() -> HashMap<String, String> {
+        HashMap::from([
+            ("altura".to_string(), "altura-da-torre".to_string()),
+            ("garagem".to_string(), "vagas_de_garagem".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_converter_renomeia_as_colunas_do_mapa() {
+        let csv = "construtora;cidade;area_do_terreno;numero_de_torres;altura;area_da_torre;garagem\n\
+                   Alpha;São Paulo;1.000,5;2;25,0;300,0;30\n";
+        let json = r#"[{"construtora": "Alpha", "cidade": "São Paulo", "area-do-terreno": 1000.5,
+            "numero-de-torres": 2, "altura": 25.0, "area-da-torre": 300.0, "garagem": 30}]"#;
+        let xml = "<empreendimentos><empreendimento>\
+                   <construtora>Alpha</construtora><cidade>São Paulo</cidade>\
+                   <area_do_terreno>1000.5</area_do_terreno><numero_de_torres>2</numero_de_torres>\
+                   <altura>25.0</altura><area_da_torre>300.0</area_da_torre><garagem>30</garagem>\
+                   </empreendimento></empreendimentos>";
+
+        for (de, conteudo) in [
+            (FileType::Csv, csv),
+            (FileType::Json, json),
+            (FileType::Xml, xml),
+        ] {
+            let mut saida = Vec::new();
+            let opcoes = OpcoesConversao {
+                de: Some(de),
+                para: Some(FormatoSaida::Json),
+                mapa: mapa(),
+                ..Default::default()
+            };
+            converter(
+                Entrada::leitor(conteudo.as_bytes()),
+                Saida::escritor(&mut saida),
+                &opcoes,
+            )
+            .unwrap_or_else(|e| panic!("{:?}: {}", de, e));
+
+            let convertido = &read_json(Entrada::leitor(&saida[..])).unwrap()[0];
+            assert_eq!(convertido.area_do_terreno, 1000.5, "{:?}", de);
+            assert_eq!(convertido.altura_da_torre, 25.0, "{:?}", de);
+            assert_eq!(convertido.vagas_de_garagem, Some(30), "{:?}", de);
+        }
+    }
+
+    #[test]
+    fn test_converter_renomeia_as_colunas_do_parquet() {
+        let lote = arrow_converter::para_record_batch(&empreendimentos()).unwrap();
+        let inverso = HashMap::from([
+            ("altura_da_torre", "altura"),
+            ("vagas_de_garagem", "garagem"),
+        ]);
+        let lote = renomear_lote(&lote, &inverso).unwrap();
+        let mut parquet = Vec::new();
+        let mut escritor =
+            parquet::arrow::ArrowWriter::try_new(&mut parquet, lote.schema(), None).unwrap();
+        escritor.write(&lote).unwrap();
+        escritor.close().unwrap();
+
+        let opcoes = OpcoesConversao {
+            para: Some(FormatoSaida::Json),
+            ..Default::default()
+        };
+        let erro = converter(
+            Entrada::leitor(&parquet[..]),
+            Saida::escritor(Vec::new()),
+            &opcoes,
+        )
+        .unwrap_err();
+        assert!(erro.to_string().contains("altura_da_torre"), "{}", erro);
+
+        let mut saida = Vec::new();
+        let opcoes = OpcoesConversao {
+            mapa: mapa(),
+            ..opcoes
+        };
+        converter(
+            Entrada::leitor(&parquet[..]),
+            Saida::escritor(&mut saida),
+            &opcoes,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(read_json(Entrada::leitor(&saida[..])).unwrap()).unwrap(),
+            serde_json::to_value(empreendimentos()).unwrap()
+        );
+
+        let opcoes = OpcoesConversao {
+            mapa: HashMap::from([("altura".to_string(), "pavimentos".to_string())]),
+            ..opcoes
+        };
+        let erro = converter(
+            Entrada::leitor(&parquet[..]),
+            Saida::escritor(Vec::new()),
+            &opcoes,
+        )
+        .unwrap_err();
+        assert!(
+            erro.to_string()
+                .starts_with("Campo 'pavimentos' desconhecido"),
+            "{}",
+            erro
+        );
+    }
+
+    #[test]
+    fn test_converter_da_memoria_para_a_memoria() {
+        let mut json = Vec::new();
+        write_json(Saida::escritor(&mut json), &empreendimentos()).unwrap();
+
+        let mut csv = Vec::new();
+        let erro = converter(
+            Entrada::leitor(&json[..]),
+            Saida::escritor(&mut csv),
+            &OpcoesConversao::default(),
+        )
+        .unwrap_err();
+        assert!(erro.to_string().contains("--para"), "{}", erro);
+
+        let opcoes = OpcoesConversao {
+            para: Some(FormatoSaida::Csv),
+            ..Default::default()
+        };
+        let convertidos = converter(
+            Entrada::leitor(&json[..]),
+            Saida::escritor(&mut csv),
+            &opcoes,
+        )
+        .unwrap();
+
+        assert_eq!(convertidos, 1);
+        assert_eq!(
+            serde_json::to_value(read_csv(Entrada::leitor(&csv[..])).unwrap()).unwrap(),
+            serde_json::to_value(empreendimentos()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_formato_pela_extensao() {
+        assert_eq!(FileType::pela_extensao("dados.CSV"), Some(FileType::Csv));
+        assert_eq!(FileType::pela_extensao("sp.osm.pbf"), Some(FileType::Osm));
+        assert_eq!(FileType::pela_extensao("dados.txt"), None);
+        assert_eq!(
+            FormatoSaida::pela_extensao("dados.arrows"),
+            Some(FormatoSaida::Arrow(FormatoIpc::Fluxo))
+        );
+        assert_eq!(
+            FormatoSaida::pela_extensao("dados.feather"),
+            Some(FormatoSaida::Arrow(FormatoIpc::Arquivo))
+        );
+        assert_eq!(FormatoSaida::pela_extensao("dados.xlsx"), None);
+    }
+}
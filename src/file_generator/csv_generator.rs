@@ -1,61 +1,200 @@
+use super::Saida;
+use super::registro::{self, EstiloNomes};
+use crate::models::dialeto_csv::DialetoCsv;
 use crate::models::empreendimento::Empreendimento;
+use csv::WriterBuilder;
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
 
-pub fn write_csv(path: &str, empreendimentos: &[Empreendimento]) -> Result<(), Box<dyn Error>> {
-    let mut file = File::create(path)?;
+/// Opções de gravação do CSV: o dialeto e o estilo dos nomes das colunas.
+#[derive(Debug, Clone, Default)]
+pub struct OpcoesGravacaoCsv {
+    pub dialeto: DialetoCsv,
+    pub nomes: EstiloNomes,
+}
+
+pub fn write_csv<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+) -> Result<(), Box<dyn Error>> {
+    write_csv_com_opcoes(saida, empreendimentos, &OpcoesGravacaoCsv::default())
+}
+
+/// Grava o CSV no `dialeto` informado; com [`DialetoCsv::excel_brasileiro`]
+/// o arquivo abre no Excel em pt-BR com números e acentos corretos.
+pub fn write_csv_com_dialeto<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    dialeto: &DialetoCsv,
+) -> Result<(), Box<dyn Error>> {
+    let opcoes = OpcoesGravacaoCsv {
+        dialeto: *dialeto,
+        ..Default::default()
+    };
+    write_csv_com_opcoes(saida, empreendimentos, &opcoes)
+}
+
+/// Grava o CSV pelo crate `csv`, que coloca entre aspas os valores com o
+/// delimitador, aspas ou quebras de linha; o arquivo é lido de volta por
+/// `read_csv` com os mesmos valores.
+pub fn write_csv_com_opcoes<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    opcoes: &OpcoesGravacaoCsv,
+) -> Result<(), Box<dyn Error>> {
+    let dialeto = &opcoes.dialeto;
+    let mut writer = WriterBuilder::new()
+        .delimiter(dialeto.delimitador)
+        .from_writer(Vec::new());
+    writer.write_record(opcoes.nomes.nomes())?;
 
-    writeln!(file, "construtora,cidade,area_do_terreno,numero_de_torres,altura_da_torre,area_da_torre,area_de_lazer")?;
-    
     for empreendimento in empreendimentos {
-        let area_de_lazer = empreendimento.area_de_lazer
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "".to_string());
-            
-        writeln!(
-            file,
-            "{},{},{},{},{},{},{}",
-            empreendimento.construtora,
-            empreendimento.cidade,
-            empreendimento.area_do_terreno,
-            empreendimento.numero_de_torres,
-            empreendimento.altura_da_torre,
-            empreendimento.area_da_torre,
-            area_de_lazer
-        )?;
+        let valores = registro::valores(empreendimento, |v| dialeto.formatar_numero(v));
+        writer.write_record(valores.map(Option::unwrap_or_default))?;
     }
-    
+
+    let texto = String::from_utf8(writer.into_inner()?)?;
+    saida.into().gravar(&dialeto.codificar(&texto)?)?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_reader::read_csv;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+    use crate::models::uso::Uso;
     use tempfile::NamedTempFile;
-    
-    #[test]
-    fn test_write_csv() {
-        let empreendimentos = vec![
+
+    fn com_caracteres_especiais() -> Vec<Empreendimento> {
+        vec![
             Empreendimento {
-                construtora: "Teste".to_string(),
-                cidade: "São Paulo".to_string(),
-                area_do_terreno: 1000.0,
+                construtora: "Silva, Souza & Filhos".to_string(),
+                cidade: "São Paulo; \"Centro\" <SP>".to_string(),
+                area_do_terreno: 1234.5,
                 numero_de_torres: 2,
-                altura_da_torre: 25.0,
+                altura_da_torre: 25.25,
                 area_da_torre: 300.0,
-                area_de_lazer: Some(150.0),
-            }
-        ];
-        
+                area_de_lazer: Some(0.0),
+                uso: Uso::Misto,
+                zona: Some("ZM 'A'\nquadra 2".to_string()),
+                vagas_de_garagem: Some(40),
+                lote: Some(Lote::local(Poligono(vec![
+                    [0.0, 0.0],
+                    [30.0, 0.0],
+                    [30.0, 41.15],
+                    [0.0, 41.15],
+                ]))),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Beta".to_string(),
+                cidade: "Boituva".to_string(),
+                area_do_terreno: 800.0,
+                numero_de_torres: 1,
+                altura_da_torre: 12.0,
+                area_da_torre: 200.0,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let empreendimentos = vec![Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            ..Default::default()
+        }];
+
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap();
-        
+
         let result = write_csv(path, &empreendimentos);
         assert!(result.is_ok());
-        
+
         let content = std::fs::read_to_string(path).unwrap();
         assert!(content.contains("construtora,cidade,area_do_terreno"));
         assert!(content.contains("Teste,São Paulo,1000,2,25,300,150"));
     }
+
+    #[test]
+    fn test_write_csv_com_dialeto_do_excel_brasileiro() {
+        let empreendimentos = vec![Empreendimento {
+            construtora: "Construtora; Filhos".to_string(),
+            cidade: "Jundiaí".to_string(),
+            area_do_terreno: 1200.5,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.25,
+            vagas_de_garagem: Some(1500),
+            ..Default::default()
+        }];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        let dialeto = DialetoCsv::excel_brasileiro();
+        write_csv_com_dialeto(path, &empreendimentos, &dialeto).unwrap();
+
+        let bytes = std::fs::read(path).unwrap();
+        assert!(bytes.starts_with(b"\xEF\xBB\xBFconstrutora;cidade;area_do_terreno;"));
+        let content = String::from_utf8(bytes).unwrap();
+        assert!(
+            content.contains("\"Construtora; Filhos\";Jundiaí;1200,5;2;25;300,25;;"),
+            "{}",
+            content
+        );
+        assert!(content.contains(";1500;"));
+
+        let lidos = read_csv(path).unwrap();
+        assert_eq!(
+            serde_json::to_value(&lidos).unwrap(),
+            serde_json::to_value(&empreendimentos).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_csv_round_trip_com_aspas_e_delimitadores() {
+        let originais = com_caracteres_especiais();
+        for nomes in [EstiloNomes::Snake, EstiloNomes::Kebab] {
+            for dialeto in [DialetoCsv::default(), DialetoCsv::excel_brasileiro()] {
+                let temp_file = NamedTempFile::new().unwrap();
+                let path = temp_file.path().to_str().unwrap();
+                let opcoes = OpcoesGravacaoCsv { dialeto, nomes };
+
+                write_csv_com_opcoes(path, &originais, &opcoes).unwrap();
+
+                assert_eq!(
+                    serde_json::to_value(read_csv(path).unwrap()).unwrap(),
+                    serde_json::to_value(&originais).unwrap(),
+                    "{:?}",
+                    opcoes
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_csv_estilo_kebab() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        let opcoes = OpcoesGravacaoCsv {
+            nomes: EstiloNomes::Kebab,
+            ..Default::default()
+        };
+
+        write_csv_com_opcoes(path, &com_caracteres_especiais(), &opcoes).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("construtora,cidade,area-do-terreno,numero-de-torres,"));
+        assert!(
+            content
+                .contains("\"Silva, Souza & Filhos\",\"São Paulo; \"\"Centro\"\" <SP>\",1234.5,")
+        );
+    }
 }
@@ -0,0 +1,134 @@
+use super::Saida;
+use super::mapa::{self, Geometria};
+use crate::business_logic::ValidationResult;
+use crate::models::empreendimento::Empreendimento;
+use serde_json::{Value, json};
+use std::error::Error;
+
+/// Grava os resultados da validação como GeoJSON `FeatureCollection`: cada
+/// empreendimento é um polígono (lote) ou ponto com suas propriedades, status,
+/// severidade e violações. A cor segue o padrão simplestyle (`marker-color`,
+/// `stroke`, `fill`), reconhecido por geojson.io, GitHub e QGIS.
+pub fn write_geojson<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    resultados: &[ValidationResult],
+) -> Result<(), Box<dyn Error>> {
+    let features: Vec<Value> = mapa::parear(empreendimentos, resultados)?
+        .map(|(e, resultado)| {
+            let geometry = match mapa::geometria(e) {
+                Some(Geometria::Ponto(ponto)) => json!({"type": "Point", "coordinates": ponto}),
+                Some(Geometria::Poligono(anel)) => {
+                    json!({"type": "Polygon", "coordinates": [anel]})
+                }
+                None => Value::Null,
+            };
+            let cor = mapa::cor(mapa::estilo(resultado));
+            let mut properties = mapa::propriedades(e, resultado);
+            properties.extend([
+                ("marker-color".to_string(), json!(cor)),
+                ("stroke".to_string(), json!(cor)),
+                ("fill".to_string(), json!(cor)),
+                ("fill-opacity".to_string(), json!(0.4)),
+            ]);
+            json!({"type": "Feature", "geometry": geometry, "properties": properties})
+        })
+        .collect();
+
+    let colecao = json!({"type": "FeatureCollection", "features": features});
+    saida
+        .into()
+        .gravar(serde_json::to_string_pretty(&colecao)?.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business_logic::validar_empreendimentos;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_write_geojson() {
+        let empreendimentos = vec![
+            Empreendimento {
+                construtora: "Aprovada".to_string(),
+                cidade: "Curitiba".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 1,
+                altura_da_torre: 20.0,
+                area_da_torre: 300.0,
+                area_de_lazer: Some(150.0),
+                latitude: Some(-25.43),
+                longitude: Some(-49.27),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Reprovada".to_string(),
+                cidade: "Curitiba".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 1,
+                altura_da_torre: 40.0,
+                area_da_torre: 300.0,
+                area_de_lazer: Some(150.0),
+                lote: Some(Lote::geografico(Poligono(vec![
+                    [-49.0, -25.0],
+                    [-48.9997, -25.0],
+                    [-48.9997, -24.9997],
+                ]))),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Sem localização".to_string(),
+                ..Default::default()
+            },
+        ];
+        let resultados = validar_empreendimentos(&empreendimentos);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        write_geojson(path, &empreendimentos, &resultados).unwrap();
+
+        let colecao: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(colecao["type"], "FeatureCollection");
+        let features = colecao["features"].as_array().unwrap();
+        assert_eq!(features.len(), 3);
+
+        let aprovada = &features[0];
+        assert_eq!(aprovada["geometry"]["type"], "Point");
+        assert_eq!(aprovada["geometry"]["coordinates"], json!([-49.27, -25.43]));
+        assert_eq!(aprovada["properties"]["construtora"], "Aprovada");
+        assert_eq!(aprovada["properties"]["status"], "aprovado");
+        assert_eq!(aprovada["properties"]["severidade"], "nenhuma");
+        assert_eq!(aprovada["properties"]["violacoes"], json!([]));
+        assert_eq!(aprovada["properties"]["marker-color"], "#2e7d32");
+
+        let reprovada = &features[1];
+        assert_eq!(reprovada["geometry"]["type"], "Polygon");
+        let anel = reprovada["geometry"]["coordinates"][0].as_array().unwrap();
+        assert_eq!(anel.len(), 4, "O anel do GeoJSON deve ser fechado");
+        assert_eq!(anel.first(), anel.last());
+        assert!(reprovada["properties"].get("lote").is_none());
+        assert_eq!(reprovada["properties"]["status"], "reprovado");
+        assert_eq!(reprovada["properties"]["severidade"], "moderada");
+        assert_eq!(reprovada["properties"]["fill"], "#f9a825");
+        assert!(
+            reprovada["properties"]["violacoes"][0]
+                .as_str()
+                .unwrap()
+                .contains("Altura da torre")
+        );
+
+        assert!(features[2]["geometry"].is_null());
+    }
+
+    #[test]
+    fn test_write_geojson_exige_um_resultado_por_empreendimento() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        let empreendimentos = vec![Empreendimento::default()];
+        assert!(write_geojson(path, &empreendimentos, &[]).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use super::Saida;
+use super::mapa::{self, Geometria};
+use crate::business_logic::ValidationResult;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::Coordenada;
+use quick_xml::escape::escape;
+use serde_json::Value;
+use std::error::Error;
+use std::io::{self, BufWriter, Write};
+
+/// Grava os resultados da validação em KML (Google Earth, My Maps): cada
+/// empreendimento é um `Placemark` com o lote ou ponto, estilizado pelo
+/// status, com as violações na descrição e os dados em `ExtendedData`.
+pub fn write_kml<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    resultados: &[ValidationResult],
+) -> Result<(), Box<dyn Error>> {
+    let mut file = BufWriter::new(saida.into().abrir()?);
+
+    writeln!(file, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(file, "<kml xmlns=\"http://www.opengis.net/kml/2.2\">")?;
+    writeln!(file, "  <Document>")?;
+    writeln!(file, "    <name>Resultados da Validação</name>")?;
+
+    for estilo in mapa::ESTILOS {
+        let cor = cor_kml(mapa::cor(estilo));
+        writeln!(file, "    <Style id=\"{}\">", estilo)?;
+        writeln!(file, "      <IconStyle><color>{}</color></IconStyle>", cor)?;
+        writeln!(
+            file,
+            "      <LineStyle><color>{}</color><width>2</width></LineStyle>",
+            cor
+        )?;
+        writeln!(
+            file,
+            "      <PolyStyle><color>66{}</color></PolyStyle>",
+            &cor[2..]
+        )?;
+        writeln!(file, "    </Style>")?;
+    }
+
+    for (e, resultado) in mapa::parear(empreendimentos, resultados)? {
+        writeln!(file, "    <Placemark>")?;
+        writeln!(file, "      <name>{}</name>", escape(&e.construtora))?;
+        writeln!(
+            file,
+            "      <styleUrl>#{}</styleUrl>",
+            mapa::estilo(resultado)
+        )?;
+        writeln!(
+            file,
+            "      <description>{}</description>",
+            escape(descricao(resultado))
+        )?;
+
+        writeln!(file, "      <ExtendedData>")?;
+        for (nome, valor) in mapa::propriedades(e, resultado) {
+            let valor = match valor {
+                Value::Null => continue,
+                Value::String(texto) => texto,
+                Value::Array(itens) => itens
+                    .iter()
+                    .map(|i| {
+                        i.as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| i.to_string())
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                outro => outro.to_string(),
+            };
+            writeln!(
+                file,
+                "        <Data name=\"{}\"><value>{}</value></Data>",
+                nome,
+                escape(valor)
+            )?;
+        }
+        writeln!(file, "      </ExtendedData>")?;
+
+        match mapa::geometria(e) {
+            Some(Geometria::Ponto(ponto)) => {
+                writeln!(
+                    file,
+                    "      <Point><coordinates>{}</coordinates></Point>",
+                    coordenadas(&[ponto])
+                )?;
+            }
+            Some(Geometria::Poligono(anel)) => {
+                writeln!(
+                    file,
+                    "      <Polygon><outerBoundaryIs><LinearRing><coordinates>{}</coordinates></LinearRing></outerBoundaryIs></Polygon>",
+                    coordenadas(&anel)
+                )?;
+            }
+            None => {}
+        }
+        writeln!(file, "    </Placemark>")?;
+    }
+
+    writeln!(file, "  </Document>")?;
+    writeln!(file, "</kml>")?;
+    file.into_inner()
+        .map_err(io::IntoInnerError::into_error)?
+        .concluir()?;
+    Ok(())
+}
+
+fn descricao(resultado: &ValidationResult) -> String {
+    if resultado.regras_ok {
+        return "Todas as regras foram atendidas".to_string();
+    }
+    let mut texto = format!("Regras violadas (severidade {}):", resultado.severidade());
+    for mensagem in &resultado.mensagens {
+        texto.push_str("\n- ");
+        texto.push_str(mensagem);
+    }
+    texto
+}
+
+fn coordenadas(pontos: &[Coordenada]) -> String {
+    pontos
+        .iter()
+        .map(|p| format!("{},{}", p[0], p[1]))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Converte `#rrggbb` para a ordem `aabbggrr` do KML, opaca.
+fn cor_kml(cor: &str) -> String {
+    format!("ff{}{}{}", &cor[5..7], &cor[3..5], &cor[1..3])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business_logic::validar_empreendimentos;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_cor_kml() {
+        assert_eq!(cor_kml("#2e7d32"), "ff327d2e");
+    }
+
+    #[test]
+    fn test_write_kml() {
+        let empreendimentos = vec![
+            Empreendimento {
+                construtora: "Alfa & Filhos".to_string(),
+                cidade: "Curitiba".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 1,
+                altura_da_torre: 20.0,
+                area_da_torre: 300.0,
+                area_de_lazer: Some(150.0),
+                latitude: Some(-25.43),
+                longitude: Some(-49.27),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Beta".to_string(),
+                cidade: "Boituva".to_string(),
+                area_do_terreno: 800.0,
+                numero_de_torres: 6,
+                altura_da_torre: 35.0,
+                area_da_torre: 200.0,
+                area_de_lazer: Some(50.0),
+                lote: Some(Lote::geografico(Poligono(vec![
+                    [-47.78, -23.28],
+                    [-47.7797, -23.28],
+                    [-47.7797, -23.2797],
+                ]))),
+                ..Default::default()
+            },
+        ];
+        let resultados = validar_empreendimentos(&empreendimentos);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        write_kml(path, &empreendimentos, &resultados).unwrap();
+        let kml = std::fs::read_to_string(path).unwrap();
+
+        assert!(kml.contains("<Style id=\"aprovado\">"));
+        assert!(kml.contains("<Style id=\"grave\">"));
+        assert!(kml.contains("<name>Alfa &amp; Filhos</name>"));
+        assert!(kml.contains("<styleUrl>#aprovado</styleUrl>"));
+        assert!(kml.contains("<Point><coordinates>-49.27,-25.43</coordinates></Point>"));
+        assert!(kml.contains("<styleUrl>#grave</styleUrl>"));
+        assert!(kml.contains(
+            "<coordinates>-47.78,-23.28 -47.7797,-23.28 -47.7797,-23.2797 -47.78,-23.28</coordinates>"
+        ));
+        assert!(kml.contains("Regras violadas (severidade grave):"));
+        assert!(kml.contains("<Data name=\"status\"><value>reprovado</value></Data>"));
+        assert!(!kml.contains("<Data name=\"zona\">"));
+
+        // O KML gerado deve ser XML bem formado.
+        let mut reader = quick_xml::Reader::from_str(&kml);
+        loop {
+            match reader.read_event().unwrap() {
+                quick_xml::events::Event::Eof => break,
+                _ => continue,
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::business_logic::{Severidade, ValidationResult};
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::Coordenada;
+use crate::models::lote::SistemaDeCoordenadas;
+use serde_json::{Map, Value, json};
+use std::error::Error;
+
+/// Geometria de um empreendimento no mapa, em longitude e latitude.
+pub(crate) enum Geometria {
+    Ponto(Coordenada),
+    /// Anel fechado do contorno do lote.
+    Poligono(Vec<Coordenada>),
+}
+
+/// Contorno do lote quando está em coordenadas geográficas; senão, o ponto de
+/// localização. Lotes em coordenadas locais não podem ser posicionados no mapa.
+pub(crate) fn geometria(e: &Empreendimento) -> Option<Geometria> {
+    match &e.lote {
+        Some(lote) if lote.sistema == SistemaDeCoordenadas::Geografico => {
+            let mut anel = lote.contorno.0.clone();
+            if anel.first() != anel.last() {
+                anel.push(anel[0]);
+            }
+            Some(Geometria::Poligono(anel))
+        }
+        _ => e.coordenadas().map(Geometria::Ponto),
+    }
+}
+
+/// Estilo do resultado no mapa: `aprovado` ou a severidade das violações.
+pub(crate) fn estilo(resultado: &ValidationResult) -> &'static str {
+    match resultado.severidade() {
+        Severidade::Nenhuma => "aprovado",
+        severidade => severidade.como_str(),
+    }
+}
+
+/// Cor de cada estilo em `#rrggbb`.
+pub(crate) fn cor(estilo: &str) -> &'static str {
+    match estilo {
+        "aprovado" => "#2e7d32",
+        "moderada" => "#f9a825",
+        _ => "#c62828",
+    }
+}
+
+pub(crate) const ESTILOS: [&str; 3] = ["aprovado", "moderada", "grave"];
+
+/// Dados do empreendimento e do resultado da validação, sem a geometria.
+pub(crate) fn propriedades(e: &Empreendimento, resultado: &ValidationResult) -> Map<String, Value> {
+    let mut propriedades = match serde_json::to_value(e) {
+        Ok(Value::Object(dados)) => dados,
+        _ => Map::new(),
+    };
+    propriedades.remove("lote");
+    propriedades.remove("torres");
+    propriedades.insert(
+        "zona".to_string(),
+        json!(resultado.zona.as_ref().or(e.zona.as_ref())),
+    );
+
+    let indices = &resultado.indices;
+    propriedades.extend([
+        (
+            "status".to_string(),
+            json!(if resultado.regras_ok {
+                "aprovado"
+            } else {
+                "reprovado"
+            }),
+        ),
+        (
+            "severidade".to_string(),
+            json!(resultado.severidade().como_str()),
+        ),
+        ("violacoes".to_string(), json!(resultado.mensagens)),
+        (
+            "altura-permitida".to_string(),
+            json!(resultado.altura_permitida),
+        ),
+        (
+            "coeficiente-de-aproveitamento".to_string(),
+            json!(indices.coeficiente_de_aproveitamento),
+        ),
+        (
+            "taxa-de-ocupacao".to_string(),
+            json!(indices.taxa_de_ocupacao),
+        ),
+        (
+            "taxa-de-permeabilidade".to_string(),
+            json!(indices.taxa_de_permeabilidade),
+        ),
+    ]);
+    propriedades
+}
+
+/// Pares empreendimento/resultado, que devem vir na mesma ordem.
+pub(crate) fn parear<'a>(
+    empreendimentos: &'a [Empreendimento],
+    resultados: &'a [ValidationResult],
+) -> Result<impl Iterator<Item = (&'a Empreendimento, &'a ValidationResult)>, Box<dyn Error>> {
+    if empreendimentos.len() != resultados.len() {
+        return Err(format!(
+            "{} empreendimentos para {} resultados de validação",
+            empreendimentos.len(),
+            resultados.len()
+        )
+        .into());
+    }
+    Ok(empreendimentos.iter().zip(resultados))
+}
@@ -1,35 +1,63 @@
+pub mod arrow_generator;
+pub mod conversao;
 pub mod csv_generator;
+pub mod geojson_generator;
 pub mod json_generator;
+pub mod kml_generator;
+mod mapa;
 pub mod parquet_generator;
+mod registro;
+mod saida;
+pub mod xlsx_generator;
 pub mod xml_generator;
 
-pub use csv_generator::write_csv;
+pub use arrow_generator::{FormatoIpc, write_arrow, write_arrow_com_formato};
+pub use conversao::{FormatoSaida, OpcoesConversao, converter};
+pub use csv_generator::{
+    OpcoesGravacaoCsv, write_csv, write_csv_com_dialeto, write_csv_com_opcoes,
+};
+pub use geojson_generator::write_geojson;
 pub use json_generator::write_json;
-pub use parquet_generator::write_parquet;
-pub use xml_generator::write_xml;
+pub use kml_generator::write_kml;
+pub use parquet_generator::{OpcoesParquet, write_parquet, write_parquet_com_opcoes};
+pub use registro::EstiloNomes;
+pub use saida::Saida;
+pub use xlsx_generator::write_xlsx_anotado;
+pub use xml_generator::{OpcoesXml, write_xml, write_xml_com_opcoes};
 
 use crate::models::empreendimento::Empreendimento;
 use std::error::Error;
 
-pub fn generate_all_files(empreendimentos: &[Empreendimento], base_name: &str) -> Result<(), Box<dyn Error>> {
+pub fn generate_all_files(
+    empreendimentos: &[Empreendimento],
+    base_name: &str,
+) -> Result<(), Box<dyn Error>> {
     println!("🔄 Gerando todos os tipos de arquivo...");
-    
+
     let csv_path = format!("{}.csv", base_name);
     write_csv(&csv_path, empreendimentos)?;
     println!("✅ CSV gerado: {}", csv_path);
-    
+
     let json_path = format!("{}.json", base_name);
     write_json(&json_path, empreendimentos)?;
     println!("✅ JSON gerado: {}", json_path);
-    
+
     let parquet_path = format!("{}.parquet", base_name);
     write_parquet(&parquet_path, empreendimentos)?;
     println!("✅ Parquet gerado: {}", parquet_path);
-    
+
     let xml_path = format!("{}.xml", base_name);
     write_xml(&xml_path, empreendimentos)?;
     println!("✅ XML gerado: {}", xml_path);
-    
+
+    let arrow_path = format!("{}.arrow", base_name);
+    write_arrow(&arrow_path, empreendimentos)?;
+    println!("✅ Arrow IPC gerado: {}", arrow_path);
+
+    let arrows_path = format!("{}.arrows", base_name);
+    write_arrow_com_formato(&arrows_path, empreendimentos, FormatoIpc::Fluxo)?;
+    println!("✅ Arrow IPC (fluxo) gerado: {}", arrows_path);
+
     println!("🎉 Todos os arquivos foram gerados com sucesso!");
     Ok(())
 }
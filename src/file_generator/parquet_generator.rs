@@ -1,43 +1,73 @@
+use super::Saida;
+use crate::models::arrow_converter;
 use crate::models::empreendimento::Empreendimento;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::{DEFAULT_MAX_ROW_GROUP_SIZE, WriterProperties};
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
-
-pub fn write_parquet(path: &str, empreendimentos: &[Empreendimento]) -> Result<(), Box<dyn Error>> {
-    let mut file = File::create(path)?;
-    
-    writeln!(file, "# Parquet-like format (simplified)")?;
-    writeln!(file, "# construtora,cidade,area_do_terreno,numero_de_torres,altura_da_torre,area_da_torre,area_de_lazer")?;
-    
-    for empreendimento in empreendimentos {
-        let area_de_lazer = empreendimento.area_de_lazer
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "0".to_string());
-            
-        writeln!(
-            file,
-            "{},{},{},{},{},{},{}",
-            empreendimento.construtora,
-            empreendimento.cidade,
-            empreendimento.area_do_terreno,
-            empreendimento.numero_de_torres,
-            empreendimento.altura_da_torre,
-            empreendimento.area_da_torre,
-            area_de_lazer
-        )?;
+
+/// Compressão e tamanho dos grupos de linhas do arquivo Parquet.
+#[derive(Debug, Clone)]
+pub struct OpcoesParquet {
+    pub compressao: Compression,
+    /// Máximo de linhas por grupo (row group).
+    pub linhas_por_grupo: usize,
+}
+
+impl Default for OpcoesParquet {
+    fn default() -> Self {
+        Self {
+            compressao: Compression::SNAPPY,
+            linhas_por_grupo: DEFAULT_MAX_ROW_GROUP_SIZE,
+        }
+    }
+}
+
+pub fn write_parquet<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+) -> Result<(), Box<dyn Error>> {
+    write_parquet_com_opcoes(saida, empreendimentos, &OpcoesParquet::default())
+}
+
+/// Grava um arquivo Parquet com o esquema de [`arrow_converter::schema`],
+/// legível por `read_parquet`, Spark e DuckDB.
+pub fn write_parquet_com_opcoes<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    opcoes: &OpcoesParquet,
+) -> Result<(), Box<dyn Error>> {
+    if opcoes.linhas_por_grupo == 0 {
+        return Err("O grupo de linhas do Parquet deve ter ao menos uma linha".into());
     }
-    
+    let batch = arrow_converter::para_record_batch(empreendimentos)?;
+    let propriedades = WriterProperties::builder()
+        .set_compression(opcoes.compressao)
+        .set_max_row_group_size(opcoes.linhas_por_grupo)
+        .build();
+
+    let mut writer =
+        ArrowWriter::try_new(saida.into().abrir()?, batch.schema(), Some(propriedades))?;
+    writer.write(&batch)?;
+    writer.into_inner()?.concluir()?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_reader::read_parquet;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+    use crate::models::torre::Torre;
+    use crate::models::uso::Uso;
+    use parquet::basic::ZstdLevel;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use std::fs::File;
     use tempfile::NamedTempFile;
-    
-    #[test]
-    fn test_write_parquet() {
-        let empreendimentos = vec![
+
+    fn empreendimentos() -> Vec<Empreendimento> {
+        vec![
             Empreendimento {
                 construtora: "Teste".to_string(),
                 cidade: "São Paulo".to_string(),
@@ -46,17 +76,84 @@ mod tests {
                 altura_da_torre: 25.0,
                 area_da_torre: 300.0,
                 area_de_lazer: Some(150.0),
-            }
-        ];
-        
+                area_construida: Some(2400.0),
+                uso: Uso::Misto,
+                zona: Some("ZM".to_string()),
+                latitude: Some(-23.55),
+                longitude: Some(-46.63),
+                vagas_de_garagem: Some(30),
+                torres: Some(vec![Torre {
+                    nome: Some("A".to_string()),
+                    projecao: Poligono(vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]]),
+                    altura: Some(25.0),
+                }]),
+                lote: Some(Lote::local(Poligono(vec![
+                    [0.0, 0.0],
+                    [40.0, 0.0],
+                    [40.0, 25.0],
+                ]))),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Sem lazer".to_string(),
+                cidade: "Boituva".to_string(),
+                area_do_terreno: 800.0,
+                numero_de_torres: 1,
+                altura_da_torre: 12.0,
+                area_da_torre: 200.0,
+                area_de_lazer: None,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_parquet_round_trip() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        let originais = empreendimentos();
+
+        write_parquet(path, &originais).unwrap();
+        let lidos = read_parquet(path).unwrap();
+
+        assert_eq!(lidos.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&lidos).unwrap(),
+            serde_json::to_value(&originais).unwrap()
+        );
+        assert_eq!(lidos[1].area_de_lazer, None, "Nulo não deve virar 0");
+    }
+
+    #[test]
+    fn test_write_parquet_com_compressao_e_grupos() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap();
-        
-        let result = write_parquet(path, &empreendimentos);
-        assert!(result.is_ok());
+        let opcoes = OpcoesParquet {
+            compressao: Compression::ZSTD(ZstdLevel::default()),
+            linhas_por_grupo: 1,
+        };
+
+        write_parquet_com_opcoes(path, &empreendimentos(), &opcoes).unwrap();
+
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 2);
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert!(matches!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::ZSTD(_)
+        ));
+        assert_eq!(read_parquet(path).unwrap().len(), 2);
+    }
 
-        let file_content = std::fs::read_to_string(path).unwrap();
-        assert!(!file_content.is_empty());
-        assert!(file_content.contains("Teste,São Paulo,1000,2,25,300,150"));
+    #[test]
+    fn test_write_parquet_sem_linhas_por_grupo() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let opcoes = OpcoesParquet {
+            linhas_por_grupo: 0,
+            ..Default::default()
+        };
+        let path = temp_file.path().to_str().unwrap();
+        assert!(write_parquet_com_opcoes(path, &empreendimentos(), &opcoes).is_err());
     }
 }
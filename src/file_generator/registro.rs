@@ -0,0 +1,122 @@
+use crate::models::empreendimento::Empreendimento;
+use crate::models::json_aninhado;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Campos gravados, em `snake_case` e `kebab-case`, na ordem das colunas.
+pub(crate) const CAMPOS: [(&str, &str); 23] = [
+    ("construtora", "construtora"),
+    ("cidade", "cidade"),
+    ("area_do_terreno", "area-do-terreno"),
+    ("numero_de_torres", "numero-de-torres"),
+    ("altura_da_torre", "altura-da-torre"),
+    ("area_da_torre", "area-da-torre"),
+    ("area_de_lazer", "area-de-lazer"),
+    ("area_construida", "area-construida"),
+    ("area_permeavel", "area-permeavel"),
+    ("area_de_projecao", "area-de-projecao"),
+    ("uso", "uso"),
+    ("zona", "zona"),
+    ("latitude", "latitude"),
+    ("longitude", "longitude"),
+    ("cota_do_terreno", "cota-do-terreno"),
+    ("numero_de_unidades", "numero-de-unidades"),
+    ("unidades_por_torre", "unidades-por-torre"),
+    ("vagas_de_garagem", "vagas-de-garagem"),
+    ("vagas_acessiveis", "vagas-acessiveis"),
+    ("vagas_de_motos", "vagas-de-motos"),
+    ("vagas_de_bicicletas", "vagas-de-bicicletas"),
+    ("torres", "torres"),
+    ("lote", "lote"),
+];
+
+/// Estilo dos nomes das colunas do CSV e dos elementos do XML gravados. Os
+/// leitores aceitam os dois.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EstiloNomes {
+    /// `area_do_terreno`
+    #[default]
+    Snake,
+    /// `area-do-terreno`, como no JSON
+    Kebab,
+}
+
+impl EstiloNomes {
+    pub(crate) fn nomes(self) -> [&'static str; CAMPOS.len()] {
+        CAMPOS.map(|(snake, kebab)| match self {
+            EstiloNomes::Snake => snake,
+            EstiloNomes::Kebab => kebab,
+        })
+    }
+}
+
+impl std::str::FromStr for EstiloNomes {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        match texto.to_lowercase().as_str() {
+            "snake" => Ok(EstiloNomes::Snake),
+            "kebab" => Ok(EstiloNomes::Kebab),
+            _ => Err(format!(
+                "Estilo de nomes '{}' inválido; use snake ou kebab",
+                texto
+            )),
+        }
+    }
+}
+
+/// Valores do empreendimento como texto, na ordem de [`EstiloNomes::nomes`];
+/// `None` para campos ausentes. `real` formata os números não inteiros, e
+/// `torres`/`lote` são texto JSON.
+pub(crate) fn valores(
+    e: &Empreendimento,
+    real: impl Fn(f64) -> String,
+) -> [Option<String>; CAMPOS.len()] {
+    let inteiro = |valor: Option<u32>| valor.map(|v| v.to_string());
+    [
+        Some(e.construtora.clone()),
+        Some(e.cidade.clone()),
+        Some(real(e.area_do_terreno)),
+        Some(e.numero_de_torres.to_string()),
+        Some(real(e.altura_da_torre)),
+        Some(real(e.area_da_torre)),
+        e.area_de_lazer.map(&real),
+        e.area_construida.map(&real),
+        e.area_permeavel.map(&real),
+        e.area_de_projecao.map(&real),
+        Some(e.uso.to_string()),
+        e.zona.clone(),
+        e.latitude.map(&real),
+        e.longitude.map(&real),
+        e.cota_do_terreno.map(&real),
+        inteiro(e.numero_de_unidades),
+        inteiro(e.unidades_por_torre),
+        inteiro(e.vagas_de_garagem),
+        inteiro(e.vagas_acessiveis),
+        inteiro(e.vagas_de_motos),
+        inteiro(e.vagas_de_bicicletas),
+        json_aninhado::para_texto(&e.torres),
+        json_aninhado::para_texto(&e.lote),
+    ]
+}
+
+/// Empreendimento serializado como estrutura com os nomes do estilo,
+/// omitindo os campos ausentes.
+pub(crate) struct Registro<'a> {
+    pub e: &'a Empreendimento,
+    pub estilo: EstiloNomes,
+}
+
+impl Serialize for Registro<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let valores = valores(self.e, |v| v.to_string());
+        let presentes = valores.iter().flatten().count();
+        let mut estrutura = serializer.serialize_struct("empreendimento", presentes)?;
+        for (nome, valor) in self.estilo.nomes().into_iter().zip(&valores) {
+            match valor {
+                Some(valor) => estrutura.serialize_field(nome, valor)?,
+                None => estrutura.skip_field(nome)?,
+            }
+        }
+        estrutura.end()
+    }
+}
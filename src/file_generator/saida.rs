@@ -0,0 +1,94 @@
+use crate::file_reader::Compressao;
+use crate::file_reader::compressao::Compactador;
+use std::fs::File;
+use std::io::{self, Write};
+
+/// Destino dos dados gravados: um arquivo ou qualquer `Write`, como a saída
+/// padrão ou um buffer em memória.
+///
+/// Um caminho terminado em `.gz`, `.zst` ou `.bz2` é gravado compactado com
+/// gzip, zstd ou bzip2, no formato da extensão anterior (`dados.csv.gz`).
+pub enum Saida<'a> {
+    Caminho(&'a str),
+    Escritor(Box<dyn Write + Send + 'a>),
+}
+
+impl<'a> Saida<'a> {
+    pub fn escritor(escritor: impl Write + Send + 'a) -> Self {
+        Saida::Escritor(Box::new(escritor))
+    }
+
+    /// Caminho do arquivo, quando a saída é um arquivo.
+    pub fn caminho(&self) -> Option<&'a str> {
+        match self {
+            Saida::Caminho(path) => Some(path),
+            Saida::Escritor(_) => None,
+        }
+    }
+
+    /// Abre a saída para gravação, criando o arquivo quando é um caminho; a
+    /// gravação termina com [`Destino::concluir`].
+    pub(crate) fn abrir(self) -> io::Result<Destino<'a>> {
+        match self {
+            Saida::Caminho(path) => {
+                let arquivo: Box<dyn Write + Send> = Box::new(File::create(path)?);
+                match Compressao::pela_extensao(path) {
+                    Some(compressao) => Ok(Destino::Compactado(compressao.compactar(arquivo)?)),
+                    None => Ok(Destino::Direto(arquivo)),
+                }
+            }
+            Saida::Escritor(escritor) => Ok(Destino::Direto(escritor)),
+        }
+    }
+
+    /// Grava `bytes` de uma vez e conclui a gravação.
+    pub(crate) fn gravar(self, bytes: &[u8]) -> io::Result<()> {
+        let mut destino = self.abrir()?;
+        destino.write_all(bytes)?;
+        destino.concluir()
+    }
+}
+
+impl<'a> From<&'a str> for Saida<'a> {
+    fn from(path: &'a str) -> Self {
+        Saida::Caminho(path)
+    }
+}
+
+impl<'a> From<&'a String> for Saida<'a> {
+    fn from(path: &'a String) -> Self {
+        Saida::Caminho(path)
+    }
+}
+
+/// Saída aberta para gravação, compactada ou não.
+pub(crate) enum Destino<'a> {
+    Direto(Box<dyn Write + Send + 'a>),
+    Compactado(Compactador<Box<dyn Write + Send + 'a>>),
+}
+
+impl Destino<'_> {
+    /// Grava o fim do fluxo compactado, se houver, e descarrega a saída.
+    pub(crate) fn concluir(self) -> io::Result<()> {
+        match self {
+            Destino::Direto(mut escritor) => escritor.flush(),
+            Destino::Compactado(compactador) => compactador.concluir()?.flush(),
+        }
+    }
+}
+
+impl Write for Destino<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Destino::Direto(escritor) => escritor.write(buf),
+            Destino::Compactado(compactador) => compactador.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Destino::Direto(escritor) => escritor.flush(),
+            Destino::Compactado(compactador) => compactador.flush(),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use super::Saida;
+use super::mapa;
+use super::registro::{self, EstiloNomes};
+use crate::business_logic::{ValidationResult, Violacao};
+use crate::models::empreendimento::Empreendimento;
+use rust_xlsxwriter::{Color, Format, FormatBorder, Note, Workbook, Worksheet, XlsxError};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Status e violações vêm depois das colunas do CSV, para que a planilha
+/// gravada possa ser lida de volta por `read_xlsx`.
+const COLUNA_STATUS: u16 = registro::CAMPOS.len() as u16;
+const COLUNA_VIOLACOES: u16 = COLUNA_STATUS + 1;
+
+/// Preenchimento das células que causaram violações (estilo "Ruim" do Excel).
+const FUNDO_VIOLACAO: u32 = 0xFFC7CE;
+const FONTE_VIOLACAO: u32 = 0x9C0006;
+
+/// Grava os empreendimentos em uma planilha Excel anotada com a validação.
+///
+/// A aba "Empreendimentos" traz os dados originais, o status e as violações de
+/// cada linha; as células dos campos avaliados por uma regra violada ficam em
+/// vermelho, com as mensagens da regra em uma nota. A aba "Resumo por regra"
+/// conta os empreendimentos reprovados em cada regra.
+///
+/// A planilha é montada na memória e gravada de uma vez, compactada quando o
+/// caminho termina em `.gz`, `.zst` ou `.bz2`.
+pub fn write_xlsx_anotado<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    resultados: &[ValidationResult],
+) -> Result<(), Box<dyn Error>> {
+    let pares: Vec<_> = mapa::parear(empreendimentos, resultados)?.collect();
+    let mut workbook = Workbook::new();
+
+    gravar_empreendimentos(
+        workbook.add_worksheet().set_name("Empreendimentos")?,
+        &pares,
+    )?;
+    gravar_resumo(
+        workbook.add_worksheet().set_name("Resumo por regra")?,
+        resultados,
+    )?;
+
+    saida.into().gravar(&workbook.save_to_buffer()?)?;
+    Ok(())
+}
+
+fn gravar_empreendimentos(
+    aba: &mut Worksheet,
+    pares: &[(&Empreendimento, &ValidationResult)],
+) -> Result<(), XlsxError> {
+    let cabecalho = Format::new()
+        .set_bold()
+        .set_border_bottom(FormatBorder::Thin);
+    let normal = Format::new();
+    let destacada = Format::new()
+        .set_background_color(Color::RGB(FUNDO_VIOLACAO))
+        .set_font_color(Color::RGB(FONTE_VIOLACAO));
+    let quebra_de_linha = Format::new().set_text_wrap();
+
+    let colunas = EstiloNomes::Snake.nomes();
+    for (coluna, nome) in colunas.iter().enumerate() {
+        aba.write_string_with_format(0, coluna as u16, *nome, &cabecalho)?;
+    }
+    aba.write_string_with_format(0, COLUNA_STATUS, "status", &cabecalho)?;
+    aba.write_string_with_format(0, COLUNA_VIOLACOES, "violacoes", &cabecalho)?;
+
+    for (indice, (e, resultado)) in pares.iter().enumerate() {
+        let linha = indice as u32 + 1;
+        let notas = mensagens_por_campo(resultado);
+        let dados = match serde_json::to_value(e) {
+            Ok(Value::Object(dados)) => dados,
+            _ => Default::default(),
+        };
+
+        for (coluna, nome) in colunas.iter().enumerate() {
+            let coluna = coluna as u16;
+            let formato = if notas.contains_key(nome) {
+                &destacada
+            } else {
+                &normal
+            };
+            match dados.get(&nome.replace('_', "-")).unwrap_or(&Value::Null) {
+                Value::Null => aba.write_blank(linha, coluna, formato)?,
+                Value::Number(numero) => aba.write_number_with_format(
+                    linha,
+                    coluna,
+                    numero.as_f64().unwrap_or_default(),
+                    formato,
+                )?,
+                Value::String(texto) => {
+                    aba.write_string_with_format(linha, coluna, texto, formato)?
+                }
+                outro => aba.write_string_with_format(linha, coluna, outro.to_string(), formato)?,
+            };
+            if let Some(mensagens) = notas.get(nome) {
+                let nota = Note::new(mensagens.join("\n")).add_author_prefix(false);
+                aba.insert_note(linha, coluna, &nota)?;
+            }
+        }
+
+        let status = Format::new()
+            .set_bold()
+            .set_font_color(mapa::cor(mapa::estilo(resultado)));
+        let status_texto = if resultado.regras_ok {
+            "aprovado"
+        } else {
+            "reprovado"
+        };
+        aba.write_string_with_format(linha, COLUNA_STATUS, status_texto, &status)?;
+        aba.write_string_with_format(
+            linha,
+            COLUNA_VIOLACOES,
+            resultado.mensagens.join("\n"),
+            &quebra_de_linha,
+        )?;
+    }
+
+    aba.autofilter(0, 0, pares.len() as u32, COLUNA_VIOLACOES)?;
+    aba.set_freeze_panes(1, 1)?;
+    aba.autofit();
+    aba.set_column_width(COLUNA_VIOLACOES, 80)?;
+    Ok(())
+}
+
+/// Mensagens de cada campo destacado, a partir das regras violadas.
+fn mensagens_por_campo(resultado: &ValidationResult) -> BTreeMap<&'static str, Vec<&str>> {
+    let mut notas: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (violacao, mensagem) in resultado.violacoes.iter().zip(&resultado.mensagens) {
+        for campo in violacao.campos {
+            notas.entry(*campo).or_default().push(mensagem.as_str());
+        }
+    }
+    notas
+}
+
+/// Violações de uma regra em todos os empreendimentos.
+struct ResumoDaRegra<'a> {
+    violacao: Violacao,
+    reprovados: usize,
+    exemplo: &'a str,
+}
+
+fn gravar_resumo(aba: &mut Worksheet, resultados: &[ValidationResult]) -> Result<(), XlsxError> {
+    let mut por_regra: BTreeMap<&str, ResumoDaRegra> = BTreeMap::new();
+    for resultado in resultados {
+        for (violacao, mensagem) in resultado.violacoes.iter().zip(&resultado.mensagens) {
+            por_regra
+                .entry(violacao.regra)
+                .or_insert(ResumoDaRegra {
+                    violacao: *violacao,
+                    reprovados: 0,
+                    exemplo: mensagem,
+                })
+                .reprovados += 1;
+        }
+    }
+    let mut resumos: Vec<_> = por_regra.into_values().collect();
+    resumos.sort_by_key(|resumo| std::cmp::Reverse(resumo.reprovados));
+
+    let cabecalho = Format::new()
+        .set_bold()
+        .set_border_bottom(FormatBorder::Thin);
+    let percentual = Format::new().set_num_format("0.0%");
+    let titulos = [
+        "Regra",
+        "Campos",
+        "Empreendimentos reprovados",
+        "% dos empreendimentos",
+        "Exemplo de mensagem",
+    ];
+    for (coluna, titulo) in titulos.iter().enumerate() {
+        aba.write_string_with_format(0, coluna as u16, *titulo, &cabecalho)?;
+    }
+
+    for (indice, resumo) in resumos.iter().enumerate() {
+        let linha = indice as u32 + 1;
+        aba.write_string(linha, 0, resumo.violacao.regra)?;
+        aba.write_string(linha, 1, resumo.violacao.campos.join(", "))?;
+        aba.write_number(linha, 2, resumo.reprovados as f64)?;
+        aba.write_number_with_format(
+            linha,
+            3,
+            resumo.reprovados as f64 / resultados.len() as f64,
+            &percentual,
+        )?;
+        aba.write_string(linha, 4, resumo.exemplo)?;
+    }
+
+    let total = resumos.len() as u32 + 1;
+    let aprovados = resultados.iter().filter(|r| r.regras_ok).count();
+    aba.write_string_with_format(total + 1, 0, "Empreendimentos", &cabecalho)?;
+    aba.write_number(total + 1, 2, resultados.len() as f64)?;
+    aba.write_string_with_format(total + 2, 0, "Aprovados", &cabecalho)?;
+    aba.write_number(total + 2, 2, aprovados as f64)?;
+    if !resultados.is_empty() {
+        aba.write_number_with_format(
+            total + 2,
+            3,
+            aprovados as f64 / resultados.len() as f64,
+            &percentual,
+        )?;
+    }
+
+    aba.autofit();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business_logic::validar_empreendimentos;
+    use crate::file_reader::read_xlsx;
+    use calamine::{Data, Reader, Xlsx, open_workbook};
+    use tempfile::NamedTempFile;
+
+    fn empreendimentos() -> Vec<Empreendimento> {
+        vec![
+            Empreendimento {
+                construtora: "Aprovada".to_string(),
+                cidade: "Curitiba".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 2,
+                altura_da_torre: 25.0,
+                area_da_torre: 200.0,
+                area_de_lazer: Some(150.0),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Reprovada".to_string(),
+                cidade: "Curitiba".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 2,
+                altura_da_torre: 60.0,
+                area_da_torre: 450.0,
+                zona: Some("ZR".to_string()),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_mensagens_por_campo_agrupa_as_regras_violadas() {
+        let resultados = validar_empreendimentos(&empreendimentos());
+
+        assert!(mensagens_por_campo(&resultados[0]).is_empty());
+        let notas = mensagens_por_campo(&resultados[1]);
+        assert_eq!(
+            notas["altura_da_torre"],
+            vec!["Altura da torre deve ser inferior a 30m."]
+        );
+        assert_eq!(
+            notas["area_de_lazer"],
+            vec!["Faltando campo 'area-de-lazer'."]
+        );
+        assert_eq!(notas["area_do_terreno"].len(), 2, "Torres e lazer");
+        assert!(!notas.contains_key("cidade"));
+    }
+
+    #[test]
+    fn test_write_xlsx_anotado_pode_ser_lido_de_volta() {
+        let originais = empreendimentos();
+        let temp_file = NamedTempFile::with_suffix(".xlsx").unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        write_xlsx_anotado(path, &originais, &validar_empreendimentos(&originais)).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(read_xlsx(path).unwrap()).unwrap(),
+            serde_json::to_value(&originais).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_xlsx_anotado_compactado_pela_extensao() {
+        let originais = empreendimentos();
+        let temp_file = NamedTempFile::with_suffix(".xlsx.gz").unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        write_xlsx_anotado(path, &originais, &validar_empreendimentos(&originais)).unwrap();
+
+        assert!(std::fs::read(path).unwrap().starts_with(&[0x1f, 0x8b]));
+        assert_eq!(
+            serde_json::to_value(read_xlsx(path).unwrap()).unwrap(),
+            serde_json::to_value(&originais).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_xlsx_anotado_grava_status_e_resumo_por_regra() {
+        let originais = empreendimentos();
+        let temp_file = NamedTempFile::with_suffix(".xlsx").unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        write_xlsx_anotado(path, &originais, &validar_empreendimentos(&originais)).unwrap();
+
+        let mut planilha: Xlsx<_> = open_workbook(path).unwrap();
+        let dados = planilha.worksheet_range("Empreendimentos").unwrap();
+        let status = COLUNA_STATUS as usize;
+        assert_eq!(dados[(0, status)], Data::String("status".to_string()));
+        assert_eq!(dados[(1, status)], Data::String("aprovado".to_string()));
+        assert_eq!(dados[(2, status)], Data::String("reprovado".to_string()));
+        assert_eq!(dados[(1, status + 1)], Data::Empty);
+        assert_eq!(
+            dados[(2, status + 1)].to_string().lines().count(),
+            3,
+            "Altura, área das torres e lazer"
+        );
+
+        let resumo = planilha.worksheet_range("Resumo por regra").unwrap();
+        let linha = |i: usize| -> Vec<String> {
+            resumo.rows().nth(i).unwrap()[..4]
+                .iter()
+                .map(Data::to_string)
+                .collect()
+        };
+        assert_eq!(linha(1), ["RegraAlturaMax", "altura_da_torre", "1", "0.5"]);
+        assert_eq!(linha(2)[0], "RegraAreaLazerMin");
+        assert_eq!(linha(3)[0], "RegraAreaTorresMax");
+        assert_eq!(linha(6)[..3], ["Aprovados", "", "1"]);
+    }
+
+    #[test]
+    fn test_write_xlsx_anotado_exige_um_resultado_por_empreendimento() {
+        let temp_file = NamedTempFile::with_suffix(".xlsx").unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        assert!(write_xlsx_anotado(path, &empreendimentos(), &[]).is_err());
+    }
+}
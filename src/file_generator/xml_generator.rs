@@ -1,58 +1,113 @@
+use super::Saida;
+use super::registro::{EstiloNomes, Registro};
 use crate::models::empreendimento::Empreendimento;
+use quick_xml::se::Serializer;
+use serde::Serialize;
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
-
-pub fn write_xml(path: &str, empreendimentos: &[Empreendimento]) -> Result<(), Box<dyn Error>> {
-    let mut file = File::create(path)?;
-
-    writeln!(file, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
-    writeln!(file, "<empreendimentos>")?;
-
-    for empreendimento in empreendimentos {
-        writeln!(file, "  <empreendimento>")?;
-        writeln!(file, "    <construtora>{}</construtora>", empreendimento.construtora)?;
-        writeln!(file, "    <cidade>{}</cidade>", empreendimento.cidade)?;
-        writeln!(file, "    <area_do_terreno>{}</area_do_terreno>", empreendimento.area_do_terreno)?;
-        writeln!(file, "    <numero_de_torres>{}</numero_de_torres>", empreendimento.numero_de_torres)?;
-        writeln!(file, "    <altura_da_torre>{}</altura_da_torre>", empreendimento.altura_da_torre)?;
-        writeln!(file, "    <area_da_torre>{}</area_da_torre>", empreendimento.area_da_torre)?;
-
-        if let Some(area_lazer) = empreendimento.area_de_lazer {
-            writeln!(file, "    <area_de_lazer>{}</area_de_lazer>", area_lazer)?;
-        } else {
-            writeln!(file, "    <area_de_lazer>0</area_de_lazer>")?;
-        }
-        
-        writeln!(file, "  </empreendimento>")?;
+
+/// Opções de gravação do XML: o estilo dos nomes dos elementos.
+#[derive(Debug, Clone, Default)]
+pub struct OpcoesXml {
+    pub nomes: EstiloNomes,
+}
+
+#[derive(Serialize)]
+struct Empreendimentos<'a> {
+    empreendimento: Vec<Registro<'a>>,
+}
+
+pub fn write_xml<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+) -> Result<(), Box<dyn Error>> {
+    write_xml_com_opcoes(saida, empreendimentos, &OpcoesXml::default())
+}
+
+/// Grava o XML pelo serializador do quick-xml, que escapa `&`, `<` e `>` nos
+/// valores. Campos ausentes são omitidos, e o arquivo é lido de volta por
+/// `read_xml` com os mesmos valores.
+pub fn write_xml_com_opcoes<'a>(
+    saida: impl Into<Saida<'a>>,
+    empreendimentos: &[Empreendimento],
+    opcoes: &OpcoesXml,
+) -> Result<(), Box<dyn Error>> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let mut serializer = Serializer::with_root(&mut xml, Some("empreendimentos"))?;
+    serializer.indent(' ', 2);
+    Empreendimentos {
+        empreendimento: empreendimentos
+            .iter()
+            .map(|e| Registro {
+                e,
+                estilo: opcoes.nomes,
+            })
+            .collect(),
     }
-    
-    writeln!(file, "</empreendimentos>")?;
+    .serialize(serializer)?;
+    xml.push('\n');
+
+    saida.into().gravar(xml.as_bytes())?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_reader::read_xml;
+    use crate::models::geometria::Poligono;
+    use crate::models::lote::Lote;
+    use crate::models::uso::Uso;
     use tempfile::NamedTempFile;
-    
-    #[test]
-    fn test_write_xml() {
-        let empreendimentos = vec![
+
+    fn com_caracteres_especiais() -> Vec<Empreendimento> {
+        vec![
             Empreendimento {
-                construtora: "Teste".to_string(),
-                cidade: "São Paulo".to_string(),
-                area_do_terreno: 1000.0,
+                construtora: "Silva, Souza & Filhos".to_string(),
+                cidade: "São Paulo; \"Centro\" <SP>".to_string(),
+                area_do_terreno: 1234.5,
                 numero_de_torres: 2,
-                altura_da_torre: 25.0,
+                altura_da_torre: 25.25,
                 area_da_torre: 300.0,
-                area_de_lazer: Some(150.0),
-            }
-        ];
-        
+                area_de_lazer: Some(0.0),
+                uso: Uso::Misto,
+                zona: Some("ZM 'A'\nquadra 2".to_string()),
+                vagas_de_garagem: Some(40),
+                lote: Some(Lote::local(Poligono(vec![
+                    [0.0, 0.0],
+                    [30.0, 0.0],
+                    [30.0, 41.15],
+                    [0.0, 41.15],
+                ]))),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Beta".to_string(),
+                cidade: "Boituva".to_string(),
+                area_do_terreno: 800.0,
+                numero_de_torres: 1,
+                altura_da_torre: 12.0,
+                area_da_torre: 200.0,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_xml() {
+        let empreendimentos = vec![Empreendimento {
+            construtora: "Teste".to_string(),
+            cidade: "São Paulo".to_string(),
+            area_do_terreno: 1000.0,
+            numero_de_torres: 2,
+            altura_da_torre: 25.0,
+            area_da_torre: 300.0,
+            area_de_lazer: Some(150.0),
+            ..Default::default()
+        }];
+
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap();
-        
+
         let result = write_xml(path, &empreendimentos);
         assert!(result.is_ok());
 
@@ -63,4 +118,49 @@ mod tests {
         assert!(content.contains("<cidade>São Paulo</cidade>"));
         assert!(content.contains("<area_do_terreno>1000</area_do_terreno>"));
     }
+
+    #[test]
+    fn test_write_xml_escapa_valores() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        write_xml(path, &com_caracteres_especiais()).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains("<construtora>Silva, Souza &amp; Filhos</construtora>"));
+        assert!(content.contains("&lt;SP&gt;"));
+        assert_eq!(
+            content.matches("<area_de_lazer>").count(),
+            1,
+            "Campos ausentes são omitidos, e não gravados como zero"
+        );
+    }
+
+    #[test]
+    fn test_write_xml_round_trip_nos_dois_estilos() {
+        let originais = com_caracteres_especiais();
+        for nomes in [EstiloNomes::Snake, EstiloNomes::Kebab] {
+            let temp_file = NamedTempFile::new().unwrap();
+            let path = temp_file.path().to_str().unwrap();
+
+            write_xml_com_opcoes(path, &originais, &OpcoesXml { nomes }).unwrap();
+
+            assert_eq!(
+                serde_json::to_value(read_xml(path).unwrap()).unwrap(),
+                serde_json::to_value(&originais).unwrap(),
+                "{:?}",
+                nomes
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_xml_sem_empreendimentos() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+
+        write_xml(path, &[]).unwrap();
+
+        assert!(read_xml(path).unwrap().is_empty());
+    }
 }
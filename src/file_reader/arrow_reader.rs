@@ -0,0 +1,152 @@
+use super::Entrada;
+use crate::models::arrow_converter;
+use crate::models::empreendimento::Empreendimento;
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::record_batch::RecordBatch;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+
+/// Assinatura no início (e no fim) do formato de arquivo do Arrow IPC.
+const ASSINATURA_ARQUIVO: &[u8; 6] = b"ARROW1";
+/// Marcador de continuação que abre cada mensagem do formato de fluxo.
+const MARCADOR_FLUXO: &[u8; 4] = &[0xFF; 4];
+
+/// Lê um arquivo Arrow IPC, no formato de arquivo (`.arrow`, Feather v2) ou
+/// de fluxo (`.arrows`), reconhecido pelo conteúdo. As colunas são convertidas
+/// como no Parquet: nomes normalizados e tipos numéricos compatíveis.
+pub fn read_arrow<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let lotes = match entrada.into() {
+        Entrada::Caminho(path) => read_arrow_em_lotes(path)?,
+        leitor => ler_lotes(Cursor::new(leitor.bytes()?))?
+            .ok_or("A entrada não é um arquivo Arrow IPC")?,
+    };
+    let mut empreendimentos = Vec::new();
+    for batch in lotes {
+        empreendimentos.extend(arrow_converter::de_record_batch(
+            &batch,
+            empreendimentos.len() + 1,
+        )?);
+    }
+    Ok(empreendimentos)
+}
+
+/// Lê o arquivo Arrow IPC como `RecordBatch`es, sem convertê-los em
+/// empreendimentos; usado pela validação colunar. Arquivos compactados são
+/// descompactados na memória.
+pub fn read_arrow_em_lotes(path: &str) -> Result<Vec<RecordBatch>, Box<dyn Error>> {
+    let entrada = Entrada::Caminho(path);
+    let lotes = match entrada.arquivo()? {
+        Some(path) => ler_lotes(File::open(path)?)?,
+        None => ler_lotes(Cursor::new(entrada.bytes()?))?,
+    };
+    lotes.ok_or_else(|| format!("'{}' não é um arquivo Arrow IPC", path).into())
+}
+
+/// Lotes do conteúdo em qualquer dos dois formatos; `None` quando ele não
+/// começa pela assinatura nem pelo marcador de fluxo.
+pub(crate) fn ler_lotes<R: Read + Seek>(
+    mut fonte: R,
+) -> Result<Option<Vec<RecordBatch>>, Box<dyn Error>> {
+    let mut assinatura = [0u8; 6];
+    let lidos = fonte.read(&mut assinatura)?;
+    fonte.seek(SeekFrom::Start(0))?;
+
+    if assinatura[..lidos].starts_with(ASSINATURA_ARQUIVO) {
+        Ok(Some(
+            FileReader::try_new(fonte, None)?.collect::<Result<_, _>>()?,
+        ))
+    } else if assinatura[..lidos].starts_with(MARCADOR_FLUXO) {
+        Ok(Some(
+            StreamReader::try_new(BufReader::new(fonte), None)?.collect::<Result<_, _>>()?,
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float32Array, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::writer::StreamWriter;
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
+
+    fn gravar_fluxo(path: &str, lotes: &[RecordBatch]) {
+        let mut writer =
+            StreamWriter::try_new(File::create(path).unwrap(), &lotes[0].schema()).unwrap();
+        for lote in lotes {
+            writer.write(lote).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    fn lote(construtoras: Vec<&str>, torres: Vec<i64>) -> RecordBatch {
+        let quantidade = construtoras.len();
+        let schema = Schema::new(vec![
+            Field::new("construtora", DataType::Utf8, false),
+            Field::new("cidade", DataType::Utf8, false),
+            Field::new("area-do-terreno", DataType::Float32, false),
+            Field::new("numero-de-torres", DataType::Int64, false),
+            Field::new("altura-da-torre", DataType::Float32, false),
+            Field::new("area-da-torre", DataType::Float32, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(construtoras)),
+                Arc::new(StringArray::from(vec!["Curitiba"; quantidade])),
+                Arc::new(Float32Array::from(vec![1000.0; quantidade])),
+                Arc::new(Int64Array::from(torres)),
+                Arc::new(Float32Array::from(vec![20.5; quantidade])),
+                Arc::new(Float32Array::from(vec![300.0; quantidade])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_read_arrow_fluxo_com_varios_lotes_e_tipos_compativeis() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        gravar_fluxo(
+            path,
+            &[lote(vec!["A", "B"], vec![1, 2]), lote(vec!["C"], vec![3])],
+        );
+
+        let empreendimentos = read_arrow(path).unwrap();
+
+        assert_eq!(empreendimentos.len(), 3);
+        assert_eq!(empreendimentos[2].construtora, "C");
+        assert_eq!(empreendimentos[2].numero_de_torres, 3);
+        assert_eq!(empreendimentos[0].altura_da_torre, 20.5);
+        assert_eq!(empreendimentos[0].area_de_lazer, None);
+    }
+
+    #[test]
+    fn test_read_arrow_erro_informa_linha_e_coluna() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        gravar_fluxo(
+            path,
+            &[lote(vec!["A", "B"], vec![1, 2]), lote(vec!["C"], vec![-1])],
+        );
+
+        let erro = read_arrow(path).unwrap_err().to_string();
+        assert_eq!(
+            erro,
+            "Linha 3, coluna 'numero_de_torres': esperado inteiro não negativo, encontrado -1"
+        );
+    }
+
+    #[test]
+    fn test_read_arrow_arquivo_invalido() {
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), "construtora,cidade\n").unwrap();
+        assert!(read_arrow(temp_file.path().to_str().unwrap()).is_err());
+    }
+}
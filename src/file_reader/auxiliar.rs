@@ -0,0 +1,37 @@
+use serde_json::{Map, Number, Value};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Lê o arquivo auxiliar `<arquivo>.json` (por exemplo `projeto.dxf.json`),
+/// com dados do empreendimento que não constam em desenhos e modelos.
+/// As chaves são normalizadas para o formato snake_case. Sem caminho, como na
+/// leitura de um `Read`, não há arquivo auxiliar.
+pub(crate) fn ler_arquivo_auxiliar(
+    path: Option<&str>,
+) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let Some(path) = path else {
+        return Ok(Map::new());
+    };
+    let auxiliar = format!("{}.json", path);
+    if !Path::new(&auxiliar).exists() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str(&fs::read_to_string(&auxiliar)?)? {
+        Value::Object(dados) => Ok(dados
+            .into_iter()
+            .map(|(chave, valor)| (normalizar_chave(&chave), valor))
+            .collect()),
+        _ => Err(format!("Arquivo auxiliar '{}' deve conter um objeto JSON", auxiliar).into()),
+    }
+}
+
+pub(crate) fn normalizar_chave(chave: &str) -> String {
+    chave.trim().to_lowercase().replace(['-', ' '], "_")
+}
+
+pub(crate) fn numero(valor: f64) -> Value {
+    Number::from_f64(valor)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
@@ -0,0 +1,173 @@
+use bzip2::read::MultiBzDecoder;
+use bzip2::write::BzEncoder;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Compressão de um arquivo inteiro, aplicada sobre qualquer formato:
+/// `dados.csv.gz`, `dados.json.zst`, `extrato.osm.bz2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compressao {
+    Gzip,
+    Zstd,
+    Bzip2,
+}
+
+/// Bytes do início do conteúdo que bastam para reconhecer a compressão.
+pub(crate) const ASSINATURA: usize = 4;
+
+impl Compressao {
+    /// Compressão pela última extensão do caminho (`.gz`, `.zst` ou `.bz2`).
+    pub fn pela_extensao(path: &str) -> Option<Compressao> {
+        let extensao = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())?
+            .to_lowercase();
+        match extensao.as_str() {
+            "gz" | "gzip" => Some(Compressao::Gzip),
+            "zst" | "zstd" => Some(Compressao::Zstd),
+            "bz2" => Some(Compressao::Bzip2),
+            _ => None,
+        }
+    }
+
+    /// Compressão pela assinatura no início do conteúdo.
+    pub fn pelo_conteudo(bytes: &[u8]) -> Option<Compressao> {
+        if bytes.starts_with(&[0x1F, 0x8B]) {
+            Some(Compressao::Gzip)
+        } else if bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Some(Compressao::Zstd)
+        } else if bytes.starts_with(b"BZh")
+            && bytes.get(3).is_some_and(|n| (b'1'..=b'9').contains(n))
+        {
+            Some(Compressao::Bzip2)
+        } else {
+            None
+        }
+    }
+
+    /// Caminho sem a extensão da compressão, cuja extensão restante indica o
+    /// formato: `dados.csv.gz` é `dados.csv`.
+    pub fn sem_extensao(path: &str) -> &str {
+        match Compressao::pela_extensao(path) {
+            Some(_) => &path[..path.rfind('.').unwrap_or(path.len())],
+            None => path,
+        }
+    }
+
+    pub(crate) fn descompactar<'a>(self, leitor: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Compressao::Gzip => Box::new(MultiGzDecoder::new(leitor)),
+            Compressao::Zstd => Box::new(zstd::Decoder::new(leitor)?),
+            Compressao::Bzip2 => Box::new(MultiBzDecoder::new(leitor)),
+        })
+    }
+
+    pub(crate) fn compactar<W: Write>(self, escritor: W) -> io::Result<Compactador<W>> {
+        Ok(match self {
+            Compressao::Gzip => Compactador::Gzip(GzEncoder::new(escritor, Default::default())),
+            Compressao::Zstd => Compactador::Zstd(zstd::Encoder::new(escritor, 0)?),
+            Compressao::Bzip2 => Compactador::Bzip2(BzEncoder::new(escritor, Default::default())),
+        })
+    }
+}
+
+/// Escritor que compacta o que recebe; [`Compactador::concluir`] grava o fim
+/// do fluxo compactado.
+pub(crate) enum Compactador<W: Write> {
+    Gzip(GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+    Bzip2(BzEncoder<W>),
+}
+
+impl<W: Write> Compactador<W> {
+    pub(crate) fn concluir(self) -> io::Result<W> {
+        match self {
+            Compactador::Gzip(escritor) => escritor.finish(),
+            Compactador::Zstd(escritor) => escritor.finish(),
+            Compactador::Bzip2(escritor) => escritor.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Compactador<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Compactador::Gzip(escritor) => escritor.write(buf),
+            Compactador::Zstd(escritor) => escritor.write(buf),
+            Compactador::Bzip2(escritor) => escritor.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Compactador::Gzip(escritor) => escritor.flush(),
+            Compactador::Zstd(escritor) => escritor.flush(),
+            Compactador::Bzip2(escritor) => escritor.flush(),
+        }
+    }
+}
+
+/// Lê o início de `leitor` e, se ele estiver compactado pela assinatura ou,
+/// na falta dela, pela compressão `esperada`, devolve o conteúdo
+/// descompactado; os bytes lidos na verificação são devolvidos ao fluxo.
+pub(crate) fn descompactar_se_preciso<'a>(
+    mut leitor: impl Read + 'a,
+    esperada: Option<Compressao>,
+) -> io::Result<Box<dyn Read + 'a>> {
+    let mut inicio = Vec::with_capacity(ASSINATURA);
+    (&mut leitor)
+        .take(ASSINATURA as u64)
+        .read_to_end(&mut inicio)?;
+    let compressao = Compressao::pelo_conteudo(&inicio).or(esperada);
+    let leitor = io::Cursor::new(inicio).chain(leitor);
+    match compressao {
+        Some(compressao) => compressao.descompactar(leitor),
+        None => Ok(Box::new(leitor)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compressao_pela_extensao() {
+        assert_eq!(
+            Compressao::pela_extensao("dados.csv.GZ"),
+            Some(Compressao::Gzip)
+        );
+        assert_eq!(
+            Compressao::pela_extensao("dados.json.zst"),
+            Some(Compressao::Zstd)
+        );
+        assert_eq!(Compressao::pela_extensao("dados.csv"), None);
+        assert_eq!(Compressao::sem_extensao("dados.osm.bz2"), "dados.osm");
+        assert_eq!(Compressao::sem_extensao("dados.csv"), "dados.csv");
+    }
+
+    #[test]
+    fn test_compactar_e_descompactar() {
+        for compressao in [Compressao::Gzip, Compressao::Zstd, Compressao::Bzip2] {
+            let mut compactador = compressao.compactar(Vec::new()).unwrap();
+            compactador.write_all(b"construtora,cidade\n").unwrap();
+            let compactado = compactador.concluir().unwrap();
+            assert_eq!(Compressao::pelo_conteudo(&compactado), Some(compressao));
+
+            let mut texto = String::new();
+            descompactar_se_preciso(&compactado[..], None)
+                .unwrap()
+                .read_to_string(&mut texto)
+                .unwrap();
+            assert_eq!(texto, "construtora,cidade\n");
+        }
+
+        let mut texto = String::new();
+        descompactar_se_preciso(&b"a,b"[..], None)
+            .unwrap()
+            .read_to_string(&mut texto)
+            .unwrap();
+        assert_eq!(texto, "a,b");
+    }
+}
@@ -1,19 +1,100 @@
+use super::Entrada;
+use crate::models::arrow_converter;
+use crate::models::dialeto_csv::{self, Codificacao, DialetoCsv};
 use crate::models::empreendimento::Empreendimento;
-use csv::ReaderBuilder;
+use crate::models::field_converter::normalize_field_name;
+use csv::{ReaderBuilder, StringRecord};
 use std::error::Error;
 
-pub fn read_csv(path: &str) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
-    let mut rdr = ReaderBuilder::new().has_headers(true).from_path(path)?;
+/// Ajustes do dialeto do CSV. Os campos informados substituem os detectados
+/// pelo conteúdo; o separador de milhar acompanha o separador decimal.
+#[derive(Debug, Clone, Default)]
+pub struct OpcoesCsv {
+    pub delimitador: Option<u8>,
+    pub decimal: Option<char>,
+    pub codificacao: Option<Codificacao>,
+}
+
+impl OpcoesCsv {
+    /// Dialeto detectado nos bytes do arquivo, com os ajustes aplicados.
+    pub fn dialeto_para(&self, bytes: &[u8]) -> DialetoCsv {
+        let detectado = DialetoCsv::detectar(bytes, coluna_numerica);
+        let delimitador = self.delimitador.unwrap_or(detectado.delimitador);
+        let decimal = self.decimal.unwrap_or(detectado.decimal);
+        DialetoCsv {
+            delimitador,
+            decimal,
+            milhar: if (delimitador, decimal) == (detectado.delimitador, detectado.decimal) {
+                detectado.milhar
+            } else {
+                dialeto_csv::milhar_para(decimal, delimitador)
+            },
+            codificacao: self.codificacao.unwrap_or(detectado.codificacao),
+            bom: detectado.bom,
+        }
+    }
+}
+
+pub fn read_csv<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    read_csv_com_opcoes(entrada, &OpcoesCsv::default())
+}
+
+/// Lê um CSV em qualquer dialeto: `,` ou `;` (também tabulação e `|`),
+/// números como `1200.50` ou `1.200,50`, UTF-8 com ou sem BOM ou
+/// Windows-1252/Latin-1. O dialeto é detectado pelo conteúdo, e as
+/// `opcoes` permitem fixar cada parte dele.
+pub fn read_csv_com_opcoes<'a>(
+    entrada: impl Into<Entrada<'a>>,
+    opcoes: &OpcoesCsv,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let bytes = entrada.into().bytes()?;
+    let dialeto = opcoes.dialeto_para(&bytes);
+    let texto = dialeto.decodificar(&bytes);
+
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(true)
+        .delimiter(dialeto.delimitador)
+        .from_reader(texto.as_bytes());
+    let headers = rdr.headers()?.clone();
+    let numericas: Vec<bool> = headers.iter().map(coluna_numerica).collect();
     let mut results = Vec::new();
 
-    for record in rdr.deserialize() {
-        let empreendimento: Empreendimento = record?;
+    for record in rdr.records() {
+        let record = record?;
+        let mut normalizado = StringRecord::with_capacity(record.as_slice().len(), record.len());
+        for ((valor, &numerica), coluna) in record.iter().zip(&numericas).zip(&headers) {
+            if !numerica {
+                normalizado.push_field(valor);
+                continue;
+            }
+            let numero = dialeto.normalizar_numero(valor).ok_or_else(|| {
+                format!(
+                    "Linha {}, coluna '{}': esperado número com decimal '{}', encontrado \"{}\"",
+                    record.position().map_or(0, |p| p.line()),
+                    normalize_field_name(coluna.trim()),
+                    dialeto.decimal,
+                    valor
+                )
+            })?;
+            normalizado.push_field(&numero);
+        }
+        normalizado.set_position(record.position().cloned());
+        let empreendimento: Empreendimento = normalizado.deserialize(Some(&headers))?;
         results.push(empreendimento);
     }
 
     Ok(results)
 }
 
+/// Colunas numéricas do esquema, pelo nome do cabeçalho.
+fn coluna_numerica(coluna: &str) -> bool {
+    arrow_converter::schema()
+        .field_with_name(normalize_field_name(coluna.trim()))
+        .is_ok_and(|campo| campo.data_type().is_numeric())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,14 +110,14 @@ mod tests {
     #[test]
     fn test_read_csv_kebab_case() {
         let csv_content = "construtora,cidade,area-do-terreno,numero-de-torres,altura-da-torre,area-da-torre,area-de-lazer\nAlpha,São Paulo,1000.0,2,25.0,300.0,150.0";
-        
+
         let temp_file = create_temp_csv(csv_content);
         let result = read_csv(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Alpha");
         assert_eq!(emp.cidade, "São Paulo");
@@ -47,17 +128,28 @@ mod tests {
         assert_eq!(emp.area_de_lazer, Some(150.0));
     }
 
+    #[test]
+    fn test_read_csv_de_um_leitor() {
+        let csv = "construtora;cidade;area_do_terreno;numero_de_torres;altura_da_torre;area_da_torre\nAlpha;Curitiba;1.000,5;2;25;300\n";
+
+        let empreendimentos = read_csv(Entrada::leitor(csv.as_bytes())).unwrap();
+
+        assert_eq!(empreendimentos.len(), 1);
+        assert_eq!(empreendimentos[0].cidade, "Curitiba");
+        assert_eq!(empreendimentos[0].area_do_terreno, 1000.5);
+    }
+
     #[test]
     fn test_read_csv_snake_case() {
         let csv_content = "construtora,cidade,area_do_terreno,numero_de_torres,altura_da_torre,area_da_torre,area_de_lazer\nBeta,Rio de Janeiro,800.0,3,30.0,250.0,100.0";
-        
+
         let temp_file = create_temp_csv(csv_content);
         let result = read_csv(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Beta");
         assert_eq!(emp.cidade, "Rio de Janeiro");
@@ -71,14 +163,14 @@ mod tests {
     #[test]
     fn test_read_csv_without_area_de_lazer() {
         let csv_content = "construtora,cidade,area-do-terreno,numero-de-torres,altura-da-torre,area-da-torre\nDelta,Salvador,600.0,1,20.0,500.0";
-        
+
         let temp_file = create_temp_csv(csv_content);
         let result = read_csv(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Delta");
         assert_eq!(emp.cidade, "Salvador");
@@ -88,4 +180,121 @@ mod tests {
         assert_eq!(emp.area_da_torre, 500.0);
         assert_eq!(emp.area_de_lazer, None);
     }
+
+    #[test]
+    fn test_read_csv_with_indices_urbanisticos() {
+        let csv_content = "construtora,cidade,area-do-terreno,numero-de-torres,altura-da-torre,area-da-torre,area-de-lazer,area-construida,area-permeavel,area-de-projecao,zona\nAlpha,São Paulo,1000.0,2,25.0,300.0,150.0,2500.0,200.0,550.0,ZM\nBeta,São Paulo,1000.0,2,25.0,300.0,150.0,,,,";
+
+        let temp_file = create_temp_csv(csv_content);
+        let empreendimentos = read_csv(temp_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(empreendimentos.len(), 2);
+
+        let emp = &empreendimentos[0];
+        assert_eq!(emp.area_construida, Some(2500.0));
+        assert_eq!(emp.area_permeavel, Some(200.0));
+        assert_eq!(emp.area_de_projecao, Some(550.0));
+        assert_eq!(emp.zona.as_deref(), Some("ZM"));
+
+        let emp = &empreendimentos[1];
+        assert_eq!(emp.area_construida, None);
+        assert_eq!(emp.area_permeavel, None);
+        assert_eq!(emp.area_de_projecao, None);
+        assert_eq!(emp.zona, None);
+    }
+
+    #[test]
+    fn test_read_csv_with_torres_e_lote_em_json() {
+        let csv_content = r#"construtora,cidade,area-do-terreno,numero-de-torres,altura-da-torre,area-da-torre,torres,lote
+Alpha,São Paulo,3000.0,1,25.0,225.0,"[{""nome"":""A"",""projecao"":[[5,5],[20,5],[20,20],[5,20]],""altura"":40.0}]","[[0,0],[60,0],[60,50],[0,50]]"
+Beta,São Paulo,3000.0,1,25.0,225.0,,"#;
+
+        let temp_file = create_temp_csv(csv_content);
+        let empreendimentos = read_csv(temp_file.path().to_str().unwrap()).unwrap();
+
+        let torres = empreendimentos[0].torres.as_ref().unwrap();
+        assert_eq!(torres.len(), 1);
+        assert_eq!(torres[0].nome.as_deref(), Some("A"));
+        assert_eq!(torres[0].altura, Some(40.0));
+        assert_eq!(torres[0].projecao.area(), 225.0);
+        assert_eq!(empreendimentos[0].lote.as_ref().unwrap().area(), 3000.0);
+
+        assert!(empreendimentos[1].torres.is_none());
+        assert!(empreendimentos[1].lote.is_none());
+    }
+
+    fn create_temp_bytes(bytes: &[u8]) -> NamedTempFile {
+        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
+        temp_file.write_all(bytes).unwrap();
+        temp_file
+    }
+
+    #[test]
+    fn test_read_csv_excel_brasileiro_em_windows_1252() {
+        let csv_content = "construtora;cidade;area-do-terreno;numero-de-torres;altura-da-torre;area-da-torre;area-de-lazer;zona\r\nAlpha;Jundiaí;1.200,50;2;25,5;300;;ZR 1,5\r\n";
+        let (bytes, _, _) = encoding_rs::WINDOWS_1252.encode(csv_content);
+
+        let temp_file = create_temp_bytes(&bytes);
+        let empreendimentos = read_csv(temp_file.path().to_str().unwrap()).unwrap();
+
+        let emp = &empreendimentos[0];
+        assert_eq!(emp.cidade, "Jundiaí");
+        assert_eq!(emp.area_do_terreno, 1200.5);
+        assert_eq!(emp.altura_da_torre, 25.5);
+        assert_eq!(emp.area_de_lazer, None);
+        assert_eq!(
+            emp.zona.as_deref(),
+            Some("ZR 1,5"),
+            "Texto não é convertido"
+        );
+    }
+
+    #[test]
+    fn test_read_csv_com_bom_utf8() {
+        let csv_content = "\u{feff}construtora,cidade,area_do_terreno,numero_de_torres,altura_da_torre,area_da_torre\nAlpha,São Paulo,1000.0,2,25.0,300.0\n";
+
+        let temp_file = create_temp_bytes(csv_content.as_bytes());
+        let empreendimentos = read_csv(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(empreendimentos[0].construtora, "Alpha");
+        assert_eq!(empreendimentos[0].cidade, "São Paulo");
+    }
+
+    #[test]
+    fn test_read_csv_opcoes_substituem_o_dialeto_detectado() {
+        // Sem outros números, `1.200` com `;` é lido como milhar.
+        let csv_content = "construtora;cidade;area_do_terreno;numero_de_torres;altura_da_torre;area_da_torre\nAlpha;Curitiba;1.200;2;25;300";
+        let temp_file = create_temp_csv(csv_content);
+        let path = temp_file.path().to_str().unwrap();
+
+        assert_eq!(read_csv(path).unwrap()[0].area_do_terreno, 1200.0);
+
+        let opcoes = OpcoesCsv {
+            decimal: Some('.'),
+            ..Default::default()
+        };
+        assert_eq!(
+            read_csv_com_opcoes(path, &opcoes).unwrap()[0].area_do_terreno,
+            1.2
+        );
+
+        let opcoes = OpcoesCsv {
+            delimitador: Some(b','),
+            ..Default::default()
+        };
+        assert!(read_csv_com_opcoes(path, &opcoes).is_err());
+    }
+
+    #[test]
+    fn test_read_csv_erro_informa_a_linha_do_valor_original() {
+        let csv_content = "construtora;cidade;area_do_terreno;numero_de_torres;altura_da_torre;area_da_torre\nAlpha;Curitiba;1.200,5;2;25;300\nBeta;Curitiba;12.50;2;25;300";
+        let temp_file = create_temp_csv(csv_content);
+
+        let erro = read_csv(temp_file.path().to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            erro,
+            "Linha 3, coluna 'area_do_terreno': esperado número com decimal ',', encontrado \"12.50\""
+        );
+    }
 }
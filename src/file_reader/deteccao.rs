@@ -0,0 +1,287 @@
+use super::compressao::descompactar_se_preciso;
+use super::{Entrada, FileType};
+use std::error::Error;
+use std::io::Read;
+
+/// Bytes do início do arquivo examinados na detecção.
+const AMOSTRA: usize = 8192;
+/// Linhas completas da amostra comparadas na detecção do CSV.
+const LINHAS_CSV: usize = 20;
+const DELIMITADORES: [u8; 4] = [b',', b';', b'\t', b'|'];
+const BOM_UTF8: &[u8; 3] = b"\xEF\xBB\xBF";
+
+/// Tipo do arquivo em `path`: pelas assinaturas do conteúdo e, na falta
+/// delas, pela extensão e por fim pela aparência de CSV. Devolve um erro,
+/// para que o tipo seja informado, quando nenhum formato é reconhecido ou
+/// quando a assinatura contradiz a extensão (`dados.csv` com conteúdo JSON).
+///
+/// Arquivos compactados são examinados já descompactados, e a extensão da
+/// compressão é ignorada: `dados.csv.gz` é CSV.
+pub fn detectar_tipo(path: &str) -> Result<FileType, Box<dyn Error>> {
+    let mut amostra = Vec::with_capacity(AMOSTRA);
+    Entrada::Caminho(path)
+        .abrir()?
+        .take(AMOSTRA as u64)
+        .read_to_end(&mut amostra)?;
+
+    match (
+        detectar_pelo_conteudo(&amostra)?,
+        FileType::pela_extensao(path),
+    ) {
+        (Some(conteudo), Some(extensao)) if conteudo != extensao => {
+            return Err(format!(
+                "Formato de '{}' ambíguo: o conteúdo indica {}, mas a extensão indica {}; informe o formato explicitamente",
+                path, conteudo, extensao
+            )
+            .into());
+        }
+        (Some(tipo), _) | (None, Some(tipo)) => return Ok(tipo),
+        (None, None) => {}
+    }
+    if parece_csv(&amostra) {
+        return Ok(FileType::Csv);
+    }
+    Err(format!(
+        "Não foi possível detectar o formato de '{}' pelo conteúdo nem pela extensão; informe o formato explicitamente",
+        path
+    )
+    .into())
+}
+
+/// Tipo de um conteúdo sem nome de arquivo, como o lido da entrada padrão:
+/// pelas assinaturas e, na falta delas, pela aparência de CSV. Conteúdo
+/// compactado é examinado já descompactado.
+pub fn detectar_tipo_do_conteudo(bytes: &[u8]) -> Result<FileType, Box<dyn Error>> {
+    let mut amostra = Vec::with_capacity(AMOSTRA);
+    descompactar_se_preciso(bytes, None)?
+        .take(AMOSTRA as u64)
+        .read_to_end(&mut amostra)?;
+    if let Some(tipo) = detectar_pelo_conteudo(&amostra)? {
+        return Ok(tipo);
+    }
+    if parece_csv(&amostra) {
+        return Ok(FileType::Csv);
+    }
+    Err("Não foi possível detectar o formato da entrada pelo conteúdo; informe o formato explicitamente".into())
+}
+
+/// Tipo indicado por uma assinatura inequívoca no início do conteúdo:
+/// `PAR1` do Parquet, `ARROW1` ou o marcador de fluxo do Arrow IPC, o
+/// cabeçalho de um PBF do OpenStreetMap, o ZIP das planilhas, `<?xml` ou um
+/// elemento (`<osm>` é OpenStreetMap), `[`/`{` do JSON, o cabeçalho STEP do
+/// IFC e a primeira seção do DXF. O CSV não tem assinatura e fica de fora.
+pub fn detectar_pelo_conteudo(bytes: &[u8]) -> Result<Option<FileType>, String> {
+    if bytes.starts_with(b"PAR1") {
+        return Ok(Some(FileType::Parquet));
+    }
+    if bytes.starts_with(b"ARROW1") || bytes.starts_with(&[0xFF; 4]) {
+        return Ok(Some(FileType::Arrow));
+    }
+    if eh_osm_pbf(bytes) {
+        return Ok(Some(FileType::Osm));
+    }
+    if bytes.starts_with(b"PK\x03\x04") {
+        return planilha_compactada(bytes).map(Some);
+    }
+
+    let bytes = bytes.strip_prefix(BOM_UTF8).unwrap_or(bytes);
+    let texto = String::from_utf8_lossy(bytes);
+    let texto = texto.trim_start();
+    if texto.starts_with('<') {
+        return Ok(Some(if raiz_xml(texto) == Some("osm") {
+            FileType::Osm
+        } else {
+            FileType::Xml
+        }));
+    }
+    if texto.starts_with('[') || texto.starts_with('{') {
+        return Ok(Some(FileType::Json));
+    }
+    if texto.starts_with("ISO-10303-21") {
+        return Ok(Some(FileType::Ifc));
+    }
+    let mut linhas = texto.lines().map(str::trim);
+    if linhas.next() == Some("0") && linhas.next() == Some("SECTION") {
+        return Ok(Some(FileType::Dxf));
+    }
+    Ok(None)
+}
+
+/// O primeiro bloco de um PBF do OpenStreetMap é do tipo `OSMHeader`, logo
+/// após o tamanho do cabeçalho.
+pub(crate) fn eh_osm_pbf(bytes: &[u8]) -> bool {
+    bytes
+        .get(4..)
+        .is_some_and(|cabecalho| cabecalho.starts_with(b"\x0A\x09OSMHeader"))
+}
+
+/// Planilhas `.ods` começam pelo arquivo `mimetype` do OpenDocument; as
+/// `.xlsx` trazem `[Content_Types].xml` e a pasta `xl/`.
+fn planilha_compactada(bytes: &[u8]) -> Result<FileType, String> {
+    let contem = |trecho: &[u8]| bytes.windows(trecho.len()).any(|janela| janela == trecho);
+    if contem(b"application/vnd.oasis.opendocument.spreadsheet") {
+        Ok(FileType::Ods)
+    } else if contem(b"[Content_Types].xml") || contem(b"xl/") {
+        Ok(FileType::Xlsx)
+    } else {
+        Err("Arquivo compactado (ZIP) que não é uma planilha .xlsx nem .ods".to_string())
+    }
+}
+
+/// Nome do elemento raiz, pulando a declaração, instruções e comentários.
+fn raiz_xml(texto: &str) -> Option<&str> {
+    let mut resto = texto;
+    loop {
+        resto = resto.trim_start();
+        if let Some(comentario) = resto.strip_prefix("<!--") {
+            resto = &comentario[comentario.find("-->")? + 3..];
+        } else if resto.starts_with("<?") || resto.starts_with("<!") {
+            resto = &resto[resto.find('>')? + 1..];
+        } else {
+            let nome = resto.strip_prefix('<')?;
+            let fim = nome
+                .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .unwrap_or(nome.len());
+            return Some(&nome[..fim]);
+        }
+    }
+}
+
+/// O conteúdo parece CSV quando, para algum delimitador, o cabeçalho tem
+/// ao menos duas colunas e as linhas completas da amostra têm o mesmo número
+/// de campos.
+fn parece_csv(bytes: &[u8]) -> bool {
+    let texto = String::from_utf8_lossy(bytes);
+    // A última linha pode ter sido cortada pela amostra.
+    let completo = if bytes.len() < AMOSTRA {
+        &texto[..]
+    } else {
+        texto.rfind('\n').map_or(&texto[..], |fim| &texto[..fim])
+    };
+    if completo.contains('\0') {
+        return false;
+    }
+
+    DELIMITADORES.iter().any(|&delimitador| {
+        let mut leitor = csv::ReaderBuilder::new()
+            .delimiter(delimitador)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(completo.as_bytes());
+        let mut contagens = leitor
+            .records()
+            .take(LINHAS_CSV)
+            .map(|registro| registro.map(|r| r.len()));
+        match contagens.next() {
+            Some(Ok(colunas)) if colunas >= 2 => contagens.all(|n| n.ok() == Some(colunas)),
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn arquivo(sufixo: &str, conteudo: &[u8]) -> NamedTempFile {
+        let mut arquivo = NamedTempFile::with_suffix(sufixo).unwrap();
+        arquivo.write_all(conteudo).unwrap();
+        arquivo
+    }
+
+    fn detectar(sufixo: &str, conteudo: &[u8]) -> Result<FileType, Box<dyn Error>> {
+        let arquivo = arquivo(sufixo, conteudo);
+        detectar_tipo(arquivo.path().to_str().unwrap())
+    }
+
+    #[test]
+    fn test_detectar_pelas_assinaturas() {
+        let casos: [(&[u8], FileType); 9] = [
+            (b"PAR1\x15\x04", FileType::Parquet),
+            (b"ARROW1\0\0", FileType::Arrow),
+            (b"\xFF\xFF\xFF\xFF\x10\x01\0\0", FileType::Arrow),
+            (
+                b"\xEF\xBB\xBF<?xml version=\"1.0\"?>\n<empreendimentos/>",
+                FileType::Xml,
+            ),
+            (
+                b"<?xml version='1.0'?>\n<!-- extrato -->\n<osm version=\"0.6\">",
+                FileType::Osm,
+            ),
+            (b"  \n[{\"construtora\": \"Alfa\"}]", FileType::Json),
+            (b"ISO-10303-21;\nHEADER;", FileType::Ifc),
+            (b"  0\r\nSECTION\r\n  2\r\nHEADER", FileType::Dxf),
+            (b"\0\0\0\x0D\x0A\x09OSMHeader\x18", FileType::Osm),
+        ];
+        for (conteudo, esperado) in casos {
+            assert_eq!(
+                detectar(".txt", conteudo).unwrap(),
+                esperado,
+                "{:?}",
+                conteudo
+            );
+        }
+    }
+
+    #[test]
+    fn test_conteudo_que_contradiz_a_extensao_pede_o_tipo() {
+        let erro = detectar(".csv", b"[]").unwrap_err().to_string();
+        assert!(
+            erro.contains("o conteúdo indica json, mas a extensão indica csv"),
+            "{}",
+            erro
+        );
+        assert!(erro.contains("informe o formato"), "{}", erro);
+
+        assert_eq!(detectar(".json", b"[]").unwrap(), FileType::Json);
+        assert_eq!(detectar(".feather", b"ARROW1").unwrap(), FileType::Arrow);
+        assert_eq!(detectar("", b"PAR1").unwrap(), FileType::Parquet);
+    }
+
+    #[test]
+    fn test_detectar_csv_sem_extensao() {
+        let csv =
+            b"construtora;cidade;area_do_terreno\nAlfa;Curitiba;1.200,50\nBeta;Jundia\xED;800\n";
+        assert_eq!(detectar("", csv).unwrap(), FileType::Csv);
+        assert_eq!(
+            detectar(".txt", b"construtora,cidade\n").unwrap(),
+            FileType::Csv
+        );
+    }
+
+    #[test]
+    fn test_conteudo_ambiguo_pede_o_tipo() {
+        let erro = detectar(".txt", b"apenas uma frase solta\nsem colunas").unwrap_err();
+        assert!(erro.to_string().contains("informe o formato"), "{}", erro);
+
+        let erro = detectar(".dat", b"a,b\n1,2,3\n").unwrap_err();
+        assert!(erro.to_string().contains("informe o formato"), "{}", erro);
+
+        let erro = detectar(".xlsx", b"PK\x03\x04\x14\0\0\0documento.txt").unwrap_err();
+        assert!(erro.to_string().contains("ZIP"), "{}", erro);
+    }
+
+    #[test]
+    fn test_detectar_conteudo_sem_nome() {
+        assert_eq!(
+            detectar_tipo_do_conteudo(b"PAR1\x15\x04").unwrap(),
+            FileType::Parquet
+        );
+        assert_eq!(
+            detectar_tipo_do_conteudo(b"construtora;cidade\nAlfa;Curitiba\n").unwrap(),
+            FileType::Csv
+        );
+        let erro = detectar_tipo_do_conteudo(b"construtora\nAlfa\n").unwrap_err();
+        assert!(erro.to_string().contains("informe o formato"), "{}", erro);
+    }
+
+    #[test]
+    fn test_sem_assinatura_usa_a_extensao() {
+        assert_eq!(
+            detectar(".csv", b"construtora\nAlfa\n").unwrap(),
+            FileType::Csv
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use super::Entrada;
+use super::auxiliar::{ler_arquivo_auxiliar, normalizar_chave, numero};
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::{Coordenada, Poligono};
+use crate::models::lote::Lote;
+use crate::models::torre::Torre;
+use dxf::Drawing;
+use dxf::entities::EntityType;
+use dxf::enums::Units;
+use serde_json::{Map, Value};
+use std::error::Error;
+
+/// Camadas do desenho de onde são lidos o lote e as projeções das torres.
+#[derive(Debug, Clone)]
+pub struct OpcoesDxf {
+    pub camada_lote: String,
+    pub camada_torres: String,
+}
+
+impl Default for OpcoesDxf {
+    fn default() -> Self {
+        Self {
+            camada_lote: "LOTE".to_string(),
+            camada_torres: "TORRES".to_string(),
+        }
+    }
+}
+
+const CAMPOS_TEXTO: [&str; 5] = ["construtora", "cidade", "uso", "zona", "nome"];
+
+pub fn read_dxf<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    read_dxf_com_opcoes(entrada, &OpcoesDxf::default())
+}
+
+/// Lê um desenho DXF: cada polilinha fechada na camada do lote gera um
+/// empreendimento, com as torres cujas projeções estão dentro dele.
+///
+/// Os demais dados vêm de blocos com atributos inseridos no lote (ou na torre,
+/// para `nome` e `altura`) e do arquivo auxiliar `<desenho>.dxf.json`, se existir.
+pub fn read_dxf_com_opcoes<'a>(
+    entrada: impl Into<Entrada<'a>>,
+    opcoes: &OpcoesDxf,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let entrada = entrada.into();
+    let auxiliar = ler_arquivo_auxiliar(entrada.caminho())?;
+    let drawing = Drawing::load(&mut entrada.abrir()?)?;
+    let escala = escala_para_metros(drawing.header.default_drawing_units);
+
+    let mut lotes = Vec::new();
+    let mut torres = Vec::new();
+    let mut blocos = Vec::new();
+
+    for entidade in drawing.entities() {
+        let camada = entidade.common.layer.as_str();
+        let contorno = match &entidade.specific {
+            EntityType::LwPolyline(polilinha) if polilinha.is_closed() => Some(Poligono(
+                polilinha
+                    .vertices
+                    .iter()
+                    .map(|v| [v.x * escala, v.y * escala])
+                    .collect(),
+            )),
+            EntityType::Polyline(polilinha) if polilinha.is_closed() => Some(Poligono(
+                polilinha
+                    .vertices()
+                    .map(|v| [v.location.x * escala, v.location.y * escala])
+                    .collect(),
+            )),
+            EntityType::Insert(bloco) => {
+                let atributos: Map<String, Value> = bloco
+                    .attributes()
+                    .map(|a| campo(&a.attribute_tag, &a.value))
+                    .collect();
+                if !atributos.is_empty() {
+                    let ponto = [bloco.location.x * escala, bloco.location.y * escala];
+                    blocos.push((ponto, atributos));
+                }
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(contorno) = contorno {
+            if camada.eq_ignore_ascii_case(&opcoes.camada_lote) {
+                lotes.push(contorno);
+            } else if camada.eq_ignore_ascii_case(&opcoes.camada_torres) {
+                torres.push(contorno);
+            }
+        }
+    }
+
+    if lotes.is_empty() {
+        return Err(format!(
+            "Nenhuma polilinha fechada na camada '{}' do desenho",
+            opcoes.camada_lote
+        )
+        .into());
+    }
+
+    let atributos_em = |contorno: &Poligono| -> Map<String, Value> {
+        blocos
+            .iter()
+            .filter(|(ponto, _)| contorno.contem(*ponto))
+            .flat_map(|(_, atributos)| atributos.clone())
+            .collect()
+    };
+
+    lotes
+        .iter()
+        .enumerate()
+        .map(|(indice, contorno)| {
+            let torres_do_lote: Vec<&Poligono> = torres
+                .iter()
+                .filter(|torre| contorno.contem(torre.centroide()))
+                .collect();
+            let dentro_de_torre =
+                |ponto: Coordenada| torres_do_lote.iter().any(|torre| torre.contem(ponto));
+
+            let mut dados = auxiliar.clone();
+            for (ponto, atributos) in &blocos {
+                if contorno.contem(*ponto) && !dentro_de_torre(*ponto) {
+                    dados.extend(atributos.clone());
+                }
+            }
+
+            let torres_do_lote: Vec<Torre> = torres_do_lote
+                .into_iter()
+                .map(|projecao| {
+                    let atributos = atributos_em(projecao);
+                    Torre {
+                        nome: atributos
+                            .get("nome")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        projecao: projecao.clone(),
+                        altura: atributos.get("altura").and_then(Value::as_f64),
+                    }
+                })
+                .collect();
+
+            completar_com_geometria(&mut dados, contorno, &torres_do_lote)?;
+            serde_json::from_value(Value::Object(dados))
+                .map_err(|e| format!("Lote {} do desenho: {}", indice + 1, e).into())
+        })
+        .collect()
+}
+
+/// Preenche os campos que o desenho permite calcular, sem sobrescrever os informados.
+fn completar_com_geometria(
+    dados: &mut Map<String, Value>,
+    contorno: &Poligono,
+    torres: &[Torre],
+) -> Result<(), serde_json::Error> {
+    let areas: Vec<f64> = torres.iter().map(|t| t.projecao.area()).collect();
+    let mut calculado = |nome: &str, valor: Value| {
+        dados.entry(nome).or_insert(valor);
+    };
+
+    calculado("area_do_terreno", numero(contorno.area()));
+    calculado("numero_de_torres", Value::from(torres.len()));
+    let media = if areas.is_empty() {
+        0.0
+    } else {
+        areas.iter().sum::<f64>() / areas.len() as f64
+    };
+    calculado("area_da_torre", numero(media));
+    if !areas.is_empty() {
+        calculado("area_de_projecao", numero(areas.iter().sum()));
+    }
+    if let Some(altura) = torres.iter().filter_map(|t| t.altura).reduce(f64::max) {
+        calculado("altura_da_torre", numero(altura));
+    }
+
+    dados.insert(
+        "lote".to_string(),
+        serde_json::to_value(Lote::local(contorno.clone()))?,
+    );
+    if !torres.is_empty() {
+        dados.insert("torres".to_string(), serde_json::to_value(torres)?);
+    }
+    Ok(())
+}
+
+fn campo(tag: &str, valor: &str) -> (String, Value) {
+    let chave = normalizar_chave(tag);
+    let valor = valor.trim();
+    let valor = if CAMPOS_TEXTO.contains(&chave.as_str()) {
+        Value::String(valor.to_string())
+    } else if let Ok(inteiro) = valor.parse::<i64>() {
+        Value::from(inteiro)
+    } else {
+        match valor.replace(',', ".").parse::<f64>() {
+            Ok(real) => numero(real),
+            Err(_) => Value::String(valor.to_string()),
+        }
+    };
+    (chave, valor)
+}
+
+fn escala_para_metros(unidades: Units) -> f64 {
+    match unidades {
+        Units::Millimeters => 0.001,
+        Units::Centimeters => 0.01,
+        Units::Decimeters => 0.1,
+        Units::Inches => 0.0254,
+        Units::Feet => 0.3048,
+        _ => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Display;
+    use std::fs;
+    use std::path::Path;
+
+    /// Desenho DXF em texto, no formato exportado pelos programas de CAD.
+    struct Desenho(String);
+
+    impl Desenho {
+        fn new(unidades: i32) -> Self {
+            let mut desenho = Desenho(String::new());
+            desenho.par(0, "SECTION").par(2, "HEADER");
+            desenho.par(9, "$ACADVER").par(1, "AC1021");
+            desenho.par(9, "$INSUNITS").par(70, unidades);
+            desenho
+                .par(0, "ENDSEC")
+                .par(0, "SECTION")
+                .par(2, "ENTITIES");
+            desenho
+        }
+
+        fn par(&mut self, codigo: i32, valor: impl Display) -> &mut Self {
+            self.0.push_str(&format!("{}\n{}\n", codigo, valor));
+            self
+        }
+
+        fn polilinha(&mut self, camada: &str, pontos: &[[f64; 2]]) -> &mut Self {
+            self.par(0, "LWPOLYLINE").par(8, camada);
+            self.par(90, pontos.len()).par(70, 1);
+            for p in pontos {
+                self.par(10, p[0]).par(20, p[1]);
+            }
+            self
+        }
+
+        fn bloco(&mut self, ponto: [f64; 2], atributos: &[(&str, &str)]) -> &mut Self {
+            self.par(0, "INSERT").par(8, "0").par(66, 1).par(2, "DADOS");
+            self.par(10, ponto[0]).par(20, ponto[1]).par(30, 0.0);
+            for (tag, valor) in atributos {
+                self.par(0, "ATTRIB").par(8, "0");
+                self.par(10, ponto[0]).par(20, ponto[1]).par(30, 0.0);
+                self.par(40, 1.0).par(1, valor).par(2, tag).par(70, 0);
+            }
+            self.par(0, "SEQEND")
+        }
+
+        fn salvar(&mut self, path: &Path) {
+            self.par(0, "ENDSEC").par(0, "EOF");
+            fs::write(path, &self.0).unwrap();
+        }
+    }
+
+    fn retangulo(x: f64, y: f64, largura: f64, altura: f64) -> Vec<[f64; 2]> {
+        vec![
+            [x, y],
+            [x + largura, y],
+            [x + largura, y + altura],
+            [x, y + altura],
+        ]
+    }
+
+    #[test]
+    fn test_read_dxf_com_atributos_de_bloco() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("projeto.dxf");
+        Desenho::new(6)
+            .polilinha("LOTE", &retangulo(0.0, 0.0, 40.0, 50.0))
+            .polilinha("TORRES", &retangulo(5.0, 5.0, 10.0, 20.0))
+            .polilinha("TORRES", &retangulo(25.0, 5.0, 10.0, 10.0))
+            .polilinha("COTAS", &retangulo(0.0, 0.0, 1.0, 1.0))
+            .bloco(
+                [20.0, 40.0],
+                &[
+                    ("CONSTRUTORA", "Delta"),
+                    ("CIDADE", "São Paulo"),
+                    ("AREA-DE-LAZER", "250,5"),
+                ],
+            )
+            .bloco([10.0, 10.0], &[("NOME", "A"), ("ALTURA", "28")])
+            .bloco([30.0, 10.0], &[("NOME", "B"), ("ALTURA", "21.5")])
+            .salvar(&path);
+
+        let empreendimentos = read_dxf(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(empreendimentos.len(), 1);
+        let e = &empreendimentos[0];
+        assert_eq!(e.construtora, "Delta");
+        assert_eq!(e.cidade, "São Paulo");
+        assert_eq!(e.area_do_terreno, 2000.0);
+        assert_eq!(e.numero_de_torres, 2);
+        assert_eq!(e.area_da_torre, 150.0);
+        assert_eq!(e.area_de_projecao, Some(300.0));
+        assert_eq!(e.altura_da_torre, 28.0);
+        assert_eq!(e.area_de_lazer, Some(250.5));
+
+        let torres = e.torres.as_ref().unwrap();
+        assert_eq!(torres[0].nome.as_deref(), Some("A"));
+        assert_eq!(torres[1].altura, Some(21.5));
+        assert_eq!(e.area_calculada_do_lote(), Some(2000.0));
+    }
+
+    #[test]
+    fn test_read_dxf_com_varios_lotes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("quadra.dxf");
+        Desenho::new(6)
+            .polilinha("LOTE", &retangulo(0.0, 0.0, 20.0, 20.0))
+            .polilinha("LOTE", &retangulo(30.0, 0.0, 20.0, 20.0))
+            .polilinha("TORRES", &retangulo(35.0, 5.0, 10.0, 10.0))
+            .bloco(
+                [5.0, 15.0],
+                &[
+                    ("CONSTRUTORA", "Zeta"),
+                    ("CIDADE", "Boituva"),
+                    ("ALTURA-DA-TORRE", "0"),
+                ],
+            )
+            .bloco(
+                [32.0, 18.0],
+                &[("CONSTRUTORA", "Eta"), ("CIDADE", "Boituva")],
+            )
+            .bloco([40.0, 10.0], &[("ALTURA", "18")])
+            .salvar(&path);
+
+        let empreendimentos = read_dxf(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(empreendimentos.len(), 2);
+        assert_eq!(empreendimentos[0].construtora, "Zeta");
+        assert_eq!(empreendimentos[0].numero_de_torres, 0);
+        assert_eq!(empreendimentos[1].construtora, "Eta");
+        assert_eq!(empreendimentos[1].numero_de_torres, 1);
+        assert_eq!(empreendimentos[1].altura_da_torre, 18.0);
+    }
+
+    #[test]
+    fn test_read_dxf_com_arquivo_auxiliar_e_milimetros() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("projeto.dxf");
+        Desenho::new(4)
+            .polilinha("lote", &retangulo(0.0, 0.0, 30000.0, 20000.0))
+            .polilinha("torres", &retangulo(5000.0, 5000.0, 10000.0, 10000.0))
+            .salvar(&path);
+        fs::write(
+            temp_dir.path().join("projeto.dxf.json"),
+            r#"{"construtora": "Epsilon", "cidade": "Curitiba", "altura-da-torre": 24.0, "area-do-terreno": 610}"#,
+        )
+        .unwrap();
+
+        let empreendimentos = read_dxf(path.to_str().unwrap()).unwrap();
+
+        let e = &empreendimentos[0];
+        assert_eq!(e.construtora, "Epsilon");
+        assert_eq!(e.area_do_terreno, 610.0, "Valor informado prevalece");
+        assert_eq!(e.area_calculada_do_lote(), Some(600.0));
+        assert_eq!(e.area_da_torre, 100.0);
+        assert_eq!(e.altura_da_torre, 24.0);
+    }
+
+    #[test]
+    fn test_read_dxf_com_camadas_configuradas() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("projeto.dxf");
+        Desenho::new(6)
+            .polilinha("A-LOTE", &retangulo(0.0, 0.0, 20.0, 20.0))
+            .polilinha("A-EDIF", &retangulo(5.0, 5.0, 10.0, 10.0))
+            .bloco(
+                [2.0, 2.0],
+                &[
+                    ("CONSTRUTORA", "Teta"),
+                    ("CIDADE", "Boituva"),
+                    ("ALTURA-DA-TORRE", "12"),
+                ],
+            )
+            .salvar(&path);
+        let opcoes = OpcoesDxf {
+            camada_lote: "A-LOTE".to_string(),
+            camada_torres: "A-EDIF".to_string(),
+        };
+
+        let empreendimentos = read_dxf_com_opcoes(path.to_str().unwrap(), &opcoes).unwrap();
+
+        assert_eq!(empreendimentos[0].numero_de_torres, 1);
+        assert!(read_dxf(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_read_dxf_sem_lote() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("projeto.dxf");
+        Desenho::new(6)
+            .polilinha("TORRES", &retangulo(0.0, 0.0, 10.0, 10.0))
+            .salvar(&path);
+
+        let erro = read_dxf(path.to_str().unwrap()).unwrap_err();
+        assert!(erro.to_string().contains("camada 'LOTE'"));
+    }
+
+    #[test]
+    fn test_read_dxf_sem_metadados() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("projeto.dxf");
+        Desenho::new(6)
+            .polilinha("LOTE", &retangulo(0.0, 0.0, 10.0, 10.0))
+            .salvar(&path);
+
+        let erro = read_dxf(path.to_str().unwrap()).unwrap_err();
+        assert!(erro.to_string().contains("Lote 1 do desenho"), "{}", erro);
+    }
+}
@@ -0,0 +1,81 @@
+use super::compressao::{self, ASSINATURA, Compressao};
+use std::fs::File;
+use std::io::{self, Read};
+
+/// Origem dos dados lidos: um arquivo ou qualquer `Read`, como a entrada
+/// padrão ou um buffer em memória.
+///
+/// Conteúdo compactado com gzip, zstd ou bzip2 é descompactado ao ser lido,
+/// pela assinatura ou pela extensão (`dados.csv.gz`).
+///
+/// Só o caminho de um arquivo localiza os arquivos auxiliares de desenhos,
+/// modelos e extratos (`<arquivo>.json`); lidos de um `Read`, eles não são
+/// consultados.
+pub enum Entrada<'a> {
+    Caminho(&'a str),
+    Leitor(Box<dyn Read + 'a>),
+}
+
+impl<'a> Entrada<'a> {
+    pub fn leitor(leitor: impl Read + 'a) -> Self {
+        Entrada::Leitor(Box::new(leitor))
+    }
+
+    /// Caminho do arquivo, quando a entrada é um arquivo.
+    pub fn caminho(&self) -> Option<&'a str> {
+        match self {
+            Entrada::Caminho(path) => Some(path),
+            Entrada::Leitor(_) => None,
+        }
+    }
+
+    /// Caminho de um arquivo sem compressão, que os leitores de acesso
+    /// aleatório abrem diretamente; arquivos compactados e demais leitores
+    /// são lidos por [`Entrada::bytes`].
+    pub(crate) fn arquivo(&self) -> io::Result<Option<&'a str>> {
+        let Entrada::Caminho(path) = self else {
+            return Ok(None);
+        };
+        if Compressao::pela_extensao(path).is_some() {
+            return Ok(None);
+        }
+        let mut inicio = Vec::with_capacity(ASSINATURA);
+        File::open(path)?
+            .take(ASSINATURA as u64)
+            .read_to_end(&mut inicio)?;
+        Ok(Compressao::pelo_conteudo(&inicio)
+            .is_none()
+            .then_some(*path))
+    }
+
+    /// Abre a entrada para leitura sequencial, já descompactada.
+    pub(crate) fn abrir(self) -> io::Result<Box<dyn Read + 'a>> {
+        match self {
+            Entrada::Caminho(path) => compressao::descompactar_se_preciso(
+                File::open(path)?,
+                Compressao::pela_extensao(path),
+            ),
+            Entrada::Leitor(leitor) => compressao::descompactar_se_preciso(leitor, None),
+        }
+    }
+
+    /// Todo o conteúdo da entrada, para os formatos que precisam dele por
+    /// inteiro ou de acesso aleatório.
+    pub(crate) fn bytes(self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.abrir()?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl<'a> From<&'a str> for Entrada<'a> {
+    fn from(path: &'a str) -> Self {
+        Entrada::Caminho(path)
+    }
+}
+
+impl<'a> From<&'a String> for Entrada<'a> {
+    fn from(path: &'a String) -> Self {
+        Entrada::Caminho(path)
+    }
+}
@@ -0,0 +1,690 @@
+use super::Entrada;
+use super::auxiliar::{ler_arquivo_auxiliar, numero};
+use super::step::{Entidade, ModeloStep, Valor};
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::{Coordenada, Poligono};
+use crate::models::torre::Torre;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Read;
+
+/// Resultado da leitura de um modelo IFC, com os avisos sobre o que não pôde
+/// ser interpretado.
+#[derive(Debug, Default)]
+pub struct LeituraIfc {
+    pub empreendimentos: Vec<Empreendimento>,
+    pub avisos: Vec<String>,
+}
+
+const TIPOS_DE_LAJE: [&str; 3] = ["IFCSLAB", "IFCSLABSTANDARDCASE", "IFCSLABELEMENTEDCASE"];
+
+pub fn read_ifc<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    Ok(read_ifc_com_avisos(entrada)?.empreendimentos)
+}
+
+/// Lê um modelo IFC (STEP): cada `IfcSite` gera um empreendimento e cada
+/// `IfcBuilding` do terreno, uma torre.
+///
+/// Pavimentos (`IfcBuildingStorey`) dão a altura pelas elevações e as lajes
+/// (`IfcSlab`), a área construída e a projeção, pelas quantidades
+/// (`GrossArea`/`NetArea`) ou pela geometria extrudada. Dados ausentes no
+/// modelo podem vir do arquivo auxiliar `<modelo>.ifc.json`.
+pub fn read_ifc_com_avisos<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<LeituraIfc, Box<dyn Error>> {
+    let entrada = entrada.into();
+    let auxiliar = ler_arquivo_auxiliar(entrada.caminho())?;
+    let mut texto = String::new();
+    entrada.abrir()?.read_to_string(&mut texto)?;
+    let modelo = ModeloStep::ler(&texto)?;
+    let mut extrator = Extrator::new(&modelo);
+
+    if !modelo.esquema.starts_with("IFC2X3") && !modelo.esquema.starts_with("IFC4") {
+        extrator.avisar(format!(
+            "Esquema '{}' não suportado; a leitura considera IFC2X3 e IFC4",
+            modelo.esquema
+        ));
+    }
+
+    let mut empreendimentos = Vec::new();
+    for terreno in extrator.terrenos() {
+        let mut dados = auxiliar.clone();
+        extrator.completar(&mut dados, &terreno);
+        let empreendimento = serde_json::from_value(Value::Object(dados))
+            .map_err(|e| format!("Terreno '{}' do modelo IFC: {}", terreno.nome, e))?;
+        empreendimentos.push(empreendimento);
+    }
+
+    if empreendimentos.is_empty() {
+        return Err("Modelo IFC sem edificações (IfcBuilding)".into());
+    }
+
+    let mut avisos = modelo.avisos.clone();
+    avisos.extend(extrator.avisos);
+    Ok(LeituraIfc {
+        empreendimentos,
+        avisos,
+    })
+}
+
+struct Terreno {
+    id: Option<u64>,
+    nome: String,
+    edificios: Vec<u64>,
+}
+
+struct Pavimento {
+    elevacao: f64,
+    altura: Option<f64>,
+    area: f64,
+}
+
+struct Edificio {
+    nome: Option<String>,
+    altura: Option<f64>,
+    area_construida: f64,
+    area_de_projecao: f64,
+    projecao: Option<Poligono>,
+}
+
+/// Transformação no plano: origem e direção do eixo x local.
+#[derive(Clone, Copy)]
+struct Transformacao {
+    origem: Coordenada,
+    eixo_x: Coordenada,
+}
+
+impl Transformacao {
+    const IDENTIDADE: Self = Self {
+        origem: [0.0, 0.0],
+        eixo_x: [1.0, 0.0],
+    };
+
+    fn aplicar(&self, p: Coordenada) -> Coordenada {
+        let [cx, sx] = self.eixo_x;
+        [
+            self.origem[0] + p[0] * cx - p[1] * sx,
+            self.origem[1] + p[0] * sx + p[1] * cx,
+        ]
+    }
+
+    fn compor(&self, interna: &Transformacao) -> Transformacao {
+        let [cx, sx] = self.eixo_x;
+        let [ix, iy] = interna.eixo_x;
+        Transformacao {
+            origem: self.aplicar(interna.origem),
+            eixo_x: [ix * cx - iy * sx, ix * sx + iy * cx],
+        }
+    }
+}
+
+struct Extrator<'a> {
+    modelo: &'a ModeloStep,
+    escala: f64,
+    filhos: HashMap<u64, Vec<u64>>,
+    propriedades: HashMap<u64, Vec<u64>>,
+    avisos: Vec<String>,
+}
+
+impl<'a> Extrator<'a> {
+    fn new(modelo: &'a ModeloStep) -> Self {
+        let mut filhos: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut propriedades: HashMap<u64, Vec<u64>> = HashMap::new();
+
+        for entidade in modelo.entidades.values() {
+            let (pai, relacionados, destino) = match entidade.tipo.as_str() {
+                "IFCRELAGGREGATES" => (entidade.atributo(4), entidade.atributo(5), &mut filhos),
+                "IFCRELCONTAINEDINSPATIALSTRUCTURE" => {
+                    (entidade.atributo(5), entidade.atributo(4), &mut filhos)
+                }
+                "IFCRELDEFINESBYPROPERTIES" => {
+                    for objeto in entidade.atributo(4).refs() {
+                        if let Some(definicao) = entidade.atributo(5).como_ref() {
+                            propriedades.entry(objeto).or_default().push(definicao);
+                        }
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            if let Some(pai) = pai.como_ref() {
+                destino.entry(pai).or_default().extend(relacionados.refs());
+            }
+        }
+        for lista in filhos.values_mut() {
+            lista.sort_unstable();
+        }
+
+        let mut extrator = Self {
+            modelo,
+            escala: 1.0,
+            filhos,
+            propriedades,
+            avisos: Vec::new(),
+        };
+        extrator.escala = extrator.escala_de_comprimento();
+        extrator
+    }
+
+    fn avisar(&mut self, aviso: String) {
+        self.avisos.push(aviso);
+    }
+
+    fn entidade(&self, id: u64) -> Option<&'a Entidade> {
+        self.modelo.entidade(id)
+    }
+
+    fn resolver(&self, valor: &Valor) -> Option<&'a Entidade> {
+        self.modelo.resolver(valor)
+    }
+
+    fn filhos_do_tipo(&self, pai: u64, tipos: &[&str]) -> Vec<u64> {
+        self.filhos
+            .get(&pai)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|id| {
+                self.entidade(*id)
+                    .is_some_and(|e| tipos.contains(&e.tipo.as_str()))
+            })
+            .collect()
+    }
+
+    fn escala_de_comprimento(&mut self) -> f64 {
+        for (_, unidade) in self.modelo.do_tipo("IFCSIUNIT") {
+            if unidade.atributo(1).como_enum() == Some("LENGTHUNIT") {
+                return match unidade.atributo(2).como_enum() {
+                    Some("MILLI") => 0.001,
+                    Some("CENTI") => 0.01,
+                    Some("DECI") => 0.1,
+                    Some("KILO") => 1000.0,
+                    _ => 1.0,
+                };
+            }
+        }
+        if !self.modelo.do_tipo("IFCCONVERSIONBASEDUNIT").is_empty() {
+            self.avisar(
+                "Unidades convertidas (IfcConversionBasedUnit) não suportadas; comprimentos lidos em metros"
+                    .to_string(),
+            );
+        }
+        1.0
+    }
+
+    /// Terrenos do modelo; edificações fora de um `IfcSite` formam um terreno à parte.
+    fn terrenos(&mut self) -> Vec<Terreno> {
+        let mut terrenos: Vec<Terreno> = self
+            .modelo
+            .do_tipo("IFCSITE")
+            .into_iter()
+            .map(|(id, site)| Terreno {
+                id: Some(id),
+                nome: nome(site).unwrap_or_else(|| format!("#{}", id)),
+                edificios: self.filhos_do_tipo(id, &["IFCBUILDING"]),
+            })
+            .collect();
+
+        let avulsos: Vec<u64> = self
+            .modelo
+            .do_tipo("IFCBUILDING")
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| !terrenos.iter().any(|t| t.edificios.contains(id)))
+            .collect();
+        if !avulsos.is_empty() {
+            self.avisar(format!(
+                "{} edificação(ões) fora de um IfcSite agrupadas em um terreno sem área",
+                avulsos.len()
+            ));
+            terrenos.push(Terreno {
+                id: None,
+                nome: "sem IfcSite".to_string(),
+                edificios: avulsos,
+            });
+        }
+
+        terrenos.retain(|t| !t.edificios.is_empty());
+        terrenos
+    }
+
+    /// Preenche os dados do terreno, sem sobrescrever os do arquivo auxiliar.
+    fn completar(&mut self, dados: &mut Map<String, Value>, terreno: &Terreno) {
+        let edificios: Vec<Edificio> = terreno
+            .edificios
+            .iter()
+            .map(|id| self.edificio(*id))
+            .collect();
+        let site = terreno.id.and_then(|id| self.entidade(id));
+
+        let mut calculado = |nome: &str, valor: Option<Value>| {
+            if let Some(valor) = valor {
+                dados.entry(nome).or_insert(valor);
+            }
+        };
+
+        calculado("construtora", self.organizacao().map(Value::from));
+        calculado(
+            "cidade",
+            site.and_then(|s| self.cidade(s.atributo(13)))
+                .or_else(|| {
+                    terreno
+                        .edificios
+                        .iter()
+                        .filter_map(|id| self.entidade(*id))
+                        .find_map(|e| self.cidade(e.atributo(11)))
+                })
+                .map(Value::from),
+        );
+        calculado(
+            "area_do_terreno",
+            terreno
+                .id
+                .and_then(|id| self.quantidade(id, "IFCQUANTITYAREA", &["GrossArea", "NetArea"]))
+                .map(numero),
+        );
+        calculado("numero_de_torres", Some(Value::from(edificios.len())));
+        calculado(
+            "altura_da_torre",
+            edificios
+                .iter()
+                .filter_map(|e| e.altura)
+                .reduce(f64::max)
+                .map(numero),
+        );
+        let projecoes: Vec<f64> = edificios.iter().map(|e| e.area_de_projecao).collect();
+        calculado(
+            "area_da_torre",
+            Some(numero(
+                projecoes.iter().sum::<f64>() / projecoes.len().max(1) as f64,
+            )),
+        );
+        calculado("area_de_projecao", Some(numero(projecoes.iter().sum())));
+        calculado(
+            "area_construida",
+            Some(numero(edificios.iter().map(|e| e.area_construida).sum())),
+        );
+
+        if let Some(site) = site {
+            calculado("latitude", angulo_composto(site.atributo(9)).map(numero));
+            calculado("longitude", angulo_composto(site.atributo(10)).map(numero));
+            calculado(
+                "cota_do_terreno",
+                site.atributo(11)
+                    .como_f64()
+                    .map(|c| numero(c * self.escala)),
+            );
+        }
+
+        if edificios.iter().all(|e| e.projecao.is_some()) {
+            let torres: Vec<Torre> = edificios
+                .into_iter()
+                .map(|e| Torre {
+                    nome: e.nome,
+                    projecao: e.projecao.unwrap_or_default(),
+                    altura: e.altura,
+                })
+                .collect();
+            calculado("torres", serde_json::to_value(torres).ok());
+        }
+    }
+
+    fn edificio(&mut self, id: u64) -> Edificio {
+        let nome_do_edificio = self.entidade(id).and_then(nome);
+        let identificacao = nome_do_edificio
+            .clone()
+            .unwrap_or_else(|| format!("#{}", id));
+
+        let mut pavimentos = Vec::new();
+        let mut maior_laje: Option<(f64, Poligono)> = None;
+        for pavimento in self.filhos_do_tipo(id, &["IFCBUILDINGSTOREY"]) {
+            let Some(entidade) = self.entidade(pavimento) else {
+                continue;
+            };
+            let mut area = 0.0;
+            for laje in self.filhos_do_tipo(pavimento, &TIPOS_DE_LAJE) {
+                if self
+                    .entidade(laje)
+                    .is_some_and(|l| l.atributo(8).como_enum() == Some("ROOF"))
+                {
+                    continue;
+                }
+                let contorno = self.contorno_da_laje(laje);
+                let area_da_laje = self
+                    .quantidade(laje, "IFCQUANTITYAREA", &["GrossArea", "NetArea"])
+                    .or_else(|| contorno.as_ref().map(Poligono::area));
+                match area_da_laje {
+                    Some(area_da_laje) => area += area_da_laje,
+                    None => self.avisar(format!(
+                        "Laje #{} de '{}' sem área: sem quantidades e com representação não suportada",
+                        laje, identificacao
+                    )),
+                }
+                if let Some(contorno) = contorno {
+                    let area_do_contorno = contorno.area();
+                    if maior_laje
+                        .as_ref()
+                        .is_none_or(|(a, _)| area_do_contorno > *a)
+                    {
+                        maior_laje = Some((area_do_contorno, contorno));
+                    }
+                }
+            }
+            pavimentos.push(Pavimento {
+                elevacao: entidade.atributo(9).como_f64().unwrap_or(0.0) * self.escala,
+                altura: self.quantidade(pavimento, "IFCQUANTITYLENGTH", &["GrossHeight", "Height"]),
+                area,
+            });
+        }
+
+        if pavimentos.is_empty() {
+            self.avisar(format!("Edificação '{}' sem pavimentos", identificacao));
+        }
+        let altura = altura_do_edificio(&mut pavimentos);
+        if altura.is_none() && !pavimentos.is_empty() {
+            self.avisar(format!(
+                "Altura de '{}' indeterminada: pavimento único sem altura informada",
+                identificacao
+            ));
+        }
+
+        Edificio {
+            nome: nome_do_edificio,
+            altura,
+            area_construida: pavimentos.iter().map(|p| p.area).sum(),
+            area_de_projecao: pavimentos.iter().map(|p| p.area).fold(0.0, f64::max),
+            projecao: maior_laje.map(|(_, contorno)| contorno),
+        }
+    }
+
+    /// Valor de uma quantidade (`IfcElementQuantity`) associada ao objeto,
+    /// pelo primeiro nome encontrado; comprimentos convertidos para metros.
+    fn quantidade(&self, objeto: u64, tipo: &str, nomes: &[&str]) -> Option<f64> {
+        let quantidades: Vec<&Entidade> = self
+            .propriedades
+            .get(&objeto)?
+            .iter()
+            .filter_map(|id| self.entidade(*id))
+            .filter(|d| d.tipo == "IFCELEMENTQUANTITY")
+            .flat_map(|d| d.atributo(5).refs())
+            .filter_map(|id| self.entidade(id))
+            .filter(|q| q.tipo == tipo)
+            .collect();
+        let escala = if tipo == "IFCQUANTITYLENGTH" {
+            self.escala
+        } else {
+            1.0
+        };
+        nomes.iter().find_map(|procurado| {
+            quantidades
+                .iter()
+                .find(|q| q.atributo(0).como_texto() == Some(procurado))
+                .and_then(|q| q.atributo(3).como_f64())
+                .map(|valor| valor * escala)
+        })
+    }
+
+    fn organizacao(&self) -> Option<String> {
+        let (_, projeto) = self.modelo.do_tipo("IFCPROJECT").into_iter().next()?;
+        let historico = self.resolver(projeto.atributo(1))?;
+        let usuario = self.resolver(historico.atributo(0))?;
+        let organizacao = self.resolver(usuario.atributo(1))?;
+        organizacao.atributo(1).como_texto().map(str::to_string)
+    }
+
+    fn cidade(&self, endereco: &Valor) -> Option<String> {
+        self.resolver(endereco)?
+            .atributo(6)
+            .como_texto()
+            .map(str::to_string)
+    }
+
+    /// Contorno em planta da laje, a partir de uma extrusão de perfil
+    /// retangular ou poligonal, em metros e no sistema global do modelo.
+    fn contorno_da_laje(&mut self, laje: u64) -> Option<Poligono> {
+        let entidade = self.entidade(laje)?;
+        let forma = self.resolver(entidade.atributo(6))?;
+        let mut nao_suportadas = Vec::new();
+
+        for representacao in forma.atributo(2).refs() {
+            let Some(representacao) = self.entidade(representacao) else {
+                continue;
+            };
+            for item in representacao.atributo(3).refs() {
+                let Some(item) = self.entidade(item) else {
+                    continue;
+                };
+                if item.tipo != "IFCEXTRUDEDAREASOLID" {
+                    nao_suportadas.push(item.tipo.clone());
+                    continue;
+                }
+                let Some(perfil) = self.resolver(item.atributo(0)) else {
+                    continue;
+                };
+                let Some(local) = self.contorno_do_perfil(perfil) else {
+                    nao_suportadas.push(perfil.tipo.clone());
+                    continue;
+                };
+                let transformacao = self
+                    .posicionamento(entidade.atributo(5))
+                    .compor(&self.eixos(item.atributo(1)));
+                return Some(Poligono(
+                    local
+                        .into_iter()
+                        .map(|p| {
+                            let [x, y] = transformacao.aplicar(p);
+                            [x * self.escala, y * self.escala]
+                        })
+                        .collect(),
+                ));
+            }
+        }
+
+        if !nao_suportadas.is_empty() {
+            nao_suportadas.dedup();
+            self.avisar(format!(
+                "Geometria da laje #{} não suportada: {}",
+                laje,
+                nao_suportadas.join(", ")
+            ));
+        }
+        None
+    }
+
+    fn contorno_do_perfil(&self, perfil: &Entidade) -> Option<Vec<Coordenada>> {
+        match perfil.tipo.as_str() {
+            "IFCRECTANGLEPROFILEDEF" => {
+                let (x, y) = (
+                    perfil.atributo(3).como_f64()?,
+                    perfil.atributo(4).como_f64()?,
+                );
+                let posicao = self.eixos(perfil.atributo(2));
+                Some(
+                    [
+                        [-x / 2.0, -y / 2.0],
+                        [x / 2.0, -y / 2.0],
+                        [x / 2.0, y / 2.0],
+                        [-x / 2.0, y / 2.0],
+                    ]
+                    .map(|p| posicao.aplicar(p))
+                    .to_vec(),
+                )
+            }
+            "IFCARBITRARYCLOSEDPROFILEDEF" => {
+                let curva = self.resolver(perfil.atributo(2))?;
+                if curva.tipo != "IFCPOLYLINE" {
+                    return None;
+                }
+                let mut pontos: Vec<Coordenada> = curva
+                    .atributo(0)
+                    .refs()
+                    .filter_map(|id| self.ponto(id))
+                    .collect();
+                if pontos.len() > 1 && pontos.first() == pontos.last() {
+                    pontos.pop();
+                }
+                Some(pontos)
+            }
+            _ => None,
+        }
+    }
+
+    fn ponto(&self, id: u64) -> Option<Coordenada> {
+        let coordenadas = self.entidade(id)?.atributo(0).como_lista();
+        Some([
+            coordenadas.first()?.como_f64()?,
+            coordenadas.get(1)?.como_f64()?,
+        ])
+    }
+
+    /// Posicionamento absoluto de um `IfcLocalPlacement`, seguindo a cadeia de
+    /// posicionamentos relativos. Rotações fora do plano são ignoradas.
+    fn posicionamento(&self, valor: &Valor) -> Transformacao {
+        let Some(posicionamento) = self.resolver(valor) else {
+            return Transformacao::IDENTIDADE;
+        };
+        if posicionamento.tipo != "IFCLOCALPLACEMENT" {
+            return Transformacao::IDENTIDADE;
+        }
+        self.posicionamento(posicionamento.atributo(0))
+            .compor(&self.eixos(posicionamento.atributo(1)))
+    }
+
+    /// Transformação de um `IfcAxis2Placement2D` ou `IfcAxis2Placement3D`.
+    fn eixos(&self, valor: &Valor) -> Transformacao {
+        let Some(eixos) = self.resolver(valor) else {
+            return Transformacao::IDENTIDADE;
+        };
+        let origem = eixos
+            .atributo(0)
+            .como_ref()
+            .and_then(|id| self.ponto(id))
+            .unwrap_or([0.0, 0.0]);
+        let direcao = match eixos.tipo.as_str() {
+            "IFCAXIS2PLACEMENT3D" => eixos.atributo(2),
+            _ => eixos.atributo(1),
+        };
+        let eixo_x = self
+            .resolver(direcao)
+            .and_then(|d| {
+                let razoes = d.atributo(0).como_lista();
+                Some([razoes.first()?.como_f64()?, razoes.get(1)?.como_f64()?])
+            })
+            .filter(|[x, y]| x.hypot(*y) > 0.0)
+            .map(|[x, y]| [x / x.hypot(y), y / x.hypot(y)])
+            .unwrap_or([1.0, 0.0]);
+        Transformacao { origem, eixo_x }
+    }
+}
+
+fn nome(entidade: &Entidade) -> Option<String> {
+    entidade.atributo(2).como_texto().map(str::to_string)
+}
+
+/// Altura do edifício do nível do térreo ao topo do último pavimento. Sem
+/// altura informada, cada pavimento vai até o seguinte, e o último repete a
+/// altura do anterior.
+fn altura_do_edificio(pavimentos: &mut [Pavimento]) -> Option<f64> {
+    pavimentos.sort_by(|a, b| a.elevacao.total_cmp(&b.elevacao));
+    let base = pavimentos
+        .iter()
+        .map(|p| p.elevacao)
+        .find(|e| *e >= 0.0)
+        .or_else(|| pavimentos.first().map(|p| p.elevacao))?;
+
+    let mut topo = None;
+    let mut altura_anterior = None;
+    for (i, pavimento) in pavimentos.iter().enumerate() {
+        let altura = pavimento
+            .altura
+            .or_else(|| {
+                pavimentos
+                    .get(i + 1)
+                    .map(|p| p.elevacao - pavimento.elevacao)
+            })
+            .or(altura_anterior);
+        if let Some(altura) = altura {
+            topo = Some(pavimento.elevacao + altura);
+        }
+        altura_anterior = altura;
+    }
+    topo.map(|topo| topo - base)
+}
+
+/// Converte um `IfcCompoundPlaneAngleMeasure` (graus, minutos, segundos e
+/// milionésimos de segundo) em graus decimais.
+fn angulo_composto(valor: &Valor) -> Option<f64> {
+    let partes: Vec<f64> = valor
+        .como_lista()
+        .iter()
+        .filter_map(Valor::como_f64)
+        .collect();
+    if partes.len() < 3 {
+        return None;
+    }
+    let negativo = partes.iter().any(|p| *p < 0.0);
+    let graus = partes[0].abs()
+        + partes[1].abs() / 60.0
+        + (partes[2].abs() + partes.get(3).map_or(0.0, |m| m.abs() / 1e6)) / 3600.0;
+    Some(if negativo { -graus } else { graus })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angulo_composto() {
+        let latitude = Valor::Lista(vec![
+            Valor::Inteiro(-23),
+            Valor::Inteiro(-33),
+            Valor::Inteiro(-1),
+            Valor::Inteiro(-500000),
+        ]);
+        let graus = angulo_composto(&latitude).unwrap();
+        assert!((graus + 23.550417).abs() < 1e-6, "{}", graus);
+        assert_eq!(angulo_composto(&Valor::Nulo), None);
+    }
+
+    #[test]
+    fn test_altura_do_edificio() {
+        let pavimento = |elevacao: f64, altura: Option<f64>| Pavimento {
+            elevacao,
+            altura,
+            area: 0.0,
+        };
+
+        let mut com_subsolo = vec![
+            pavimento(3.0, None),
+            pavimento(-3.0, None),
+            pavimento(0.0, None),
+            pavimento(6.0, Some(4.0)),
+        ];
+        assert_eq!(altura_do_edificio(&mut com_subsolo), Some(10.0));
+
+        let mut sem_altura_no_topo = vec![pavimento(0.0, None), pavimento(3.0, None)];
+        assert_eq!(altura_do_edificio(&mut sem_altura_no_topo), Some(6.0));
+
+        let mut unico = vec![pavimento(0.0, None)];
+        assert_eq!(altura_do_edificio(&mut unico), None);
+    }
+
+    #[test]
+    fn test_transformacao_composta() {
+        let rotacionada = Transformacao {
+            origem: [10.0, 0.0],
+            eixo_x: [0.0, 1.0],
+        };
+        let deslocada = Transformacao {
+            origem: [2.0, 0.0],
+            eixo_x: [1.0, 0.0],
+        };
+        let composta = rotacionada.compor(&deslocada);
+
+        assert_eq!(composta.aplicar([0.0, 0.0]), [10.0, 2.0]);
+        assert_eq!(composta.aplicar([1.0, 0.0]), [10.0, 3.0]);
+    }
+}
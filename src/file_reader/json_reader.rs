@@ -1,10 +1,14 @@
+use super::Entrada;
 use crate::models::empreendimento::Empreendimento;
 use serde_json::from_str;
 use std::error::Error;
-use std::fs;
+use std::io::Read;
 
-pub fn read_json(path: &str) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
-    let data = fs::read_to_string(path)?;
+pub fn read_json<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let mut data = String::new();
+    entrada.into().abrir()?.read_to_string(&mut data)?;
     let results: Vec<Empreendimento> = from_str(&data)?;
     Ok(results)
 }
@@ -34,14 +38,14 @@ mod tests {
                 "area-de-lazer": 150.0
             }
         ]"#;
-        
+
         let temp_file = create_temp_json(json_content);
         let result = read_json(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Alpha");
         assert_eq!(emp.cidade, "São Paulo");
@@ -65,14 +69,14 @@ mod tests {
                 "area_de_lazer": 100.0
             }
         ]"#;
-        
+
         let temp_file = create_temp_json(json_content);
         let result = read_json(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Beta");
         assert_eq!(emp.cidade, "Rio de Janeiro");
@@ -96,14 +100,14 @@ mod tests {
                 "area-de-lazer": 200.0
             }
         ]"#;
-        
+
         let temp_file = create_temp_json(json_content);
         let result = read_json(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Gamma");
         assert_eq!(emp.cidade, "Brasília");
@@ -126,14 +130,14 @@ mod tests {
                 "area-da-torre": 500.0
             }
         ]"#;
-        
+
         let temp_file = create_temp_json(json_content);
         let result = read_json(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Delta");
         assert_eq!(emp.cidade, "Salvador");
@@ -166,20 +170,113 @@ mod tests {
                 "area_de_lazer": 100.0
             }
         ]"#;
-        
+
         let temp_file = create_temp_json(json_content);
         let result = read_json(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 2);
-        
+
         let emp1 = &empreendimentos[0];
         assert_eq!(emp1.construtora, "Alpha");
         assert_eq!(emp1.cidade, "São Paulo");
-        
+
         let emp2 = &empreendimentos[1];
         assert_eq!(emp2.construtora, "Beta");
         assert_eq!(emp2.cidade, "Rio de Janeiro");
     }
+
+    #[test]
+    fn test_read_json_with_vagas() {
+        let json_content = r#"[
+            {
+                "construtora": "Alpha",
+                "cidade": "São Paulo",
+                "area-do-terreno": 1000.0,
+                "numero-de-torres": 2,
+                "altura-da-torre": 25.0,
+                "area-da-torre": 300.0,
+                "numero-de-unidades": 40,
+                "vagas-de-garagem": 50,
+                "vagas_acessiveis": 2,
+                "vagas-de-motos": 3
+            }
+        ]"#;
+
+        let temp_file = create_temp_json(json_content);
+        let empreendimentos = read_json(temp_file.path().to_str().unwrap()).unwrap();
+
+        let emp = &empreendimentos[0];
+        assert_eq!(emp.numero_de_unidades, Some(40));
+        assert_eq!(emp.vagas_de_garagem, Some(50));
+        assert_eq!(emp.vagas_acessiveis, Some(2));
+        assert_eq!(emp.vagas_de_motos, Some(3));
+        assert_eq!(emp.vagas_de_bicicletas, None);
+    }
+
+    #[test]
+    fn test_read_json_opcionais_com_numeros_entre_aspas() {
+        let json_content = r#"[
+            {
+                "construtora": "Alpha",
+                "cidade": "São Paulo",
+                "area-do-terreno": 1000.0,
+                "numero-de-torres": 2,
+                "altura-da-torre": 25.0,
+                "area-da-torre": 300.0,
+                "area-de-lazer": "150,5",
+                "vagas-de-garagem": "30",
+                "vagas-de-motos": ""
+            }
+        ]"#;
+
+        let temp_file = create_temp_json(json_content);
+        let erro = read_json(temp_file.path().to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(erro.contains("\"150,5\""), "{}", erro);
+
+        let temp_file = create_temp_json(&json_content.replace("150,5", "150.5"));
+        let emp = &read_json(temp_file.path().to_str().unwrap()).unwrap()[0];
+        assert_eq!(emp.area_de_lazer, Some(150.5));
+        assert_eq!(emp.vagas_de_garagem, Some(30));
+        assert_eq!(emp.vagas_de_motos, None, "Texto vazio é ausente");
+
+        let temp_file = create_temp_json(&json_content.replace("2,", "\"2\","));
+        assert!(
+            read_json(temp_file.path().to_str().unwrap()).is_err(),
+            "Campos obrigatórios não aceitam números entre aspas"
+        );
+    }
+
+    #[test]
+    fn test_read_json_with_torres_e_lote() {
+        let json_content = r#"[
+            {
+                "construtora": "Alpha",
+                "cidade": "São Paulo",
+                "area-do-terreno": 3000.0,
+                "numero-de-torres": 2,
+                "altura-da-torre": 25.0,
+                "area-da-torre": 225.0,
+                "torres": [
+                    { "nome": "A", "projecao": [[5, 5], [20, 5], [20, 20], [5, 20]] },
+                    { "nome": "B", "projecao": [[30, 5], [45, 5], [45, 20], [30, 20]], "altura": 40.0 }
+                ],
+                "lote": [[0, 0], [60, 0], [60, 50], [0, 50], [0, 0]]
+            }
+        ]"#;
+
+        let temp_file = create_temp_json(json_content);
+        let empreendimentos = read_json(temp_file.path().to_str().unwrap()).unwrap();
+
+        let emp = &empreendimentos[0];
+        let torres = emp.torres.as_ref().unwrap();
+        assert_eq!(torres.len(), 2);
+        assert_eq!(emp.altura_de(&torres[0]), 25.0);
+        assert_eq!(emp.altura_de(&torres[1]), 40.0);
+        assert_eq!(torres[0].projecao.distancia_ate(&torres[1].projecao), 10.0);
+        assert_eq!(emp.lote.as_ref().unwrap().area(), 3000.0);
+    }
 }
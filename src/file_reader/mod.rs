@@ -1,28 +1,125 @@
+pub mod arrow_reader;
+mod auxiliar;
+pub mod compressao;
 pub mod csv_reader;
+pub mod deteccao;
+pub mod dxf_reader;
+mod entrada;
+pub mod ifc_reader;
 pub mod json_reader;
+pub mod osm_reader;
 pub mod parquet_reader;
+pub mod planilha_reader;
+pub mod restricoes_altura_reader;
+mod step;
 pub mod xml_reader;
+pub mod zoneamento_reader;
 
-pub use csv_reader::read_csv;
+pub use arrow_reader::{read_arrow, read_arrow_em_lotes};
+pub use compressao::Compressao;
+pub use csv_reader::{OpcoesCsv, read_csv, read_csv_com_opcoes};
+pub use deteccao::{detectar_pelo_conteudo, detectar_tipo, detectar_tipo_do_conteudo};
+pub use dxf_reader::{OpcoesDxf, read_dxf, read_dxf_com_opcoes};
+pub use entrada::Entrada;
+pub use ifc_reader::{LeituraIfc, read_ifc, read_ifc_com_avisos};
 pub use json_reader::read_json;
-pub use parquet_reader::read_parquet;
+pub use osm_reader::{LeituraOsm, read_osm, read_osm_com_avisos};
+pub use parquet_reader::{read_parquet, read_parquet_em_lotes};
+pub use planilha_reader::{
+    Aba, OpcoesPlanilha, read_ods, read_ods_com_opcoes, read_xlsx, read_xlsx_com_opcoes,
+};
+pub use restricoes_altura_reader::read_restricoes_altura;
 pub use xml_reader::read_xml;
+pub use zoneamento_reader::read_zoneamento;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileType {
     Csv,
     Json,
     Parquet,
     Xml,
+    Dxf,
+    Ifc,
+    Osm,
+    Arrow,
+    Xlsx,
+    Ods,
 }
 
-pub fn read_file(
+impl FileType {
+    /// Tipo do arquivo pela extensão do caminho, sem diferenciar maiúsculas;
+    /// a extensão de compressão é ignorada (`dados.csv.gz` é CSV).
+    pub fn pela_extensao(path: &str) -> Option<FileType> {
+        let extensao = std::path::Path::new(Compressao::sem_extensao(path))
+            .extension()
+            .and_then(|ext| ext.to_str())?
+            .to_lowercase();
+        match extensao.as_str() {
+            "pbf" => Some(FileType::Osm),
+            "arrows" | "feather" | "ipc" => Some(FileType::Arrow),
+            outra => outra.parse().ok(),
+        }
+    }
+}
+
+impl std::str::FromStr for FileType {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        match texto.to_lowercase().as_str() {
+            "csv" => Ok(FileType::Csv),
+            "json" => Ok(FileType::Json),
+            "parquet" => Ok(FileType::Parquet),
+            "xml" => Ok(FileType::Xml),
+            "dxf" => Ok(FileType::Dxf),
+            "ifc" => Ok(FileType::Ifc),
+            "osm" => Ok(FileType::Osm),
+            "arrow" => Ok(FileType::Arrow),
+            "xlsx" => Ok(FileType::Xlsx),
+            "ods" => Ok(FileType::Ods),
+            _ => Err(format!(
+                "Tipo de arquivo '{}' inválido. Use csv, json, parquet, xml, dxf, ifc, osm, arrow, xlsx ou ods.",
+                texto
+            )),
+        }
+    }
+}
+
+/// Nome do tipo como aceito em `--file-type`.
+impl std::fmt::Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FileType::Csv => "csv",
+            FileType::Json => "json",
+            FileType::Parquet => "parquet",
+            FileType::Xml => "xml",
+            FileType::Dxf => "dxf",
+            FileType::Ifc => "ifc",
+            FileType::Osm => "osm",
+            FileType::Arrow => "arrow",
+            FileType::Xlsx => "xlsx",
+            FileType::Ods => "ods",
+        })
+    }
+}
+
+/// Lê os empreendimentos de `entrada`: o caminho de um arquivo ou qualquer
+/// `Read`, como a entrada padrão ou um buffer em memória ([`Entrada::leitor`]).
+pub fn read_file<'a>(
     file_type: FileType,
-    path: &str,
+    entrada: impl Into<Entrada<'a>>,
 ) -> Result<Vec<crate::models::empreendimento::Empreendimento>, Box<dyn std::error::Error>> {
+    let entrada = entrada.into();
     match file_type {
-        FileType::Csv => read_csv(path),
-        FileType::Json => read_json(path),
-        FileType::Parquet => read_parquet(path),
-        FileType::Xml => read_xml(path),
+        FileType::Csv => read_csv(entrada),
+        FileType::Json => read_json(entrada),
+        FileType::Parquet => read_parquet(entrada),
+        FileType::Xml => read_xml(entrada),
+        FileType::Dxf => read_dxf(entrada),
+        FileType::Ifc => read_ifc(entrada),
+        FileType::Osm => read_osm(entrada),
+        FileType::Arrow => read_arrow(entrada),
+        FileType::Xlsx => read_xlsx(entrada),
+        FileType::Ods => read_ods(entrada),
     }
 }
@@ -0,0 +1,753 @@
+use super::Entrada;
+use super::auxiliar::{ler_arquivo_auxiliar, numero};
+use super::deteccao;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::geometria::{Coordenada, Poligono};
+use crate::models::lote::Lote;
+use crate::models::torre::Torre;
+use osmpbf::{Element, ElementReader};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use rstar::{AABB, RTree, RTreeObject};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, Read};
+
+/// Empreendimentos lidos de um extrato OpenStreetMap, com os avisos sobre
+/// elementos que ficaram de fora.
+#[derive(Debug, Default)]
+pub struct LeituraOsm {
+    pub empreendimentos: Vec<Empreendimento>,
+    pub avisos: Vec<String>,
+}
+
+/// Pé-direito usado para converter `building:levels` em altura e vice-versa, em metros.
+pub const ALTURA_POR_PAVIMENTO: f64 = 3.0;
+
+pub fn read_osm<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    Ok(read_osm_com_avisos(entrada)?.empreendimentos)
+}
+
+/// Lê um extrato OpenStreetMap (`.osm` ou `.osm.pbf`): cada área `landuse`
+/// que contém edificações gera um empreendimento, e cada edificação
+/// (`building`) com `height` ou `building:levels`, uma torre.
+///
+/// As edificações são atribuídas à menor área `landuse` que contém o seu
+/// centroide. Dados que o mapa não traz, como a cidade, podem vir do arquivo
+/// auxiliar `<extrato>.json`.
+///
+/// O extrato é percorrido duas vezes; lido de um `Read` ou compactado, ele é
+/// mantido em memória entre as passagens.
+pub fn read_osm_com_avisos<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<LeituraOsm, Box<dyn Error>> {
+    let entrada = entrada.into();
+    let auxiliar = ler_arquivo_auxiliar(entrada.caminho())?;
+    let extrato = match entrada.arquivo()? {
+        Some(path) => Extrato::Arquivo(path),
+        None => Extrato::Bytes(entrada.bytes()?),
+    };
+    let mut avisos = Vec::new();
+
+    let mut caminhos = Vec::new();
+    let mut relacoes_ignoradas = 0;
+    percorrer(&extrato, Passagem::Caminhos, |elemento| match elemento {
+        ElementoOsm::Caminho { id, nos, tags }
+            if tags.contains_key("landuse") || eh_edificacao(&tags) =>
+        {
+            caminhos.push(Caminho { id, nos, tags });
+        }
+        ElementoOsm::Relacao { tags } if tags.contains_key("landuse") || eh_edificacao(&tags) => {
+            relacoes_ignoradas += 1;
+        }
+        _ => {}
+    })?;
+
+    let necessarios: HashSet<i64> = caminhos.iter().flat_map(|c| c.nos.clone()).collect();
+    let mut nos = HashMap::new();
+    percorrer(&extrato, Passagem::Nos, |elemento| {
+        if let ElementoOsm::No { id, coordenada } = elemento
+            && necessarios.contains(&id)
+        {
+            nos.insert(id, coordenada);
+        }
+    })?;
+
+    let mut lotes = Vec::new();
+    let mut edificacoes = Vec::new();
+    let (mut abertos, mut incompletos, mut sem_altura) = (0, 0, 0);
+    for caminho in caminhos {
+        if caminho.nos.len() < 4 || caminho.nos.first() != caminho.nos.last() {
+            abertos += 1;
+            continue;
+        }
+        let Some(contorno) = caminho
+            .nos
+            .iter()
+            .map(|id| nos.get(id).copied())
+            .collect::<Option<Vec<Coordenada>>>()
+        else {
+            incompletos += 1;
+            continue;
+        };
+        let contorno = Poligono(contorno);
+
+        if eh_edificacao(&caminho.tags) {
+            match Edificacao::de_tags(caminho.id, contorno, &caminho.tags) {
+                Some(edificacao) => edificacoes.push(edificacao),
+                None => sem_altura += 1,
+            }
+        } else {
+            lotes.push(Parcela::new(caminho.id, contorno, caminho.tags));
+        }
+    }
+
+    let indice = RTree::bulk_load(lotes);
+    let mut por_lote: HashMap<i64, Vec<Edificacao>> = HashMap::new();
+    let mut fora_de_lote = 0;
+    for edificacao in edificacoes {
+        let centroide = edificacao.projecao.centroide();
+        let lote = indice
+            .locate_in_envelope_intersecting(&AABB::from_point(centroide))
+            .filter(|p| p.lote.contorno.contem(centroide))
+            .min_by(|a, b| a.area.total_cmp(&b.area));
+        match lote {
+            Some(lote) => por_lote.entry(lote.id).or_default().push(edificacao),
+            None => fora_de_lote += 1,
+        }
+    }
+
+    for (quantidade, motivo) in [
+        (
+            sem_altura,
+            "edificações sem 'height' nem 'building:levels' ignoradas",
+        ),
+        (
+            fora_de_lote,
+            "edificações fora de áreas 'landuse' ignoradas",
+        ),
+        (
+            abertos,
+            "contornos de edificação ou 'landuse' não fechados ignorados",
+        ),
+        (
+            incompletos,
+            "contornos com nós ausentes do extrato ignorados",
+        ),
+        (
+            relacoes_ignoradas,
+            "relações (multipolígonos) de edificação ou 'landuse' não suportadas",
+        ),
+    ] {
+        if quantidade > 0 {
+            avisos.push(format!("{} {}", quantidade, motivo));
+        }
+    }
+
+    let mut lotes: Vec<&Parcela> = indice.iter().collect();
+    lotes.sort_by_key(|p| p.id);
+    let mut empreendimentos = Vec::new();
+    for parcela in lotes {
+        let Some(edificacoes) = por_lote.remove(&parcela.id) else {
+            continue;
+        };
+        let mut dados = auxiliar.clone();
+        completar_com_mapa(&mut dados, parcela, &edificacoes)?;
+        empreendimentos.push(
+            serde_json::from_value(Value::Object(dados))
+                .map_err(|e| format!("Área 'landuse' {} do extrato: {}", parcela.id, e))?,
+        );
+    }
+
+    Ok(LeituraOsm {
+        empreendimentos,
+        avisos,
+    })
+}
+
+struct Caminho {
+    id: i64,
+    nos: Vec<i64>,
+    tags: HashMap<String, String>,
+}
+
+/// Área `landuse` com a área geodésica calculada uma única vez, indexada
+/// pelo retângulo que a envolve.
+struct Parcela {
+    id: i64,
+    lote: Lote,
+    area: f64,
+    envelope: AABB<Coordenada>,
+    tags: HashMap<String, String>,
+}
+
+impl Parcela {
+    fn new(id: i64, contorno: Poligono, tags: HashMap<String, String>) -> Self {
+        let (min, max) = contorno.limites();
+        let lote = Lote::geografico(contorno);
+        Self {
+            id,
+            area: lote.area(),
+            envelope: AABB::from_corners(min, max),
+            lote,
+            tags,
+        }
+    }
+}
+
+impl RTreeObject for Parcela {
+    type Envelope = AABB<Coordenada>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+struct Edificacao {
+    nome: Option<String>,
+    cidade: Option<String>,
+    projecao: Poligono,
+    altura: f64,
+    pavimentos: f64,
+}
+
+impl Edificacao {
+    fn de_tags(id: i64, projecao: Poligono, tags: &HashMap<String, String>) -> Option<Self> {
+        let pavimentos = tags
+            .get("building:levels")
+            .and_then(|v| v.trim().replace(',', ".").parse::<f64>().ok())
+            .filter(|p| *p > 0.0);
+        let altura = tags
+            .get("height")
+            .and_then(|v| altura_em_metros(v))
+            .or_else(|| pavimentos.map(|p| p * ALTURA_POR_PAVIMENTO))?;
+        Some(Self {
+            nome: tags
+                .get("name")
+                .cloned()
+                .or_else(|| Some(format!("Edificação OSM {}", id))),
+            cidade: tags.get("addr:city").cloned(),
+            projecao,
+            altura,
+            pavimentos: pavimentos.unwrap_or((altura / ALTURA_POR_PAVIMENTO).round().max(1.0)),
+        })
+    }
+}
+
+/// Preenche os campos que o mapa permite calcular, sem sobrescrever os informados.
+fn completar_com_mapa(
+    dados: &mut Map<String, Value>,
+    parcela: &Parcela,
+    edificacoes: &[Edificacao],
+) -> Result<(), serde_json::Error> {
+    let areas: Vec<f64> = edificacoes
+        .iter()
+        .map(|e| parcela.lote.projetar(&e.projecao).area())
+        .collect();
+    let [longitude, latitude] = parcela.lote.centroide();
+    let cidade = parcela
+        .tags
+        .get("addr:city")
+        .or_else(|| edificacoes.iter().find_map(|e| e.cidade.as_ref()));
+    let construtora = parcela
+        .tags
+        .get("name")
+        .cloned()
+        .unwrap_or_else(|| format!("Lote OSM {}", parcela.id));
+    let mut calculado = |nome: &str, valor: Value| {
+        dados.entry(nome).or_insert(valor);
+    };
+
+    calculado("construtora", Value::String(construtora));
+    calculado("cidade", Value::String(cidade.cloned().unwrap_or_default()));
+    if let Some(uso) = uso_do_landuse(&parcela.tags["landuse"]) {
+        calculado("uso", Value::String(uso.to_string()));
+    }
+    calculado("area_do_terreno", numero(parcela.area));
+    calculado("numero_de_torres", Value::from(edificacoes.len()));
+    calculado(
+        "altura_da_torre",
+        numero(edificacoes.iter().map(|e| e.altura).fold(0.0, f64::max)),
+    );
+    calculado(
+        "area_da_torre",
+        numero(areas.iter().sum::<f64>() / areas.len() as f64),
+    );
+    calculado("area_de_projecao", numero(areas.iter().sum()));
+    calculado(
+        "area_construida",
+        numero(
+            areas
+                .iter()
+                .zip(edificacoes)
+                .map(|(area, e)| area * e.pavimentos)
+                .sum(),
+        ),
+    );
+    calculado("latitude", numero(latitude));
+    calculado("longitude", numero(longitude));
+
+    let torres: Vec<Torre> = edificacoes
+        .iter()
+        .map(|e| Torre {
+            nome: e.nome.clone(),
+            projecao: e.projecao.clone(),
+            altura: Some(e.altura),
+        })
+        .collect();
+    dados.insert("lote".to_string(), serde_json::to_value(&parcela.lote)?);
+    dados.insert("torres".to_string(), serde_json::to_value(torres)?);
+    Ok(())
+}
+
+fn eh_edificacao(tags: &HashMap<String, String>) -> bool {
+    tags.get("building").is_some_and(|v| v != "no")
+}
+
+fn uso_do_landuse(landuse: &str) -> Option<&'static str> {
+    match landuse {
+        "residential" => Some("residencial"),
+        "commercial" | "retail" => Some("comercial"),
+        _ => None,
+    }
+}
+
+/// Converte o valor de `height` para metros; aceita `12`, `12 m`, `12,5` e pés (`40 ft`, `40'`).
+fn altura_em_metros(valor: &str) -> Option<f64> {
+    let valor = valor.trim().replace(',', ".");
+    let (numero, escala) = if let Some(pes) = valor
+        .strip_suffix("ft")
+        .or_else(|| valor.strip_suffix('\''))
+    {
+        (pes, 0.3048)
+    } else {
+        (valor.strip_suffix('m').unwrap_or(&valor), 1.0)
+    };
+    numero
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|h| *h > 0.0)
+        .map(|h| h * escala)
+}
+
+/// Elementos de interesse de cada leitura do extrato: primeiro os caminhos e
+/// relações, depois apenas as coordenadas dos nós usados por eles.
+#[derive(Clone, Copy, PartialEq)]
+enum Passagem {
+    Caminhos,
+    Nos,
+}
+
+enum ElementoOsm {
+    No {
+        id: i64,
+        coordenada: Coordenada,
+    },
+    Caminho {
+        id: i64,
+        nos: Vec<i64>,
+        tags: HashMap<String, String>,
+    },
+    Relacao {
+        tags: HashMap<String, String>,
+    },
+}
+
+/// Origem do extrato, relida a cada passagem.
+enum Extrato<'a> {
+    Arquivo(&'a str),
+    Bytes(Vec<u8>),
+}
+
+fn percorrer(
+    extrato: &Extrato,
+    passagem: Passagem,
+    f: impl FnMut(ElementoOsm),
+) -> Result<(), Box<dyn Error>> {
+    match extrato {
+        Extrato::Arquivo(path) => {
+            let mut inicio = Vec::new();
+            File::open(path)?.take(16).read_to_end(&mut inicio)?;
+            if path.to_lowercase().ends_with(".pbf") || deteccao::eh_osm_pbf(&inicio) {
+                percorrer_pbf(ElementReader::from_path(path)?, passagem, f)
+            } else {
+                percorrer_xml(Reader::from_file(path)?, passagem, f)
+            }
+        }
+        Extrato::Bytes(bytes) if deteccao::eh_osm_pbf(bytes) => {
+            percorrer_pbf(ElementReader::new(&bytes[..]), passagem, f)
+        }
+        Extrato::Bytes(bytes) => percorrer_xml(Reader::from_reader(&bytes[..]), passagem, f),
+    }
+}
+
+fn percorrer_pbf<R: Read + Send>(
+    leitor: ElementReader<R>,
+    passagem: Passagem,
+    mut f: impl FnMut(ElementoOsm),
+) -> Result<(), Box<dyn Error>> {
+    let tags = |iter: &mut dyn Iterator<Item = (&str, &str)>| -> HashMap<String, String> {
+        iter.map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    };
+    leitor.for_each(|elemento| match (passagem, elemento) {
+        (Passagem::Nos, Element::Node(no)) => f(ElementoOsm::No {
+            id: no.id(),
+            coordenada: [no.lon(), no.lat()],
+        }),
+        (Passagem::Nos, Element::DenseNode(no)) => f(ElementoOsm::No {
+            id: no.id(),
+            coordenada: [no.lon(), no.lat()],
+        }),
+        (Passagem::Caminhos, Element::Way(caminho)) => f(ElementoOsm::Caminho {
+            id: caminho.id(),
+            nos: caminho.refs().collect(),
+            tags: tags(&mut caminho.tags()),
+        }),
+        (Passagem::Caminhos, Element::Relation(relacao)) => f(ElementoOsm::Relacao {
+            tags: tags(&mut relacao.tags()),
+        }),
+        _ => {}
+    })?;
+    Ok(())
+}
+
+fn percorrer_xml<R: BufRead>(
+    mut reader: Reader<R>,
+    passagem: Passagem,
+    mut f: impl FnMut(ElementoOsm),
+) -> Result<(), Box<dyn Error>> {
+    let mut buffer = Vec::new();
+    let mut atual: Option<ElementoOsm> = None;
+
+    loop {
+        let (evento, vazio) = match reader.read_event_into(&mut buffer)? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(e) => {
+                if matches!(e.name().as_ref(), b"node" | b"way" | b"relation")
+                    && let Some(elemento) = atual.take()
+                {
+                    f(elemento);
+                }
+                buffer.clear();
+                continue;
+            }
+            Event::Eof => break,
+            _ => {
+                buffer.clear();
+                continue;
+            }
+        };
+
+        let atributos = atributos(&evento)?;
+        let numero = |nome: &str| -> Result<f64, Box<dyn Error>> {
+            atributos
+                .get(nome)
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| format!("Atributo '{}' inválido no extrato OSM", nome).into())
+        };
+        let elemento = match (passagem, evento.name().as_ref()) {
+            (Passagem::Nos, b"node") => Some(ElementoOsm::No {
+                id: numero("id")? as i64,
+                coordenada: [numero("lon")?, numero("lat")?],
+            }),
+            (Passagem::Caminhos, b"way") => Some(ElementoOsm::Caminho {
+                id: numero("id")? as i64,
+                nos: Vec::new(),
+                tags: HashMap::new(),
+            }),
+            (Passagem::Caminhos, b"relation") => Some(ElementoOsm::Relacao {
+                tags: HashMap::new(),
+            }),
+            (Passagem::Caminhos, b"nd") => {
+                if let Some(ElementoOsm::Caminho { nos, .. }) = &mut atual {
+                    nos.push(numero("ref")? as i64);
+                }
+                None
+            }
+            (Passagem::Caminhos, b"tag") => {
+                if let Some(ElementoOsm::Caminho { tags, .. } | ElementoOsm::Relacao { tags }) =
+                    &mut atual
+                    && let (Some(k), Some(v)) = (atributos.get("k"), atributos.get("v"))
+                {
+                    tags.insert(k.clone(), v.clone());
+                }
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(elemento) = elemento {
+            if vazio {
+                f(elemento);
+            } else {
+                atual = Some(elemento);
+            }
+        }
+        buffer.clear();
+    }
+    Ok(())
+}
+
+fn atributos(elemento: &BytesStart) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    elemento
+        .attributes()
+        .map(|atributo| {
+            let atributo = atributo?;
+            Ok((
+                String::from_utf8_lossy(atributo.key.as_ref()).into_owned(),
+                atributo
+                    .decode_and_unescape_value(elemento.decoder())?
+                    .into_owned(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn extrato(conteudo: &str) -> NamedTempFile {
+        let mut arquivo = tempfile::Builder::new().suffix(".osm").tempfile().unwrap();
+        write!(
+            arquivo,
+            r#"<?xml version="1.0" encoding="UTF-8"?><osm version="0.6">{}</osm>"#,
+            conteudo
+        )
+        .unwrap();
+        arquivo
+    }
+
+    /// Quadrado de lado `lado` graus a partir de `origem`, com nós numerados a partir de `primeiro`.
+    fn quadrado(primeiro: i64, origem: [f64; 2], lado: f64) -> (String, Vec<i64>) {
+        let cantos = [
+            origem,
+            [origem[0] + lado, origem[1]],
+            [origem[0] + lado, origem[1] + lado],
+            [origem[0], origem[1] + lado],
+        ];
+        let nos = cantos
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                format!(
+                    r#"<node id="{}" lat="{}" lon="{}"/>"#,
+                    primeiro + i as i64,
+                    c[1],
+                    c[0]
+                )
+            })
+            .collect();
+        let mut refs: Vec<i64> = (primeiro..primeiro + 4).collect();
+        refs.push(primeiro);
+        (nos, refs)
+    }
+
+    fn caminho(id: i64, refs: &[i64], tags: &[(&str, &str)]) -> String {
+        let nds: String = refs
+            .iter()
+            .map(|r| format!(r#"<nd ref="{}"/>"#, r))
+            .collect();
+        let tags: String = tags
+            .iter()
+            .map(|(k, v)| format!(r#"<tag k="{}" v="{}"/>"#, k, v))
+            .collect();
+        format!(r#"<way id="{}">{}{}</way>"#, id, nds, tags)
+    }
+
+    #[test]
+    fn test_altura_em_metros() {
+        assert_eq!(altura_em_metros("12"), Some(12.0));
+        assert_eq!(altura_em_metros("12.5 m"), Some(12.5));
+        assert_eq!(altura_em_metros("7,5m"), Some(7.5));
+        assert!((altura_em_metros("100 ft").unwrap() - 30.48).abs() < 1e-9);
+        assert!((altura_em_metros("10'").unwrap() - 3.048).abs() < 1e-9);
+        assert_eq!(altura_em_metros("alto"), None);
+        assert_eq!(altura_em_metros("0"), None);
+    }
+
+    #[test]
+    fn test_edificacoes_agrupadas_pela_menor_area_landuse() {
+        let (nos_bairro, bairro) = quadrado(1, [-46.640, -23.550], 0.01);
+        let (nos_quadra, quadra) = quadrado(10, [-46.639, -23.549], 0.001);
+        let (nos_a, a) = quadrado(20, [-46.6389, -23.5489], 0.0002);
+        let (nos_b, b) = quadrado(30, [-46.6385, -23.5485], 0.0002);
+        let (nos_c, c) = quadrado(40, [-46.635, -23.545], 0.0002);
+        let conteudo = [
+            nos_bairro,
+            nos_quadra,
+            nos_a,
+            nos_b,
+            nos_c,
+            caminho(100, &bairro, &[("landuse", "residential")]),
+            caminho(
+                101,
+                &quadra,
+                &[
+                    ("landuse", "commercial"),
+                    ("name", "Quadra Central"),
+                    ("addr:city", "São Paulo"),
+                ],
+            ),
+            caminho(
+                200,
+                &a,
+                &[
+                    ("building", "yes"),
+                    ("height", "30 m"),
+                    ("name", "Edifício A"),
+                ],
+            ),
+            caminho(
+                201,
+                &b,
+                &[("building", "apartments"), ("building:levels", "4")],
+            ),
+            caminho(202, &c, &[("building", "house"), ("building:levels", "2")]),
+        ]
+        .concat();
+        let arquivo = extrato(&conteudo);
+
+        let leitura = read_osm_com_avisos(arquivo.path().to_str().unwrap()).unwrap();
+        assert!(leitura.avisos.is_empty(), "{:?}", leitura.avisos);
+        assert_eq!(leitura.empreendimentos.len(), 2);
+
+        let bairro = &leitura.empreendimentos[0];
+        assert_eq!(bairro.construtora, "Lote OSM 100");
+        assert_eq!(bairro.cidade, "");
+        assert_eq!(bairro.numero_de_torres, 1);
+        assert_eq!(bairro.altura_da_torre, 6.0);
+
+        let quadra = &leitura.empreendimentos[1];
+        assert_eq!(quadra.construtora, "Quadra Central");
+        assert_eq!(quadra.cidade, "São Paulo");
+        assert_eq!(quadra.uso.como_str(), "comercial");
+        assert_eq!(quadra.numero_de_torres, 2);
+        assert_eq!(quadra.altura_da_torre, 30.0);
+        let torres = quadra.torres.as_ref().unwrap();
+        assert_eq!(torres[0].nome.as_deref(), Some("Edifício A"));
+        assert_eq!(torres[1].nome.as_deref(), Some("Edificação OSM 201"));
+        assert_eq!(torres[1].altura, Some(12.0));
+
+        // Quadra de 0,001° (≈ 102 m x 111 m) e edificações de 0,0002° (≈ 20 m x 22 m).
+        assert!((quadra.area_do_terreno - 11_350.0).abs() < 100.0);
+        let projecao = quadra.area_de_projecao.unwrap();
+        assert!((projecao - 2.0 * 454.0).abs() < 10.0, "{}", projecao);
+        let construida = quadra.area_construida.unwrap();
+        assert!((construida - projecao / 2.0 * (10.0 + 4.0)).abs() < 1.0);
+        assert!((quadra.latitude.unwrap() + 23.5485).abs() < 1e-9);
+        assert!((quadra.longitude.unwrap() + 46.6385).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_area_landuse_em_sentido_horario() {
+        let (nos_bairro, mut bairro) = quadrado(1, [-46.640, -23.550], 0.01);
+        let (nos_quadra, mut quadra) = quadrado(10, [-46.639, -23.549], 0.001);
+        let (nos_a, a) = quadrado(20, [-46.6389, -23.5489], 0.0002);
+        let (nos_b, b) = quadrado(30, [-46.635, -23.545], 0.0002);
+        bairro.reverse();
+        quadra.reverse();
+        let conteudo = [
+            nos_bairro,
+            nos_quadra,
+            nos_a,
+            nos_b,
+            caminho(100, &bairro, &[("landuse", "residential")]),
+            caminho(101, &quadra, &[("landuse", "commercial")]),
+            caminho(200, &a, &[("building", "yes"), ("building:levels", "2")]),
+            caminho(201, &b, &[("building", "yes"), ("building:levels", "2")]),
+        ]
+        .concat();
+        let arquivo = extrato(&conteudo);
+
+        let leitura = read_osm_com_avisos(arquivo.path().to_str().unwrap()).unwrap();
+        assert_eq!(leitura.empreendimentos.len(), 2);
+
+        // Bairro de 0,01° (≈ 1,02 km x 1,11 km), única área que contém a edificação 201.
+        let bairro = &leitura.empreendimentos[0];
+        assert_eq!(bairro.construtora, "Lote OSM 100");
+        assert!(
+            (bairro.area_do_terreno - 1_135_000.0).abs() < 10_000.0,
+            "{}",
+            bairro.area_do_terreno
+        );
+
+        // A quadra continua sendo a menor área que contém a edificação 200.
+        let quadra = &leitura.empreendimentos[1];
+        assert_eq!(quadra.construtora, "Lote OSM 101");
+        assert!(
+            (quadra.area_do_terreno - 11_350.0).abs() < 100.0,
+            "{}",
+            quadra.area_do_terreno
+        );
+    }
+
+    #[test]
+    fn test_avisos_de_edificacoes_ignoradas() {
+        let (nos_lote, lote) = quadrado(1, [10.0, 50.0], 0.001);
+        let (nos_a, a) = quadrado(10, [10.0002, 50.0002], 0.0001);
+        let (nos_b, b) = quadrado(20, [10.1, 50.1], 0.0001);
+        let conteudo = [
+            nos_lote,
+            nos_a,
+            nos_b,
+            caminho(1, &lote, &[("landuse", "residential")]),
+            caminho(2, &a, &[("building", "yes")]),
+            caminho(3, &b, &[("building", "yes"), ("height", "9")]),
+            caminho(4, &[1, 2, 3], &[("building", "yes"), ("height", "9")]),
+            caminho(5, &[1, 2, 99, 1], &[("building", "yes"), ("height", "9")]),
+            r#"<relation id="7"><member type="way" ref="1" role="outer"/><tag k="type" v="multipolygon"/><tag k="building" v="yes"/></relation>"#.to_string(),
+        ]
+        .concat();
+        let arquivo = extrato(&conteudo);
+
+        let leitura = read_osm_com_avisos(arquivo.path().to_str().unwrap()).unwrap();
+        let do_leitor = read_osm_com_avisos(Entrada::leitor(arquivo.reopen().unwrap())).unwrap();
+        assert_eq!(do_leitor.avisos, leitura.avisos);
+        assert!(leitura.empreendimentos.is_empty());
+        assert_eq!(
+            leitura.avisos,
+            vec![
+                "1 edificações sem 'height' nem 'building:levels' ignoradas",
+                "1 edificações fora de áreas 'landuse' ignoradas",
+                "1 contornos de edificação ou 'landuse' não fechados ignorados",
+                "1 contornos com nós ausentes do extrato ignorados",
+                "1 relações (multipolígonos) de edificação ou 'landuse' não suportadas",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arquivo_auxiliar_completa_os_dados() {
+        let (nos_lote, lote) = quadrado(1, [10.0, 50.0], 0.001);
+        let (nos_a, a) = quadrado(10, [10.0002, 50.0002], 0.0001);
+        let conteudo = [
+            nos_lote,
+            nos_a,
+            caminho(1, &lote, &[("landuse", "residential")]),
+            caminho(2, &a, &[("building", "yes"), ("building:levels", "3")]),
+        ]
+        .concat();
+        let arquivo = extrato(&conteudo);
+        let path = arquivo.path().to_str().unwrap();
+        std::fs::write(
+            format!("{}.json", path),
+            r#"{"cidade": "Curitiba", "construtora": "Auditoria"}"#,
+        )
+        .unwrap();
+
+        let empreendimentos = read_osm(path).unwrap();
+        std::fs::remove_file(format!("{}.json", path)).unwrap();
+        assert_eq!(empreendimentos[0].cidade, "Curitiba");
+        assert_eq!(empreendimentos[0].construtora, "Auditoria");
+        assert_eq!(empreendimentos[0].altura_da_torre, 9.0);
+    }
+}
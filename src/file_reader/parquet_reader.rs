@@ -1,132 +1,216 @@
+use super::Entrada;
+use crate::models::arrow_converter;
 use crate::models::empreendimento::Empreendimento;
-use crate::models::field_converter::normalize_field_name;
-use parquet::file::reader::{FileReader, SerializedFileReader};
-use parquet::record::RowAccessor;
+use bytes::Bytes;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
 use std::error::Error;
 use std::fs::File;
-use std::collections::HashMap;
 
-pub fn read_parquet(path: &str) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let reader = SerializedFileReader::new(file)?;
-    let mut results = Vec::new();
-
-    let metadata = reader.metadata();
-    let schema = metadata.file_metadata().schema();
-    let column_mapping = create_column_mapping(schema);
-
-    for row in reader.get_row_iter(None)? {
-        let empreendimento = deserialize_row(&row, &column_mapping)?;
-        results.push(empreendimento);
+/// Lê um arquivo Parquet, convertendo tipos compatíveis: inteiros de qualquer
+/// largura, `FLOAT`, `DECIMAL` e números em texto. Colunas opcionais nulas
+/// viram `None`; valores de tipo incompatível são erros com linha e coluna.
+///
+/// As linhas são convertidas por [`arrow_converter::de_record_batch`], com os
+/// mesmos tipos e limites da validação colunar.
+///
+/// Lido de um `Read` ou compactado, o arquivo é carregado na memória, já que
+/// o rodapé do Parquet fica no fim.
+pub fn read_parquet<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let mut empreendimentos = Vec::new();
+    for batch in leitor_de_lotes(entrada.into())? {
+        empreendimentos.extend(arrow_converter::de_record_batch(
+            &batch?,
+            empreendimentos.len() + 1,
+        )?);
     }
-
-    Ok(results)
+    Ok(empreendimentos)
 }
 
-fn create_column_mapping(schema: &parquet::schema::types::Type) -> HashMap<String, usize> {
-    let mut mapping = HashMap::new();
-    
-    if let parquet::schema::types::Type::GroupType { fields, .. } = schema {
-        for (index, field) in fields.iter().enumerate() {
-            let normalized_name = normalize_field_name(field.name());
-            mapping.insert(normalized_name.to_string(), index);
-        }
-    }
-    
-    mapping
+/// Lê o arquivo Parquet como `RecordBatch`es do Arrow, um por bloco de linhas,
+/// sem convertê-los em empreendimentos; usado pela validação colunar. Os
+/// lotes são lidos sob demanda, à medida que o leitor é percorrido.
+pub fn read_parquet_em_lotes(path: &str) -> Result<ParquetRecordBatchReader, Box<dyn Error>> {
+    leitor_de_lotes(Entrada::Caminho(path))
 }
 
-fn deserialize_row(row: &parquet::record::Row, column_mapping: &HashMap<String, usize>) -> Result<Empreendimento, Box<dyn Error>> {
-    let get_field = |field_name: &str| -> Result<String, Box<dyn Error>> {
-        let index = column_mapping.get(field_name)
-            .ok_or_else(|| format!("Campo '{}' não encontrado no Parquet", field_name))?;
-        Ok(row.get_string(*index)?.to_string())
+fn leitor_de_lotes(entrada: Entrada) -> Result<ParquetRecordBatchReader, Box<dyn Error>> {
+    let leitor = match entrada.arquivo()? {
+        Some(path) => ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?,
+        None => ParquetRecordBatchReaderBuilder::try_new(Bytes::from(entrada.bytes()?))?.build()?,
     };
+    Ok(leitor)
+}
 
-    let get_numeric_field = |field_name: &str| -> Result<f64, Box<dyn Error>> {
-        let index = column_mapping.get(field_name)
-            .ok_or_else(|| format!("Campo '{}' não encontrado no Parquet", field_name))?;
-        Ok(row.get_double(*index)?)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        ArrayRef, Decimal128Array, Float32Array, Float64Array, Int16Array, Int64Array, StringArray,
+        UInt8Array,
     };
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use std::sync::Arc;
+    use tempfile::NamedTempFile;
 
-    let get_u32_field = |field_name: &str| -> Result<u32, Box<dyn Error>> {
-        let index = column_mapping.get(field_name)
-            .ok_or_else(|| format!("Campo '{}' não encontrado no Parquet", field_name))?;
-        Ok(row.get_int(*index)? as u32)
-    };
+    fn gravar(colunas: Vec<(&str, ArrayRef)>) -> NamedTempFile {
+        let batch = RecordBatch::try_from_iter(colunas).unwrap();
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut writer =
+            ArrowWriter::try_new(temp_file.reopen().unwrap(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        temp_file
+    }
 
-    let area_de_lazer = if column_mapping.contains_key("area_de_lazer") {
-        match get_numeric_field("area_de_lazer") {
-            Ok(value) => Some(value),
-            Err(_) => None,
-        }
-    } else {
-        None
-    };
+    fn texto(valores: &[Option<&str>]) -> ArrayRef {
+        Arc::new(StringArray::from(valores.to_vec()))
+    }
 
-    Ok(Empreendimento {
-        construtora: get_field("construtora")?,
-        cidade: get_field("cidade")?,
-        area_do_terreno: get_numeric_field("area_do_terreno")?,
-        numero_de_torres: get_u32_field("numero_de_torres")?,
-        altura_da_torre: get_numeric_field("altura_da_torre")?,
-        area_da_torre: get_numeric_field("area_da_torre")?,
-        area_de_lazer,
-    })
-}
+    fn reais(valores: &[Option<f64>]) -> ArrayRef {
+        Arc::new(Float64Array::from(valores.to_vec()))
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use parquet::schema::parser::parse_message_type;
+    #[test]
+    fn test_coercao_de_tipos_compativeis() {
+        let arquivo = gravar(vec![
+            ("construtora", texto(&[Some("Alfa")])),
+            ("cidade", texto(&[Some("Curitiba")])),
+            (
+                "area-do-terreno",
+                Arc::new(Float32Array::from(vec![1000.5])),
+            ),
+            ("numero_de_torres", Arc::new(Int64Array::from(vec![3]))),
+            (
+                "altura_da_torre",
+                Arc::new(
+                    Decimal128Array::from(vec![2575])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+            ),
+            ("area_da_torre", texto(&[Some(" 300,5 ")])),
+            ("area_de_lazer", Arc::new(Int16Array::from(vec![150]))),
+            ("vagas_de_garagem", Arc::new(UInt8Array::from(vec![40]))),
+            ("unidades_por_torre", texto(&[Some("12")])),
+            ("latitude", reais(&[None])),
+        ]);
+
+        let lidos = read_parquet(arquivo.path().to_str().unwrap()).unwrap();
+        let e = &lidos[0];
+        assert_eq!(e.area_do_terreno, 1000.5);
+        assert_eq!(e.numero_de_torres, 3);
+        assert_eq!(e.altura_da_torre, 25.75);
+        assert_eq!(e.area_da_torre, 300.5);
+        assert_eq!(e.area_de_lazer, Some(150.0));
+        assert_eq!(e.vagas_de_garagem, Some(40));
+        assert_eq!(e.unidades_por_torre, Some(12));
+        assert_eq!(e.latitude, None);
+    }
+
+    #[test]
+    fn test_read_parquet_de_um_leitor() {
+        let arquivo = gravar(obrigatorias(2));
+
+        let lidos = read_parquet(Entrada::leitor(arquivo.reopen().unwrap())).unwrap();
+        assert_eq!(lidos.len(), 2);
+        assert_eq!(lidos[1].construtora, "Alfa");
+
+        assert!(read_parquet(Entrada::leitor(&b"construtora,cidade\n"[..])).is_err());
+    }
+
+    fn obrigatorias(linhas: usize) -> Vec<(&'static str, ArrayRef)> {
+        vec![
+            ("construtora", texto(&vec![Some("Alfa"); linhas])),
+            ("cidade", texto(&vec![Some("Curitiba"); linhas])),
+            ("area_do_terreno", reais(&vec![Some(1000.0); linhas])),
+            (
+                "numero_de_torres",
+                Arc::new(Int64Array::from(vec![1; linhas])),
+            ),
+            ("altura_da_torre", reais(&vec![Some(20.0); linhas])),
+            ("area_da_torre", reais(&vec![Some(300.0); linhas])),
+        ]
+    }
 
     #[test]
-    fn test_column_mapping_creation() {
-        let schema = "
-            message empreendimento {
-                REQUIRED BINARY construtora (UTF8);
-                REQUIRED BINARY cidade (UTF8);
-                REQUIRED DOUBLE area_do_terreno;
-                REQUIRED INT32 numero_de_torres;
-                REQUIRED DOUBLE altura_da_torre;
-                REQUIRED DOUBLE area_da_torre;
-                OPTIONAL DOUBLE area_de_lazer;
-            }
-        ";
-        
-        let message_type = parse_message_type(schema).unwrap();
-        let mapping = create_column_mapping(&message_type);
-        
-        assert!(mapping.contains_key("construtora"));
-        assert!(mapping.contains_key("cidade"));
-        assert!(mapping.contains_key("area_do_terreno"));
-        assert!(mapping.contains_key("numero_de_torres"));
-        assert!(mapping.contains_key("altura_da_torre"));
-        assert!(mapping.contains_key("area_da_torre"));
-        assert!(mapping.contains_key("area_de_lazer"));
+    fn test_nulo_e_diferente_de_erro_de_tipo() {
+        let mut colunas = obrigatorias(3);
+        colunas.push(("area_de_lazer", texto(&[None, Some(""), Some("150")])));
+        let arquivo = gravar(colunas);
+        let lidos = read_parquet(arquivo.path().to_str().unwrap()).unwrap();
+        assert_eq!(lidos[0].area_de_lazer, None);
+        assert_eq!(lidos[1].area_de_lazer, None);
+        assert_eq!(lidos[2].area_de_lazer, Some(150.0));
+
+        let mut colunas = obrigatorias(2);
+        colunas.push(("area_de_lazer", texto(&[Some("150"), Some("muita")])));
+        let arquivo = gravar(colunas);
+        let erro = read_parquet(arquivo.path().to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            erro,
+            "Linha 2, coluna 'area_de_lazer': esperado número, encontrado \"muita\""
+        );
     }
 
     #[test]
-    fn test_column_mapping_with_kebab_case() {
-        let schema = "
-            message empreendimento {
-                REQUIRED BINARY construtora (UTF8);
-                REQUIRED BINARY cidade (UTF8);
-                REQUIRED DOUBLE area-do-terreno;
-                REQUIRED INT32 numero-de-torres;
-                REQUIRED DOUBLE altura-da-torre;
-                REQUIRED DOUBLE area-da-torre;
-                OPTIONAL DOUBLE area-de-lazer;
-            }
-        ";
-        
-        let message_type = parse_message_type(schema).unwrap();
-        let mapping = create_column_mapping(&message_type);
-        
-        assert!(mapping.contains_key("area_do_terreno"));
-        assert!(mapping.contains_key("numero_de_torres"));
-        assert!(mapping.contains_key("altura_da_torre"));
-        assert!(mapping.contains_key("area_da_torre"));
-        assert!(mapping.contains_key("area_de_lazer"));
+    fn test_obrigatorio_nulo_informa_linha_e_coluna() {
+        let mut colunas = obrigatorias(2);
+        colunas[1] = ("cidade", texto(&[Some("Curitiba"), None]));
+        let arquivo = gravar(colunas);
+        let erro = read_parquet(arquivo.path().to_str().unwrap()).unwrap_err();
+        assert_eq!(erro.to_string(), "Linha 2, coluna 'cidade': valor nulo");
+    }
+
+    #[test]
+    fn test_inteiro_invalido() {
+        let mut colunas = obrigatorias(2);
+        colunas[3] = ("numero_de_torres", reais(&[Some(2.0), Some(2.5)]));
+        let arquivo = gravar(colunas);
+        let erro = read_parquet(arquivo.path().to_str().unwrap()).unwrap_err();
+        assert_eq!(
+            erro.to_string(),
+            "Linha 2, coluna 'numero_de_torres': esperado inteiro não negativo, encontrado 2.5"
+        );
+
+        let mut colunas = obrigatorias(1);
+        colunas[0] = ("construtora", reais(&[Some(1.0)]));
+        let arquivo = gravar(colunas);
+        let erro = read_parquet(arquivo.path().to_str().unwrap()).unwrap_err();
+        assert!(
+            erro.to_string()
+                .starts_with("Linha 1, coluna 'construtora': esperado texto")
+        );
+    }
+
+    #[test]
+    fn test_limites_iguais_aos_da_validacao_colunar() {
+        let mut colunas = obrigatorias(2);
+        colunas.push((
+            "numero_de_unidades",
+            Arc::new(Int64Array::from(vec![40, 3_000_000_000])),
+        ));
+        let arquivo = gravar(colunas);
+        let path = arquivo.path().to_str().unwrap();
+
+        let erro = read_parquet(path).unwrap_err().to_string();
+        let lote = read_parquet_em_lotes(path)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let erro_colunar = arrow_converter::normalizar(&lote, 1)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(erro, erro_colunar);
+        assert!(
+            erro.starts_with("Linha 2, coluna 'numero_de_unidades'"),
+            "{}",
+            erro
+        );
     }
 }
@@ -0,0 +1,406 @@
+use super::Entrada;
+use crate::models::empreendimento::Empreendimento;
+use crate::models::field_converter::normalize_field_name;
+use crate::models::json_aninhado;
+use calamine::{Data, Ods, Range, Reader, Xlsx, open_workbook};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{Cursor, Read, Seek};
+
+/// Linhas examinadas, a partir do topo da aba, em busca do cabeçalho.
+const LINHAS_ATE_O_CABECALHO: usize = 20;
+
+/// Aba da planilha a ler: pelo nome ou pela posição, a partir de 0.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aba {
+    Indice(usize),
+    /// Nome da aba; um nome numérico que não corresponde a nenhuma aba,
+    /// como `2`, é tomado como a posição.
+    Nome(String),
+}
+
+impl Default for Aba {
+    fn default() -> Self {
+        Aba::Indice(0)
+    }
+}
+
+impl From<&str> for Aba {
+    /// O texto é o nome da aba, ou a posição se nenhuma aba tiver esse nome.
+    fn from(texto: &str) -> Self {
+        Aba::Nome(texto.to_string())
+    }
+}
+
+impl Aba {
+    /// Nome da aba entre os `nomes` da planilha.
+    fn escolher(&self, nomes: &[String]) -> Result<String, String> {
+        let indice = match self {
+            Aba::Indice(indice) => *indice,
+            Aba::Nome(nome) => match nomes.iter().find(|n| *n == nome) {
+                Some(nome) => return Ok(nome.clone()),
+                None => nome.trim().parse().map_err(|_| {
+                    format!(
+                        "Aba '{}' não encontrada; abas disponíveis: {}",
+                        nome,
+                        nomes.join(", ")
+                    )
+                })?,
+            },
+        };
+        nomes.get(indice).cloned().ok_or_else(|| {
+            format!(
+                "A planilha tem {} aba(s); não há aba na posição {}",
+                nomes.len(),
+                indice
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OpcoesPlanilha {
+    pub aba: Aba,
+}
+
+pub fn read_xlsx<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    read_xlsx_com_opcoes(entrada, &OpcoesPlanilha::default())
+}
+
+/// Lê uma planilha Excel (`.xlsx`): o cabeçalho é a primeira linha com a
+/// coluna `construtora`, e os nomes das colunas em português ("Área do
+/// Terreno") são convertidos para os campos do empreendimento. Células vazias
+/// viram `None`; erros indicam a aba e a célula, como `Aba 'Plan1', célula C4`.
+pub fn read_xlsx_com_opcoes<'a>(
+    entrada: impl Into<Entrada<'a>>,
+    opcoes: &OpcoesPlanilha,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let entrada = entrada.into();
+    match entrada.arquivo()? {
+        Some(path) => ler_planilha(&mut open_workbook::<Xlsx<_>, _>(path)?, opcoes),
+        None => ler_planilha(&mut Xlsx::new(Cursor::new(entrada.bytes()?))?, opcoes),
+    }
+}
+
+pub fn read_ods<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    read_ods_com_opcoes(entrada, &OpcoesPlanilha::default())
+}
+
+/// Lê uma planilha OpenDocument (`.ods`), com as mesmas regras de [`read_xlsx_com_opcoes`].
+pub fn read_ods_com_opcoes<'a>(
+    entrada: impl Into<Entrada<'a>>,
+    opcoes: &OpcoesPlanilha,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let entrada = entrada.into();
+    match entrada.arquivo()? {
+        Some(path) => ler_planilha(&mut open_workbook::<Ods<_>, _>(path)?, opcoes),
+        None => ler_planilha(&mut Ods::new(Cursor::new(entrada.bytes()?))?, opcoes),
+    }
+}
+
+fn ler_planilha<R, RS>(
+    planilha: &mut R,
+    opcoes: &OpcoesPlanilha,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>>
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+    R::Error: Error + 'static,
+{
+    let nome = opcoes.aba.escolher(&planilha.sheet_names())?;
+    let celulas = planilha.worksheet_range(&nome)?;
+    ler_aba(&nome, &celulas)
+}
+
+fn ler_aba(aba: &str, celulas: &Range<Data>) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let (linha_inicial, coluna_inicial) = celulas.start().unwrap_or((0, 0));
+    let mut linhas = celulas.rows().enumerate();
+
+    let colunas = linhas
+        .by_ref()
+        .take(LINHAS_ATE_O_CABECALHO)
+        .map(|(_, linha)| mapear_cabecalho(linha))
+        .find(|colunas| colunas.contains_key("construtora"))
+        .ok_or_else(|| {
+            format!(
+                "Aba '{}': cabeçalho com a coluna 'construtora' não encontrado",
+                aba
+            )
+        })?;
+
+    linhas
+        .filter(|(_, valores)| valores.iter().any(|valor| !vazia(valor)))
+        .map(|(indice, valores)| {
+            Linha {
+                aba,
+                numero: linha_inicial as usize + indice + 1,
+                coluna_inicial: coluna_inicial as usize,
+                valores,
+                colunas: &colunas,
+            }
+            .empreendimento()
+        })
+        .collect()
+}
+
+/// Posição de cada campo conhecido na linha do cabeçalho.
+fn mapear_cabecalho(linha: &[Data]) -> HashMap<String, usize> {
+    linha
+        .iter()
+        .enumerate()
+        .filter_map(|(indice, valor)| match valor {
+            Data::String(texto) => Some((campo_do_cabecalho(texto), indice)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Converte o título da coluna no nome do campo: "Área do Terreno" e
+/// "area-do-terreno" viram `area_do_terreno`.
+fn campo_do_cabecalho(titulo: &str) -> String {
+    let mut chave = String::new();
+    for c in titulo.trim().to_lowercase().chars() {
+        let c = match c {
+            'á' | 'à' | 'â' | 'ã' => 'a',
+            'é' | 'ê' => 'e',
+            'í' => 'i',
+            'ó' | 'ô' | 'õ' => 'o',
+            'ú' | 'ü' => 'u',
+            'ç' => 'c',
+            ' ' | '_' | '-' => '-',
+            outro => outro,
+        };
+        if c != '-' || !chave.ends_with('-') {
+            chave.push(c);
+        }
+    }
+    normalize_field_name(&chave).to_string()
+}
+
+fn vazia(valor: &Data) -> bool {
+    match valor {
+        Data::Empty => true,
+        Data::String(texto) => texto.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Referência da célula no estilo A1, com índices a partir de 0.
+fn referencia(linha: usize, coluna: usize) -> String {
+    let mut letras = Vec::new();
+    let mut resto = coluna + 1;
+    while resto > 0 {
+        resto -= 1;
+        letras.push(b'A' + (resto % 26) as u8);
+        resto /= 26;
+    }
+    letras.reverse();
+    format!(
+        "{}{}",
+        String::from_utf8(letras).unwrap_or_default(),
+        linha + 1
+    )
+}
+
+/// Uma linha da aba, com acesso às células pelo nome do campo.
+struct Linha<'a> {
+    aba: &'a str,
+    /// Número da linha na planilha, a partir de 1.
+    numero: usize,
+    coluna_inicial: usize,
+    valores: &'a [Data],
+    colunas: &'a HashMap<String, usize>,
+}
+
+impl<'a> Linha<'a> {
+    fn erro(&self, campo: &str, mensagem: impl std::fmt::Display) -> Box<dyn Error> {
+        let celula = self
+            .colunas
+            .get(campo)
+            .map(|&coluna| referencia(self.numero - 1, self.coluna_inicial + coluna))
+            .unwrap_or_default();
+        format!(
+            "Aba '{}', célula {} ({}): {}",
+            self.aba, celula, campo, mensagem
+        )
+        .into()
+    }
+
+    /// Valor da célula; `None` quando a coluna não existe ou a célula está vazia.
+    fn valor(&self, campo: &str) -> Option<&'a Data> {
+        self.colunas
+            .get(campo)
+            .and_then(|&indice| self.valores.get(indice))
+            .filter(|valor| !vazia(valor))
+    }
+
+    fn obrigatorio<T>(&self, campo: &str, valor: Option<T>) -> Result<T, Box<dyn Error>> {
+        match valor {
+            Some(valor) => Ok(valor),
+            None if self.colunas.contains_key(campo) => Err(self.erro(campo, "célula vazia")),
+            None => Err(format!(
+                "Aba '{}': coluna '{}' não encontrada no cabeçalho",
+                self.aba, campo
+            )
+            .into()),
+        }
+    }
+
+    fn texto(&self, campo: &str) -> Result<Option<String>, Box<dyn Error>> {
+        self.valor(campo)
+            .map(|valor| match valor {
+                Data::String(texto) => Ok(texto.trim().to_string()),
+                Data::Int(numero) => Ok(numero.to_string()),
+                Data::Float(numero) => Ok(numero.to_string()),
+                outro => Err(self.erro(campo, format!("esperado texto, encontrado {}", outro))),
+            })
+            .transpose()
+    }
+
+    fn real(&self, campo: &str) -> Result<Option<f64>, Box<dyn Error>> {
+        let Some(valor) = self.valor(campo) else {
+            return Ok(None);
+        };
+        let numero = match valor {
+            Data::Float(numero) => Some(*numero),
+            Data::Int(numero) => Some(*numero as f64),
+            Data::String(texto) => texto.trim().replace(',', ".").parse().ok(),
+            _ => None,
+        };
+        numero
+            .map(Some)
+            .ok_or_else(|| self.erro(campo, format!("esperado número, encontrado \"{}\"", valor)))
+    }
+
+    fn inteiro(&self, campo: &str) -> Result<Option<u32>, Box<dyn Error>> {
+        let Some(numero) = self.real(campo)? else {
+            return Ok(None);
+        };
+        if numero.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(&numero) {
+            Ok(Some(numero as u32))
+        } else {
+            Err(self.erro(
+                campo,
+                format!("esperado inteiro não negativo, encontrado {}", numero),
+            ))
+        }
+    }
+
+    fn json<T: serde::de::DeserializeOwned>(
+        &self,
+        campo: &str,
+    ) -> Result<Option<T>, Box<dyn Error>> {
+        json_aninhado::de_texto(self.texto(campo)?).map_err(|e| self.erro(campo, e))
+    }
+
+    fn empreendimento(&self) -> Result<Empreendimento, Box<dyn Error>> {
+        Ok(Empreendimento {
+            construtora: self.obrigatorio("construtora", self.texto("construtora")?)?,
+            cidade: self.obrigatorio("cidade", self.texto("cidade")?)?,
+            area_do_terreno: self.obrigatorio("area_do_terreno", self.real("area_do_terreno")?)?,
+            numero_de_torres: self
+                .obrigatorio("numero_de_torres", self.inteiro("numero_de_torres")?)?,
+            altura_da_torre: self.obrigatorio("altura_da_torre", self.real("altura_da_torre")?)?,
+            area_da_torre: self.obrigatorio("area_da_torre", self.real("area_da_torre")?)?,
+            area_de_lazer: self.real("area_de_lazer")?,
+            area_construida: self.real("area_construida")?,
+            area_permeavel: self.real("area_permeavel")?,
+            area_de_projecao: self.real("area_de_projecao")?,
+            uso: self
+                .texto("uso")?
+                .map(|uso| uso.parse().map_err(|e| self.erro("uso", e)))
+                .transpose()?
+                .unwrap_or_default(),
+            zona: self.texto("zona")?,
+            latitude: self.real("latitude")?,
+            longitude: self.real("longitude")?,
+            cota_do_terreno: self.real("cota_do_terreno")?,
+            numero_de_unidades: self.inteiro("numero_de_unidades")?,
+            unidades_por_torre: self.inteiro("unidades_por_torre")?,
+            vagas_de_garagem: self.inteiro("vagas_de_garagem")?,
+            vagas_acessiveis: self.inteiro("vagas_acessiveis")?,
+            vagas_de_motos: self.inteiro("vagas_de_motos")?,
+            vagas_de_bicicletas: self.inteiro("vagas_de_bicicletas")?,
+            torres: self.json("torres")?,
+            lote: self.json("lote")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_campo_do_cabecalho() {
+        assert_eq!(campo_do_cabecalho("Construtora"), "construtora");
+        assert_eq!(campo_do_cabecalho(" Área do  Terreno "), "area_do_terreno");
+        assert_eq!(campo_do_cabecalho("Número de Torres"), "numero_de_torres");
+        assert_eq!(campo_do_cabecalho("area-de-lazer"), "area_de_lazer");
+        assert_eq!(campo_do_cabecalho("vagas_acessiveis"), "vagas_acessiveis");
+        assert_eq!(campo_do_cabecalho("Área Permeável"), "area_permeavel");
+    }
+
+    #[test]
+    fn test_referencia() {
+        assert_eq!(referencia(0, 0), "A1");
+        assert_eq!(referencia(3, 2), "C4");
+        assert_eq!(referencia(9, 25), "Z10");
+        assert_eq!(referencia(0, 26), "AA1");
+        assert_eq!(referencia(0, 701), "ZZ1");
+    }
+
+    #[test]
+    fn test_aba_por_texto() {
+        let nomes = ["Notas", "2024", "Empreendimentos", "1"].map(String::from);
+        let escolher = |texto: &str| Aba::from(texto).escolher(&nomes);
+
+        assert_eq!(escolher("Empreendimentos").unwrap(), "Empreendimentos");
+        assert_eq!(escolher("2024").unwrap(), "2024", "Nome numérico existente");
+        assert_eq!(
+            escolher("1").unwrap(),
+            "1",
+            "O nome prevalece sobre a posição"
+        );
+        assert_eq!(
+            escolher("2").unwrap(),
+            "Empreendimentos",
+            "Posição sem aba com o nome"
+        );
+        assert_eq!(Aba::Indice(1).escolher(&nomes).unwrap(), "2024");
+        assert_eq!(
+            escolher("7").unwrap_err(),
+            "A planilha tem 4 aba(s); não há aba na posição 7"
+        );
+        assert_eq!(
+            escolher("Resumo").unwrap_err(),
+            "Aba 'Resumo' não encontrada; abas disponíveis: Notas, 2024, Empreendimentos, 1"
+        );
+    }
+
+    #[test]
+    fn test_ler_aba_com_titulo_acima_do_cabecalho() {
+        let mut celulas = Range::new((2, 1), (4, 4));
+        let texto = |t: &str| Data::String(t.to_string());
+        celulas.set_value((2, 1), texto("Empreendimentos 2024"));
+        for (coluna, titulo) in ["Construtora", "Cidade", "Área do Terreno", "Área de Lazer"]
+            .into_iter()
+            .enumerate()
+        {
+            celulas.set_value((3, 1 + coluna as u32), texto(titulo));
+        }
+        celulas.set_value((4, 1), texto("Alfa"));
+        celulas.set_value((4, 2), texto("Curitiba"));
+        celulas.set_value((4, 3), texto("mil"));
+
+        let erro = ler_aba("Plan1", &celulas).unwrap_err().to_string();
+        assert_eq!(
+            erro,
+            "Aba 'Plan1', célula D5 (area_do_terreno): esperado número, encontrado \"mil\""
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::models::geometria::{Coordenada, Poligono};
+use crate::models::restricao_altura::{FormaDaSuperficie, SuperficieDeRestricao};
+use serde_json::Value;
+use std::error::Error;
+use std::fs;
+
+/// Lê superfícies de restrição de altura de uma `FeatureCollection` GeoJSON.
+///
+/// Polígonos usam a propriedade `altitude_maxima`; pontos definem cones e
+/// exigem também `inclinacao` e `raio`. A propriedade `nome` é opcional.
+pub fn read_restricoes_altura(path: &str) -> Result<Vec<SuperficieDeRestricao>, Box<dyn Error>> {
+    let data = fs::read_to_string(path)?;
+    let camada: Value = serde_json::from_str(&data)?;
+    let features = camada
+        .get("features")
+        .and_then(Value::as_array)
+        .ok_or("Camada de restrições de altura deve ser uma FeatureCollection")?;
+
+    let mut superficies = Vec::new();
+    for (indice, feature) in features.iter().enumerate() {
+        let propriedade = |nome: &str| -> Result<f64, String> {
+            feature
+                .pointer(&format!("/properties/{}", nome))
+                .and_then(Value::as_f64)
+                .ok_or_else(|| format!("Feição {} sem o atributo numérico '{}'", indice, nome))
+        };
+        let nome = feature
+            .pointer("/properties/nome")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Superfície {}", indice + 1));
+        let altitude_maxima = propriedade("altitude_maxima")?;
+
+        let geometria = feature.get("geometry").unwrap_or(&Value::Null);
+        let coordenadas = geometria["coordinates"].clone();
+        let formas = match geometria.get("type").and_then(Value::as_str) {
+            Some("Point") => vec![FormaDaSuperficie::Cone {
+                origem: serde_json::from_value::<Coordenada>(coordenadas)?,
+                inclinacao: propriedade("inclinacao")?,
+                raio: propriedade("raio")?,
+            }],
+            Some("Polygon") => vec![area(serde_json::from_value(coordenadas)?)],
+            Some("MultiPolygon") => serde_json::from_value::<Vec<Vec<Poligono>>>(coordenadas)?
+                .into_iter()
+                .map(area)
+                .collect(),
+            outro => {
+                return Err(format!(
+                    "Feição {} com geometria {:?} não suportada; use Point, Polygon ou MultiPolygon",
+                    indice, outro
+                )
+                .into());
+            }
+        };
+
+        superficies.extend(formas.into_iter().map(|forma| SuperficieDeRestricao {
+            nome: nome.clone(),
+            altitude_maxima,
+            forma,
+        }));
+    }
+
+    Ok(superficies)
+}
+
+fn area(aneis: Vec<Poligono>) -> FormaDaSuperficie {
+    let mut aneis = aneis.into_iter();
+    FormaDaSuperficie::Area {
+        contorno: aneis.next().unwrap_or_default(),
+        furos: aneis.collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_read_restricoes_altura() {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": { "nome": "Cone SBSP", "altitude_maxima": 10, "inclinacao": 0.05, "raio": 4000 },
+                    "geometry": { "type": "Point", "coordinates": [-46.65, -23.62] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": { "altitude_maxima": 20.5 },
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1], [0, 0]]]
+                    }
+                }
+            ]
+        }"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", geojson).unwrap();
+
+        let superficies = read_restricoes_altura(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(superficies.len(), 2);
+        assert_eq!(superficies[0].nome, "Cone SBSP");
+        assert_eq!(
+            superficies[0].forma,
+            FormaDaSuperficie::Cone {
+                origem: [-46.65, -23.62],
+                inclinacao: 0.05,
+                raio: 4000.0
+            }
+        );
+        assert_eq!(superficies[1].nome, "Superfície 2");
+        assert_eq!(superficies[1].altitude_maxima, 20.5);
+    }
+
+    #[test]
+    fn test_cone_sem_raio() {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"altitude_maxima": 10, "inclinacao": 0.05},
+             "geometry": {"type": "Point", "coordinates": [0, 0]}}
+        ]}"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", geojson).unwrap();
+
+        let erro = read_restricoes_altura(temp_file.path().to_str().unwrap()).unwrap_err();
+        assert!(erro.to_string().contains("'raio'"));
+    }
+}
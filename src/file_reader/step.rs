@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+
+/// Valor de um atributo em um arquivo STEP (ISO 10303-21).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Valor {
+    /// `$`: atributo não informado.
+    Nulo,
+    /// `*`: atributo derivado de outros.
+    Derivado,
+    Inteiro(i64),
+    Real(f64),
+    Texto(String),
+    /// `.VALOR.`: enumeração ou booleano.
+    Enum(String),
+    /// `#123`: referência a outra entidade.
+    Ref(u64),
+    Lista(Vec<Valor>),
+    /// `IFCLABEL('x')`: valor com tipo explícito.
+    Tipado(String, Box<Valor>),
+}
+
+impl Valor {
+    pub fn como_ref(&self) -> Option<u64> {
+        match self {
+            Valor::Ref(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn como_f64(&self) -> Option<f64> {
+        match self {
+            Valor::Real(v) => Some(*v),
+            Valor::Inteiro(v) => Some(*v as f64),
+            Valor::Tipado(_, v) => v.como_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn como_texto(&self) -> Option<&str> {
+        match self {
+            Valor::Texto(v) => Some(v),
+            Valor::Tipado(_, v) => v.como_texto(),
+            _ => None,
+        }
+    }
+
+    pub fn como_enum(&self) -> Option<&str> {
+        match self {
+            Valor::Enum(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn como_lista(&self) -> &[Valor] {
+        match self {
+            Valor::Lista(v) => v,
+            _ => &[],
+        }
+    }
+
+    /// Referências de uma lista, ignorando os demais valores.
+    pub fn refs(&self) -> impl Iterator<Item = u64> + '_ {
+        self.como_lista().iter().filter_map(Valor::como_ref)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entidade {
+    /// Nome do tipo em maiúsculas, como no arquivo (`IFCSLAB`).
+    pub tipo: String,
+    pub atributos: Vec<Valor>,
+}
+
+impl Entidade {
+    pub fn atributo(&self, indice: usize) -> &Valor {
+        self.atributos.get(indice).unwrap_or(&Valor::Nulo)
+    }
+}
+
+/// Conteúdo da seção DATA de um arquivo STEP, indexado pelo número da instância.
+#[derive(Debug, Default)]
+pub struct ModeloStep {
+    pub esquema: String,
+    pub entidades: HashMap<u64, Entidade>,
+    /// Instâncias que não puderam ser interpretadas e foram ignoradas.
+    pub avisos: Vec<String>,
+}
+
+impl ModeloStep {
+    pub fn ler(texto: &str) -> Result<Self, String> {
+        let sem_comentarios = remover_comentarios(texto);
+        let mut modelo = ModeloStep::default();
+        let mut em_dados = false;
+
+        for instrucao in instrucoes(&sem_comentarios) {
+            let instrucao = instrucao.trim();
+            if instrucao.is_empty() {
+                continue;
+            }
+            if !em_dados {
+                if let Some(parametros) = instrucao.strip_prefix("FILE_SCHEMA") {
+                    modelo.esquema = ler_valor(&mut Cursor::new(parametros))
+                        .ok()
+                        .and_then(|parametros| {
+                            let esquemas = parametros.como_lista().first()?;
+                            esquemas
+                                .como_lista()
+                                .first()?
+                                .como_texto()
+                                .map(str::to_string)
+                        })
+                        .unwrap_or_default();
+                } else if instrucao == "DATA" {
+                    em_dados = true;
+                }
+                continue;
+            }
+            if instrucao == "ENDSEC" {
+                break;
+            }
+            match ler_instancia(instrucao) {
+                Ok((id, entidade)) => {
+                    modelo.entidades.insert(id, entidade);
+                }
+                Err(erro) => modelo.avisos.push(format!(
+                    "Instância ignorada ({}): {}",
+                    erro,
+                    resumir(instrucao)
+                )),
+            }
+        }
+
+        if !em_dados {
+            return Err("Arquivo STEP sem seção DATA".to_string());
+        }
+        Ok(modelo)
+    }
+
+    pub fn entidade(&self, id: u64) -> Option<&Entidade> {
+        self.entidades.get(&id)
+    }
+
+    /// Entidade referenciada pelo valor, se for uma referência válida.
+    pub fn resolver(&self, valor: &Valor) -> Option<&Entidade> {
+        valor.como_ref().and_then(|id| self.entidade(id))
+    }
+
+    /// Instâncias de um tipo, em ordem crescente de número.
+    pub fn do_tipo(&self, tipo: &str) -> Vec<(u64, &Entidade)> {
+        let mut encontradas: Vec<(u64, &Entidade)> = self
+            .entidades
+            .iter()
+            .filter(|(_, e)| e.tipo == tipo)
+            .map(|(id, e)| (*id, e))
+            .collect();
+        encontradas.sort_by_key(|(id, _)| *id);
+        encontradas
+    }
+}
+
+fn resumir(instrucao: &str) -> String {
+    let resumo: String = instrucao.chars().take(60).collect();
+    if resumo.len() < instrucao.len() {
+        format!("{}...", resumo)
+    } else {
+        resumo
+    }
+}
+
+fn remover_comentarios(texto: &str) -> String {
+    let mut resultado = String::with_capacity(texto.len());
+    let mut chars = texto.chars().peekable();
+    let mut em_texto = false;
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            em_texto = !em_texto;
+        } else if !em_texto && c == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut anterior = ' ';
+            for c in chars.by_ref() {
+                if anterior == '*' && c == '/' {
+                    break;
+                }
+                anterior = c;
+            }
+            continue;
+        }
+        resultado.push(c);
+    }
+    resultado
+}
+
+/// Divide o arquivo nas instruções terminadas por `;`, respeitando textos.
+fn instrucoes(texto: &str) -> Vec<&str> {
+    let mut partes = Vec::new();
+    let mut inicio = 0;
+    let mut em_texto = false;
+    for (i, c) in texto.char_indices() {
+        match c {
+            '\'' => em_texto = !em_texto,
+            ';' if !em_texto => {
+                partes.push(&texto[inicio..i]);
+                inicio = i + 1;
+            }
+            _ => {}
+        }
+    }
+    partes
+}
+
+fn ler_instancia(instrucao: &str) -> Result<(u64, Entidade), String> {
+    let (id, corpo) = instrucao
+        .strip_prefix('#')
+        .and_then(|resto| resto.split_once('='))
+        .ok_or("esperado '#id='")?;
+    let id = id
+        .trim()
+        .parse()
+        .map_err(|_| format!("número de instância inválido '{}'", id.trim()))?;
+
+    let mut cursor = Cursor::new(corpo.trim());
+    let tipo = cursor.palavra();
+    if tipo.is_empty() {
+        return Err("tipo da entidade ausente".to_string());
+    }
+    let atributos = match ler_valor(&mut cursor)? {
+        Valor::Lista(atributos) => atributos,
+        _ => return Err("atributos ausentes".to_string()),
+    };
+    cursor.pular_espacos();
+    if !cursor.fim() {
+        return Err("conteúdo após os atributos".to_string());
+    }
+    Ok((id, Entidade { tipo, atributos }))
+}
+
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(texto: &'a str) -> Self {
+        Self {
+            chars: texto.chars().peekable(),
+        }
+    }
+
+    fn pular_espacos(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    fn fim(&mut self) -> bool {
+        self.chars.peek().is_none()
+    }
+
+    fn palavra(&mut self) -> String {
+        self.pular_espacos();
+        let mut palavra = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                palavra.push(c.to_ascii_uppercase());
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        palavra
+    }
+}
+
+fn ler_valor(cursor: &mut Cursor) -> Result<Valor, String> {
+    cursor.pular_espacos();
+    let c = *cursor.chars.peek().ok_or("valor ausente")?;
+    match c {
+        '(' => {
+            cursor.chars.next();
+            let mut itens = Vec::new();
+            cursor.pular_espacos();
+            if cursor.chars.peek() == Some(&')') {
+                cursor.chars.next();
+                return Ok(Valor::Lista(itens));
+            }
+            loop {
+                itens.push(ler_valor(cursor)?);
+                cursor.pular_espacos();
+                match cursor.chars.next() {
+                    Some(',') => continue,
+                    Some(')') => return Ok(Valor::Lista(itens)),
+                    _ => return Err("lista não terminada".to_string()),
+                }
+            }
+        }
+        '$' => {
+            cursor.chars.next();
+            Ok(Valor::Nulo)
+        }
+        '*' => {
+            cursor.chars.next();
+            Ok(Valor::Derivado)
+        }
+        '#' => {
+            cursor.chars.next();
+            let mut numero = String::new();
+            while let Some(&d) = cursor.chars.peek().filter(|d| d.is_ascii_digit()) {
+                numero.push(d);
+                cursor.chars.next();
+            }
+            numero
+                .parse()
+                .map(Valor::Ref)
+                .map_err(|_| "referência inválida".to_string())
+        }
+        '\'' => {
+            cursor.chars.next();
+            let mut texto = String::new();
+            loop {
+                match cursor.chars.next() {
+                    Some('\'') if cursor.chars.peek() == Some(&'\'') => {
+                        cursor.chars.next();
+                        texto.push('\'');
+                    }
+                    Some('\'') => return Ok(Valor::Texto(decodificar_texto(&texto))),
+                    Some(c) => texto.push(c),
+                    None => return Err("texto não terminado".to_string()),
+                }
+            }
+        }
+        '.' => {
+            cursor.chars.next();
+            let mut nome = String::new();
+            for c in cursor.chars.by_ref() {
+                if c == '.' {
+                    return Ok(Valor::Enum(nome));
+                }
+                nome.push(c);
+            }
+            Err("enumeração não terminada".to_string())
+        }
+        '"' => {
+            // Binário: mantido como texto hexadecimal.
+            cursor.chars.next();
+            let texto: String = cursor.chars.by_ref().take_while(|&c| c != '"').collect();
+            Ok(Valor::Texto(texto))
+        }
+        c if c == '-' || c == '+' || c.is_ascii_digit() => {
+            let mut numero = String::new();
+            while let Some(&d) = cursor.chars.peek() {
+                if d.is_ascii_digit() || matches!(d, '-' | '+' | '.' | 'E' | 'e') {
+                    numero.push(d);
+                    cursor.chars.next();
+                } else {
+                    break;
+                }
+            }
+            if let Ok(inteiro) = numero.parse() {
+                Ok(Valor::Inteiro(inteiro))
+            } else {
+                numero
+                    .parse()
+                    .map(Valor::Real)
+                    .map_err(|_| format!("número inválido '{}'", numero))
+            }
+        }
+        c if c.is_ascii_alphabetic() => {
+            let tipo = cursor.palavra();
+            match ler_valor(cursor)? {
+                Valor::Lista(mut valores) if valores.len() == 1 => {
+                    Ok(Valor::Tipado(tipo, Box::new(valores.remove(0))))
+                }
+                _ => Err(format!("valor tipado '{}' inválido", tipo)),
+            }
+        }
+        c => Err(format!("caractere inesperado '{}'", c)),
+    }
+}
+
+/// Decodifica as sequências de escape de texto do STEP (`\X2\`, `\X\`, `\S\`).
+fn decodificar_texto(texto: &str) -> String {
+    let mut resultado = String::with_capacity(texto.len());
+    let mut resto = texto;
+    while let Some(pos) = resto.find('\\') {
+        resultado.push_str(&resto[..pos]);
+        resto = &resto[pos..];
+        if let Some(codificado) = resto.strip_prefix("\\X2\\") {
+            let fim = codificado.find("\\X0\\").unwrap_or(codificado.len());
+            let unidades: Vec<u16> = codificado.as_bytes()[..fim]
+                .chunks(4)
+                .filter_map(|c| u16::from_str_radix(std::str::from_utf8(c).ok()?, 16).ok())
+                .collect();
+            resultado.push_str(&String::from_utf16_lossy(&unidades));
+            resto = codificado.get(fim + 4..).unwrap_or("");
+        } else if let Some(codificado) = resto.strip_prefix("\\X\\") {
+            match codificado
+                .get(..2)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                Some(byte) => {
+                    resultado.push(byte as char);
+                    resto = &codificado[2..];
+                }
+                None => {
+                    resultado.push('\\');
+                    resto = &resto[1..];
+                }
+            }
+        } else if let Some(codificado) = resto.strip_prefix("\\S\\") {
+            match codificado.chars().next() {
+                Some(c) => {
+                    resultado.push(char::from_u32(c as u32 + 128).unwrap_or(c));
+                    resto = &codificado[c.len_utf8()..];
+                }
+                None => resto = "",
+            }
+        } else if let Some(barra) = resto.strip_prefix("\\\\") {
+            resultado.push('\\');
+            resto = barra;
+        } else {
+            resultado.push('\\');
+            resto = &resto[1..];
+        }
+    }
+    resultado.push_str(resto);
+    resultado
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARQUIVO: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('ViewDefinition [CoordinationView]'),'2;1');
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+/* comentário; com ponto e vírgula */
+#1=IFCBUILDINGSTOREY('2x',$,'T\\X2\\00E9\\X0\\rreo',$,$,#5,$,$,.ELEMENT.,-3.5E-1);
+#2=IFCPROPERTYSINGLEVALUE('Nome',$,IFCLABEL('D''Ávila'),$);
+#3=IFCCARTESIANPOINT((0.,10.5,-2));
+#4=IFCQUEBRADO('x',);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    #[test]
+    fn test_ler_modelo_step() {
+        let modelo = ModeloStep::ler(ARQUIVO).unwrap();
+
+        assert_eq!(modelo.esquema, "IFC4");
+        assert_eq!(modelo.entidades.len(), 3);
+
+        let pavimento = modelo.entidade(1).unwrap();
+        assert_eq!(pavimento.tipo, "IFCBUILDINGSTOREY");
+        assert_eq!(pavimento.atributo(2).como_texto(), Some("Térreo"));
+        assert_eq!(pavimento.atributo(5).como_ref(), Some(5));
+        assert_eq!(pavimento.atributo(8).como_enum(), Some("ELEMENT"));
+        assert_eq!(pavimento.atributo(9).como_f64(), Some(-0.35));
+        assert_eq!(pavimento.atributo(20), &Valor::Nulo);
+
+        let propriedade = modelo.entidade(2).unwrap();
+        assert_eq!(propriedade.atributo(2).como_texto(), Some("D'Ávila"));
+
+        let ponto = modelo.entidade(3).unwrap();
+        let coordenadas: Vec<f64> = ponto
+            .atributo(0)
+            .como_lista()
+            .iter()
+            .filter_map(Valor::como_f64)
+            .collect();
+        assert_eq!(coordenadas, vec![0.0, 10.5, -2.0]);
+    }
+
+    #[test]
+    fn test_instancia_invalida_vira_aviso() {
+        let modelo = ModeloStep::ler(ARQUIVO).unwrap();
+
+        assert_eq!(modelo.avisos.len(), 1);
+        assert!(
+            modelo.avisos[0].contains("#4=IFCQUEBRADO"),
+            "{}",
+            modelo.avisos[0]
+        );
+    }
+
+    #[test]
+    fn test_arquivo_sem_dados() {
+        assert!(ModeloStep::ler("ISO-10303-21;\nHEADER;\nENDSEC;\n").is_err());
+    }
+}
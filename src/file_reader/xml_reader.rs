@@ -1,18 +1,20 @@
+use super::Entrada;
 use crate::models::empreendimento::Empreendimento;
-use serde_xml_rs::from_reader;
-use std::error::Error;
-use std::fs::File;
+use quick_xml::de::from_reader;
 use serde::Deserialize;
+use std::error::Error;
+use std::io::BufReader;
 
-pub fn read_xml(path: &str) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
-    let file = File::open(path)?;
-    let wrapper: EmpreendimentosWrapper = from_reader(file)?;
+pub fn read_xml<'a>(
+    entrada: impl Into<Entrada<'a>>,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let wrapper: EmpreendimentosWrapper = from_reader(BufReader::new(entrada.into().abrir()?))?;
     Ok(wrapper.empreendimentos)
 }
 
 #[derive(Debug, Deserialize)]
 struct EmpreendimentosWrapper {
-    #[serde(rename = "empreendimento")]
+    #[serde(default, rename = "empreendimento")]
     pub empreendimentos: Vec<Empreendimento>,
 }
 
@@ -42,14 +44,14 @@ mod tests {
         <area-de-lazer>150.0</area-de-lazer>
     </empreendimento>
 </empreendimentos>"#;
-        
+
         let temp_file = create_temp_xml(xml_content);
         let result = read_xml(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Alpha");
         assert_eq!(emp.cidade, "São Paulo");
@@ -73,14 +75,14 @@ mod tests {
         <area-da-torre>500.0</area-da-torre>
     </empreendimento>
 </empreendimentos>"#;
-        
+
         let temp_file = create_temp_xml(xml_content);
         let result = read_xml(temp_file.path().to_str().unwrap());
-        
+
         assert!(result.is_ok());
         let empreendimentos = result.unwrap();
         assert_eq!(empreendimentos.len(), 1);
-        
+
         let emp = &empreendimentos[0];
         assert_eq!(emp.construtora, "Delta");
         assert_eq!(emp.cidade, "Salvador");
@@ -90,4 +92,28 @@ mod tests {
         assert_eq!(emp.area_da_torre, 500.0);
         assert_eq!(emp.area_de_lazer, None);
     }
+
+    #[test]
+    fn test_read_xml_with_lote_em_json() {
+        let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<empreendimentos>
+    <empreendimento>
+        <construtora>Alpha</construtora>
+        <cidade>São Paulo</cidade>
+        <area-do-terreno>3000.0</area-do-terreno>
+        <numero-de-torres>1</numero-de-torres>
+        <altura-da-torre>20.0</altura-da-torre>
+        <area-da-torre>225.0</area-da-torre>
+        <torres>[{"projecao":[[5,5],[20,5],[20,20],[5,20]]}]</torres>
+        <lote>[[0,0],[60,0],[60,50],[0,50]]</lote>
+    </empreendimento>
+</empreendimentos>"#;
+
+        let temp_file = create_temp_xml(xml_content);
+        let empreendimentos = read_xml(temp_file.path().to_str().unwrap()).unwrap();
+
+        let emp = &empreendimentos[0];
+        assert_eq!(emp.torres.as_ref().unwrap()[0].projecao.area(), 225.0);
+        assert_eq!(emp.lote.as_ref().unwrap().area(), 3000.0);
+    }
 }
@@ -0,0 +1,237 @@
+use crate::models::geometria::Poligono;
+use crate::models::zoneamento::AreaDeZoneamento;
+use serde_json::Value;
+use shapefile::dbase::FieldValue;
+use shapefile::{PolygonRing, Shape};
+use std::error::Error;
+use std::fs;
+
+/// Lê uma camada de zoneamento em GeoJSON ou Shapefile (pela extensão), usando
+/// o atributo `campo_zona` de cada feição como nome da zona.
+pub fn read_zoneamento(
+    path: &str,
+    campo_zona: &str,
+) -> Result<Vec<AreaDeZoneamento>, Box<dyn Error>> {
+    let extensao = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match extensao.as_str() {
+        "shp" => read_zoneamento_shapefile(path, campo_zona),
+        _ => read_zoneamento_geojson(path, campo_zona),
+    }
+}
+
+pub fn read_zoneamento_geojson(
+    path: &str,
+    campo_zona: &str,
+) -> Result<Vec<AreaDeZoneamento>, Box<dyn Error>> {
+    let data = fs::read_to_string(path)?;
+    let camada: Value = serde_json::from_str(&data)?;
+    let features = camada
+        .get("features")
+        .and_then(Value::as_array)
+        .ok_or("Camada de zoneamento deve ser uma FeatureCollection")?;
+
+    let mut areas = Vec::new();
+    for (indice, feature) in features.iter().enumerate() {
+        let zona = match feature.pointer(&format!("/properties/{}", campo_zona)) {
+            Some(Value::String(zona)) => zona.clone(),
+            Some(Value::Number(zona)) => zona.to_string(),
+            _ => {
+                return Err(
+                    format!("Feição {} sem o atributo de zona '{}'", indice, campo_zona).into(),
+                );
+            }
+        };
+
+        let geometria = feature.get("geometry").unwrap_or(&Value::Null);
+        let poligonos: Vec<Vec<Poligono>> = match geometria.get("type").and_then(Value::as_str) {
+            Some("Polygon") => vec![serde_json::from_value(geometria["coordinates"].clone())?],
+            Some("MultiPolygon") => serde_json::from_value(geometria["coordinates"].clone())?,
+            outro => {
+                return Err(format!(
+                    "Feição {} com geometria {:?} não suportada; use Polygon ou MultiPolygon",
+                    indice, outro
+                )
+                .into());
+            }
+        };
+
+        for aneis in poligonos {
+            let mut aneis = aneis.into_iter();
+            if let Some(contorno) = aneis.next() {
+                areas.push(AreaDeZoneamento {
+                    zona: zona.clone(),
+                    contorno,
+                    furos: aneis.collect(),
+                });
+            }
+        }
+    }
+
+    Ok(areas)
+}
+
+pub fn read_zoneamento_shapefile(
+    path: &str,
+    campo_zona: &str,
+) -> Result<Vec<AreaDeZoneamento>, Box<dyn Error>> {
+    let mut reader = shapefile::Reader::from_path(path)?;
+    let mut areas = Vec::new();
+
+    for (indice, resultado) in reader.iter_shapes_and_records().enumerate() {
+        let (shape, record) = resultado?;
+        let zona = match record.get(campo_zona) {
+            Some(FieldValue::Character(Some(zona))) | Some(FieldValue::Memo(zona)) => {
+                zona.trim().to_string()
+            }
+            Some(FieldValue::Numeric(Some(zona))) => zona.to_string(),
+            Some(FieldValue::Integer(zona)) => zona.to_string(),
+            _ => {
+                return Err(format!(
+                    "Registro {} sem o atributo de zona '{}'",
+                    indice, campo_zona
+                )
+                .into());
+            }
+        };
+
+        let aneis = match shape {
+            Shape::Polygon(poligono) => poligono.into_inner(),
+            Shape::NullShape => continue,
+            outro => {
+                return Err(format!(
+                    "Registro {} com geometria {} não suportada; use Polygon",
+                    indice,
+                    outro.shapetype()
+                )
+                .into());
+            }
+        };
+
+        // No Shapefile, cada anel externo inicia um polígono e os internos são seus furos.
+        for anel in aneis {
+            let externo = matches!(anel, PolygonRing::Outer(_));
+            let contorno = Poligono(anel.into_inner().iter().map(|p| [p.x, p.y]).collect());
+            match areas.last_mut() {
+                Some(AreaDeZoneamento { furos, .. }) if !externo => furos.push(contorno),
+                _ => areas.push(AreaDeZoneamento {
+                    zona: zona.clone(),
+                    contorno,
+                    furos: Vec::new(),
+                }),
+            }
+        }
+    }
+
+    Ok(areas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shapefile::dbase::{FieldName, Record, TableWriterBuilder};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_read_zoneamento_geojson() {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": { "zona": "ZM" },
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [
+                            [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                            [[4, 4], [6, 4], [6, 6], [4, 6], [4, 4]]
+                        ]
+                    }
+                },
+                {
+                    "type": "Feature",
+                    "properties": { "zona": "ZEU" },
+                    "geometry": {
+                        "type": "MultiPolygon",
+                        "coordinates": [
+                            [[[20, 0], [30, 0], [30, 10], [20, 0]]],
+                            [[[40, 0], [50, 0], [50, 10], [40, 0]]]
+                        ]
+                    }
+                }
+            ]
+        }"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", geojson).unwrap();
+
+        let areas = read_zoneamento(temp_file.path().to_str().unwrap(), "zona").unwrap();
+
+        assert_eq!(areas.len(), 3);
+        assert_eq!(areas[0].zona, "ZM");
+        assert_eq!(areas[0].furos.len(), 1);
+        assert!(areas[0].contem([2.0, 2.0]));
+        assert!(
+            !areas[0].contem([5.0, 5.0]),
+            "Ponto no furo não pertence à zona"
+        );
+        assert_eq!(areas[2].zona, "ZEU");
+    }
+
+    #[test]
+    fn test_read_zoneamento_geojson_sem_atributo() {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"nome": "x"},
+             "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1]]]}}
+        ]}"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", geojson).unwrap();
+
+        let erro = read_zoneamento(temp_file.path().to_str().unwrap(), "zona").unwrap_err();
+        assert!(erro.to_string().contains("sem o atributo de zona 'zona'"));
+    }
+
+    #[test]
+    fn test_read_zoneamento_shapefile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zonas.shp");
+
+        let tabela =
+            TableWriterBuilder::new().add_character_field(FieldName::try_from("zona").unwrap(), 10);
+        let mut writer = shapefile::Writer::from_path(&path, tabela).unwrap();
+        let poligono = shapefile::Polygon::with_rings(vec![
+            PolygonRing::Outer(vec![
+                shapefile::Point::new(0.0, 0.0),
+                shapefile::Point::new(0.0, 10.0),
+                shapefile::Point::new(10.0, 10.0),
+                shapefile::Point::new(10.0, 0.0),
+                shapefile::Point::new(0.0, 0.0),
+            ]),
+            PolygonRing::Inner(vec![
+                shapefile::Point::new(4.0, 4.0),
+                shapefile::Point::new(6.0, 4.0),
+                shapefile::Point::new(6.0, 6.0),
+                shapefile::Point::new(4.0, 6.0),
+                shapefile::Point::new(4.0, 4.0),
+            ]),
+        ]);
+        let mut registro = Record::default();
+        registro.insert(
+            "zona".to_string(),
+            FieldValue::Character(Some("ZER".to_string())),
+        );
+        writer.write_shape_and_record(&poligono, &registro).unwrap();
+        drop(writer);
+
+        let areas = read_zoneamento(path.to_str().unwrap(), "zona").unwrap();
+
+        assert_eq!(areas.len(), 1);
+        assert_eq!(areas[0].zona, "ZER");
+        assert_eq!(areas[0].furos.len(), 1);
+        assert!(areas[0].contem([2.0, 2.0]));
+        assert!(!areas[0].contem([5.0, 5.0]));
+    }
+}
@@ -1,50 +1,345 @@
-mod business_logic;
-mod file_reader;
-mod file_generator;
-mod models;
-
-use business_logic::validar_empreendimentos;
-use clap::Parser;
+use arqgen::business_logic::indices::IndicesUrbanisticos;
+use arqgen::business_logic::restricoes_altura::MapaRestricoesAltura;
+use arqgen::business_logic::zoneamento::MapaZoneamento;
+use arqgen::business_logic::{
+    CamadasGeograficas, ValidationResult, validar_empreendimentos_com_camadas,
+    validar_record_batches, validar_record_batches_em_fluxo,
+};
+use arqgen::file_generator::{
+    EstiloNomes, FormatoSaida, OpcoesConversao, Saida, converter, write_geojson, write_kml,
+    write_xlsx_anotado,
+};
+use arqgen::file_reader::{
+    Aba, Compressao, Entrada, FileType, OpcoesCsv, OpcoesDxf, OpcoesPlanilha, detectar_tipo,
+    detectar_tipo_do_conteudo, read_arrow_em_lotes, read_csv_com_opcoes, read_dxf_com_opcoes,
+    read_file, read_ifc_com_avisos, read_ods_com_opcoes, read_osm_com_avisos,
+    read_parquet_em_lotes, read_xlsx_com_opcoes,
+};
+use arqgen::models::dialeto_csv::Codificacao;
+use arqgen::models::empreendimento::Empreendimento;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use colored::*;
-use file_reader::{FileType, read_file};
+use std::io::{self, Read, Write};
+
+/// Nome que indica a entrada ou a saída padrão no lugar de um arquivo.
+const PADRAO: &str = "-";
 
 #[derive(Parser)]
 #[command(name = "arqgen")]
 #[command(author = "Gabriel")]
 #[command(version = "1.0.0")]
-#[command(about = "Valida empreendimentos a partir de arquivos CSV ou Parquet", long_about = None)]
+#[command(
+    about = "Valida e converte empreendimentos em CSV, JSON, XML, Parquet, Arrow IPC, planilhas, DXF, IFC ou OpenStreetMap",
+    long_about = None
+)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    comando: Option<Comando>,
+
+    #[command(flatten)]
+    validar: Option<ArgsValidar>,
+}
+
+#[derive(Args)]
+struct ArgsValidar {
+    /// Arquivo a validar; `-` lê da entrada padrão
+    #[arg(value_name = "ARQUIVO", conflicts_with = "path")]
+    arquivo: Option<String>,
+
+    #[arg(short, long, required_unless_present = "arquivo")]
+    path: Option<String>,
+
+    /// Formato do arquivo; por padrão é detectado pelo conteúdo e pela extensão
     #[arg(short, long)]
-    path: String,
+    file_type: Option<FileType>,
+
+    /// Camada de zoneamento (GeoJSON ou Shapefile) para obter a zona pela localização
+    #[arg(long)]
+    zoneamento: Option<String>,
+
+    /// Atributo da camada de zoneamento com o nome da zona
+    #[arg(long, default_value = "zona")]
+    campo_zona: String,
+
+    /// Superfícies de restrição de altura (GeoJSON) de aeródromos e áreas protegidas
+    #[arg(long)]
+    restricoes_altura: Option<String>,
+
+    /// Camada do desenho DXF com o contorno dos lotes
+    #[arg(long, default_value = "LOTE")]
+    camada_lote: String,
+
+    /// Camada do desenho DXF com as projeções das torres
+    #[arg(long, default_value = "TORRES")]
+    camada_torres: String,
+
+    /// Delimitador do CSV; por padrão é detectado (`,`, `;`, tabulação ou `|`)
+    #[arg(long)]
+    delimitador: Option<char>,
+
+    /// Separador decimal do CSV (`.` ou `,`); por padrão é detectado
+    #[arg(long)]
+    decimal: Option<char>,
+
+    /// Codificação do CSV (`utf-8` ou `windows-1252`/`latin1`); por padrão é detectada
+    #[arg(long)]
+    codificacao: Option<Codificacao>,
+
+    /// Aba das planilhas `.xlsx`/`.ods`, pelo nome ou, se nenhuma aba tiver
+    /// esse nome, pela posição a partir de 0
+    #[arg(long, default_value = "0")]
+    aba: String,
+
+    /// Grava os resultados em um mapa: KML (`.kml`) ou GeoJSON (demais
+    /// extensões e `-`, a saída padrão, com o relatório na saída de erros)
+    #[arg(long)]
+    mapa: Option<String>,
+
+    /// Grava uma planilha `.xlsx` com os empreendimentos e as violações destacadas
+    #[arg(long)]
+    anotar: Option<String>,
+}
+
+impl ArgsValidar {
+    fn origem(&self) -> &str {
+        self.arquivo
+            .as_deref()
+            .or(self.path.as_deref())
+            .unwrap_or(PADRAO)
+    }
+}
+
+#[derive(Subcommand)]
+enum Comando {
+    /// Valida os empreendimentos de um arquivo ou da entrada padrão (`-`)
+    Validar(ArgsValidar),
+    /// Converte empreendimentos entre formatos
+    Converter(ArgsConverter),
+}
 
-    #[arg(short, long, default_value = "csv")]
-    file_type: String,
+#[derive(Args)]
+struct ArgsConverter {
+    /// Arquivo de entrada, em qualquer formato aceito na validação; `-` lê da entrada padrão
+    entrada: String,
+
+    /// Arquivo de saída: CSV, JSON, XML, Parquet ou Arrow IPC; `-` grava na saída padrão
+    saida: String,
+
+    /// Formato da entrada; por padrão é detectado pelo conteúdo e pela extensão
+    #[arg(long)]
+    de: Option<FileType>,
+
+    /// Formato da saída (csv, json, xml, parquet, arrow ou arrows); por padrão é inferido pela extensão
+    #[arg(long)]
+    para: Option<FormatoSaida>,
+
+    /// Campos opcionais mantidos, separados por vírgula; os demais ficam ausentes
+    #[arg(long, value_delimiter = ',')]
+    campos: Option<Vec<String>>,
+
+    /// Nomes das colunas do CSV e dos elementos do XML: snake ou kebab
+    #[arg(long, default_value = "snake")]
+    nomes: EstiloNomes,
+
+    /// Colunas da entrada renomeadas para campos, como `altura=altura-da-torre`,
+    /// separadas por vírgula
+    #[arg(long, value_delimiter = ',', value_parser = coluna_para_campo)]
+    mapa: Vec<(String, String)>,
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    let file_type = match std::path::Path::new(&cli.path)
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("")
-        .to_lowercase()
-        .as_str()
-    {
-        "csv" => FileType::Csv,
-        "json" => FileType::Json,
-        "parquet" => FileType::Parquet,
-        "xml" => FileType::Xml,
-        _ => {
-            eprintln!(
-                "{}",
-                "Tipo de arquivo inválido. Use csv, json, parquet ou xml.".red()
-            );
+    let args = match (cli.comando, cli.validar) {
+        (Some(Comando::Converter(converter)), _) => {
+            converter_arquivo(&converter);
+            return;
+        }
+        (Some(Comando::Validar(args)), _) | (None, Some(args)) => args,
+        (None, None) => Cli::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "informe o arquivo (`-` para a entrada padrão) ou um subcomando",
+            )
+            .exit(),
+    };
+
+    let path = args.origem();
+    // A entrada padrão é lida por inteiro: a detecção do formato e os
+    // formatos binários precisam do conteúdo completo.
+    let padrao = (path == PADRAO).then(|| {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes).unwrap_or_else(|e| {
+            eprintln!("{}: {}", "Erro ao ler a entrada padrão".red(), e);
+            std::process::exit(1);
+        });
+        bytes
+    });
+
+    let file_type = match (args.file_type, &padrao) {
+        (Some(file_type), _) => Ok(file_type),
+        (None, Some(bytes)) => detectar_tipo_do_conteudo(bytes),
+        (None, None) => detectar_tipo(path),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("{} (--file-type)", e.to_string().red());
+        std::process::exit(1);
+    });
+    let entrada = match &padrao {
+        Some(bytes) => Entrada::leitor(&bytes[..]),
+        None => Entrada::Caminho(path),
+    };
+
+    // Sem camadas nem saídas com os dados, arquivos Parquet e Arrow são
+    // validados pelo motor colunar, sem materializar cada empreendimento; a
+    // entrada padrão é sempre validada por linha.
+    let sem_camadas = args.zoneamento.is_none()
+        && args.restricoes_altura.is_none()
+        && args.mapa.is_none()
+        && args.anotar.is_none()
+        && padrao.is_none();
+    let colunares = match file_type {
+        FileType::Parquet if sem_camadas => {
+            Some(read_parquet_em_lotes(path).and_then(validar_record_batches_em_fluxo))
+        }
+        FileType::Arrow if sem_camadas => {
+            Some(read_arrow_em_lotes(path).and_then(|lotes| validar_record_batches(&lotes)))
+        }
+        _ => None,
+    };
+    let (empreendimentos, resultados) = if let Some(colunares) = colunares {
+        match colunares {
+            Ok(resultados) => (Vec::new(), resultados),
+            Err(e) => {
+                eprintln!("{}: {}", "Erro ao ler o arquivo".red(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        validar_por_linha(&args, file_type, entrada)
+    };
+
+    // Com o mapa na saída padrão, o relatório vai para a saída de erros e
+    // não se mistura ao GeoJSON.
+    let mapa_no_padrao = args.mapa.as_deref() == Some(PADRAO);
+    let relatado = if mapa_no_padrao {
+        exibir_resultados(&mut io::stderr(), &resultados)
+    } else {
+        exibir_resultados(&mut io::stdout(), &resultados)
+    };
+    match relatado {
+        Ok(()) => {}
+        // O leitor do pipeline (`head`, por exemplo) já encerrou.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => std::process::exit(0),
+        Err(e) => {
+            eprintln!("{}: {}", "Erro ao exibir os resultados".red(), e);
             std::process::exit(1);
         }
+    }
+
+    if let Some(mapa) = &args.mapa {
+        let gravado = if mapa_no_padrao {
+            write_geojson(Saida::escritor(io::stdout()), &empreendimentos, &resultados)
+        } else if Compressao::sem_extensao(&mapa.to_lowercase()).ends_with(".kml") {
+            write_kml(mapa, &empreendimentos, &resultados)
+        } else {
+            write_geojson(mapa, &empreendimentos, &resultados)
+        };
+        match gravado {
+            Ok(()) if mapa_no_padrao => {}
+            Ok(()) => println!("🗺️  Mapa gerado: {}", mapa),
+            Err(e) => {
+                eprintln!("{}: {}", "Erro ao gravar o mapa".red(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(planilha) = &args.anotar {
+        match write_xlsx_anotado(planilha, &empreendimentos, &resultados) {
+            Ok(()) => println!("📊 Planilha anotada gerada: {}", planilha),
+            Err(e) => {
+                eprintln!("{}: {}", "Erro ao gravar a planilha anotada".red(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn converter_arquivo(args: &ArgsConverter) {
+    let opcoes = OpcoesConversao {
+        de: args.de,
+        para: args.para,
+        campos: args.campos.clone(),
+        nomes: args.nomes,
+        mapa: args.mapa.iter().cloned().collect(),
+    };
+    let entrada = if args.entrada == PADRAO {
+        Entrada::leitor(io::stdin())
+    } else {
+        Entrada::Caminho(&args.entrada)
     };
+    let saida = if args.saida == PADRAO {
+        Saida::escritor(io::stdout())
+    } else {
+        Saida::Caminho(&args.saida)
+    };
+    // A mensagem vai para a saída de erros quando os dados vão para a padrão.
+    match converter(entrada, saida, &opcoes) {
+        Ok(convertidos) if args.saida == PADRAO => {
+            eprintln!("✅ {} empreendimento(s) convertido(s)", convertidos)
+        }
+        Ok(convertidos) => println!(
+            "✅ {} empreendimento(s) convertido(s): {}",
+            convertidos, args.saida
+        ),
+        Err(e) => {
+            eprintln!("{}: {}", "Erro ao converter o arquivo".red(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Par `coluna=campo` do `--mapa`.
+fn coluna_para_campo(texto: &str) -> Result<(String, String), String> {
+    match texto.split_once('=') {
+        Some((coluna, campo)) if !coluna.trim().is_empty() && !campo.trim().is_empty() => {
+            Ok((coluna.trim().to_string(), campo.trim().to_string()))
+        }
+        _ => Err(format!("esperado coluna=campo, encontrado '{}'", texto)),
+    }
+}
 
-    let empreendimentos = match read_file(file_type, &cli.path) {
+/// Lê os empreendimentos e valida um a um, consultando as camadas geográficas.
+fn validar_por_linha(
+    args: &ArgsValidar,
+    file_type: FileType,
+    entrada: Entrada,
+) -> (Vec<Empreendimento>, Vec<ValidationResult>) {
+    let lidos = match file_type {
+        FileType::Dxf => {
+            let opcoes = OpcoesDxf {
+                camada_lote: args.camada_lote.clone(),
+                camada_torres: args.camada_torres.clone(),
+            };
+            read_dxf_com_opcoes(entrada, &opcoes)
+        }
+        FileType::Ifc => read_ifc_com_avisos(entrada).map(|leitura| {
+            exibir_avisos(&leitura.avisos);
+            leitura.empreendimentos
+        }),
+        FileType::Osm => read_osm_com_avisos(entrada).map(|leitura| {
+            exibir_avisos(&leitura.avisos);
+            leitura.empreendimentos
+        }),
+        FileType::Csv => opcoes_csv(args).and_then(|opcoes| read_csv_com_opcoes(entrada, &opcoes)),
+        FileType::Xlsx => read_xlsx_com_opcoes(entrada, &opcoes_planilha(args)),
+        FileType::Ods => read_ods_com_opcoes(entrada, &opcoes_planilha(args)),
+        outro => read_file(outro, entrada),
+    };
+    let empreendimentos = match lidos {
         Ok(data) => data,
         Err(e) => {
             eprintln!("{}: {}", "Erro ao ler o arquivo".red(), e);
@@ -52,19 +347,108 @@ fn main() {
         }
     };
 
-    let resultados = validar_empreendimentos(&empreendimentos);
+    let zoneamento = args.zoneamento.as_ref().map(|path| {
+        MapaZoneamento::carregar(path, &args.campo_zona).unwrap_or_else(|e| {
+            eprintln!("{}: {}", "Erro ao ler o zoneamento".red(), e);
+            std::process::exit(1);
+        })
+    });
+    let restricoes_altura = args.restricoes_altura.as_ref().map(|path| {
+        MapaRestricoesAltura::carregar(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", "Erro ao ler as restrições de altura".red(), e);
+            std::process::exit(1);
+        })
+    });
+
+    let resultados = validar_empreendimentos_com_camadas(
+        &empreendimentos,
+        CamadasGeograficas {
+            zoneamento: zoneamento.as_ref(),
+            restricoes_altura: restricoes_altura.as_ref(),
+        },
+    );
+
+    (empreendimentos, resultados)
+}
 
-    println!("{}", "===== Resultados da Validação =====".bold().blue());
+fn exibir_resultados(saida: &mut dyn Write, resultados: &[ValidationResult]) -> io::Result<()> {
+    writeln!(
+        saida,
+        "{}",
+        "===== Resultados da Validação =====".bold().blue()
+    )?;
     for resultado in resultados {
-        println!("Empreendimento: {}", resultado.empreendimento.bold());
+        writeln!(saida, "Empreendimento: {}", resultado.empreendimento.bold())?;
+        if let Some(zona) = &resultado.zona {
+            writeln!(saida, "  {}", format!("Zona: {}", zona).dimmed())?;
+        }
+        if let Some(altura) = resultado.altura_permitida {
+            writeln!(
+                saida,
+                "  {}",
+                format!("Altura permitida: {:.1}m", altura).dimmed()
+            )?;
+        }
+        if resultado.indices.algum_calculado() {
+            writeln!(saida, "  {}", formatar_indices(&resultado.indices).dimmed())?;
+        }
         if resultado.regras_ok {
-            println!("  {}", "✅ Todas as regras foram atendidas!".green());
+            writeln!(saida, "  {}", "✅ Todas as regras foram atendidas!".green())?;
         } else {
-            println!("  {}", "❌ Regras violadas:".red());
-            for msg in resultado.mensagens {
-                println!("    - {}", msg.yellow());
+            writeln!(saida, "  {}", "❌ Regras violadas:".red())?;
+            for msg in &resultado.mensagens {
+                writeln!(saida, "    - {}", msg.yellow())?;
             }
         }
-        println!("{}", "-".repeat(40));
+        writeln!(saida, "{}", "-".repeat(40))?;
+    }
+    Ok(())
+}
+
+fn opcoes_csv(args: &ArgsValidar) -> Result<OpcoesCsv, Box<dyn std::error::Error>> {
+    let delimitador = match args.delimitador {
+        Some(c) if c.is_ascii() => Some(c as u8),
+        Some(c) => {
+            return Err(format!("Delimitador '{}' inválido: use um caractere ASCII", c).into());
+        }
+        None => None,
+    };
+    let decimal = match args.decimal {
+        Some(c) if c == '.' || c == ',' => Some(c),
+        Some(c) => {
+            return Err(format!("Separador decimal '{}' inválido: use '.' ou ','", c).into());
+        }
+        None => None,
+    };
+    Ok(OpcoesCsv {
+        delimitador,
+        decimal,
+        codificacao: args.codificacao,
+    })
+}
+
+fn opcoes_planilha(args: &ArgsValidar) -> OpcoesPlanilha {
+    OpcoesPlanilha {
+        aba: Aba::from(args.aba.as_str()),
+    }
+}
+
+fn formatar_indices(indices: &IndicesUrbanisticos) -> String {
+    let formatar = |valor: Option<f64>| {
+        valor
+            .map(|v| format!("{:.2}", v))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    format!(
+        "CA: {} | TO: {} | TP: {}",
+        formatar(indices.coeficiente_de_aproveitamento),
+        formatar(indices.taxa_de_ocupacao),
+        formatar(indices.taxa_de_permeabilidade)
+    )
+}
+
+fn exibir_avisos(avisos: &[String]) {
+    for aviso in avisos {
+        eprintln!("{} {}", "Aviso:".yellow(), aviso);
     }
 }
@@ -0,0 +1,419 @@
+use crate::models::empreendimento::Empreendimento;
+use crate::models::field_converter::normalize_field_name;
+use crate::models::json_aninhado;
+use crate::models::uso::Uso;
+use arrow::array::{
+    Array, ArrayRef, AsArray, Float64Array, Int32Array, Scalar, StringArray, new_null_array,
+};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, is_null};
+use arrow::datatypes::{DataType, Field, Float64Type, Int32Type, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+/// Chave dos metadados de um campo do [`schema`] com o valor que
+/// [`normalizar`] usa quando a coluna falta ou tem nulos.
+pub const PADRAO: &str = "padrao";
+
+/// Esquema colunar dos empreendimentos, com nomes em snake_case.
+///
+/// Campos opcionais são colunas anuláveis; contagens são `Int32` para manter
+/// compatibilidade com Spark e DuckDB, e `torres` e `lote` são texto JSON.
+/// O `uso` ausente vale o padrão declarado em [`PADRAO`], como nos demais leitores.
+pub fn schema() -> SchemaRef {
+    let campo = |nome: &str, tipo: DataType, anulavel: bool| Field::new(nome, tipo, anulavel);
+    let com_padrao = |campo: Field, padrao: &str| {
+        campo.with_metadata(HashMap::from([(PADRAO.to_string(), padrao.to_string())]))
+    };
+    Arc::new(Schema::new(vec![
+        campo("construtora", DataType::Utf8, false),
+        campo("cidade", DataType::Utf8, false),
+        campo("area_do_terreno", DataType::Float64, false),
+        campo("numero_de_torres", DataType::Int32, false),
+        campo("altura_da_torre", DataType::Float64, false),
+        campo("area_da_torre", DataType::Float64, false),
+        campo("area_de_lazer", DataType::Float64, true),
+        campo("area_construida", DataType::Float64, true),
+        campo("area_permeavel", DataType::Float64, true),
+        campo("area_de_projecao", DataType::Float64, true),
+        com_padrao(
+            campo("uso", DataType::Utf8, true),
+            Uso::default().como_str(),
+        ),
+        campo("zona", DataType::Utf8, true),
+        campo("latitude", DataType::Float64, true),
+        campo("longitude", DataType::Float64, true),
+        campo("cota_do_terreno", DataType::Float64, true),
+        campo("numero_de_unidades", DataType::Int32, true),
+        campo("unidades_por_torre", DataType::Int32, true),
+        campo("vagas_de_garagem", DataType::Int32, true),
+        campo("vagas_acessiveis", DataType::Int32, true),
+        campo("vagas_de_motos", DataType::Int32, true),
+        campo("vagas_de_bicicletas", DataType::Int32, true),
+        campo("torres", DataType::Utf8, true),
+        campo("lote", DataType::Utf8, true),
+    ]))
+}
+
+/// Converte os empreendimentos em um `RecordBatch` com o [`schema`].
+pub fn para_record_batch(empreendimentos: &[Empreendimento]) -> Result<RecordBatch, ArrowError> {
+    let texto = |f: fn(&Empreendimento) -> Option<String>| -> ArrayRef {
+        Arc::new(StringArray::from(
+            empreendimentos.iter().map(f).collect::<Vec<_>>(),
+        ))
+    };
+    let real = |f: fn(&Empreendimento) -> Option<f64>| -> ArrayRef {
+        Arc::new(Float64Array::from(
+            empreendimentos.iter().map(f).collect::<Vec<_>>(),
+        ))
+    };
+    let inteiro = |nome: &str, f: fn(&Empreendimento) -> Option<u32>| {
+        empreendimentos
+            .iter()
+            .map(|e| {
+                f(e).map(|v| {
+                    i32::try_from(v).map_err(|_| {
+                        ArrowError::InvalidArgumentError(format!(
+                            "Valor {} de '{}' excede o limite de Int32",
+                            v, nome
+                        ))
+                    })
+                })
+                .transpose()
+            })
+            .collect::<Result<Int32Array, _>>()
+            .map(|array| Arc::new(array) as ArrayRef)
+    };
+
+    RecordBatch::try_new(
+        schema(),
+        vec![
+            texto(|e| Some(e.construtora.clone())),
+            texto(|e| Some(e.cidade.clone())),
+            real(|e| Some(e.area_do_terreno)),
+            inteiro("numero_de_torres", |e| Some(e.numero_de_torres))?,
+            real(|e| Some(e.altura_da_torre)),
+            real(|e| Some(e.area_da_torre)),
+            real(|e| e.area_de_lazer),
+            real(|e| e.area_construida),
+            real(|e| e.area_permeavel),
+            real(|e| e.area_de_projecao),
+            texto(|e| Some(e.uso.to_string())),
+            texto(|e| e.zona.clone()),
+            real(|e| e.latitude),
+            real(|e| e.longitude),
+            real(|e| e.cota_do_terreno),
+            inteiro("numero_de_unidades", |e| e.numero_de_unidades)?,
+            inteiro("unidades_por_torre", |e| e.unidades_por_torre)?,
+            inteiro("vagas_de_garagem", |e| e.vagas_de_garagem)?,
+            inteiro("vagas_acessiveis", |e| e.vagas_acessiveis)?,
+            inteiro("vagas_de_motos", |e| e.vagas_de_motos)?,
+            inteiro("vagas_de_bicicletas", |e| e.vagas_de_bicicletas)?,
+            texto(|e| json_aninhado::para_texto(&e.torres)),
+            texto(|e| json_aninhado::para_texto(&e.lote)),
+        ],
+    )
+}
+
+/// Converte um `RecordBatch` qualquer para o [`schema`]: as colunas são
+/// localizadas pelo nome normalizado, tipos compatíveis são convertidos
+/// (inteiros, `FLOAT`, `DECIMAL` e números em texto) e colunas opcionais
+/// ausentes viram nulas ou o [`PADRAO`] do campo. `primeira_linha` numera as linhas nas mensagens de erro.
+pub fn normalizar(
+    batch: &RecordBatch,
+    primeira_linha: usize,
+) -> Result<RecordBatch, Box<dyn Error>> {
+    let origem: HashMap<&str, usize> = batch
+        .schema_ref()
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, campo)| (normalize_field_name(campo.name()), i))
+        .collect();
+
+    let alvo = schema();
+    let mut colunas = Vec::with_capacity(alvo.fields().len());
+    for campo in alvo.fields() {
+        let nome = campo.name().as_str();
+        let coluna = match origem.get(nome) {
+            Some(&i) => converter_coluna(batch.column(i), campo, primeira_linha)?,
+            None if campo.is_nullable() => new_null_array(campo.data_type(), batch.num_rows()),
+            None => return Err(format!("Campo '{}' não encontrado", nome).into()),
+        };
+        let coluna = preencher_com_padrao(coluna, campo)?;
+        if !campo.is_nullable()
+            && let Some(linha) = (0..coluna.len()).find(|&i| coluna.is_null(i))
+        {
+            return Err(format!(
+                "Linha {}, coluna '{}': valor nulo",
+                primeira_linha + linha,
+                nome
+            )
+            .into());
+        }
+        colunas.push(coluna);
+    }
+    Ok(RecordBatch::try_new(alvo, colunas)?)
+}
+
+/// Substitui os nulos da coluna pelo [`PADRAO`] declarado no campo, se houver.
+fn preencher_com_padrao(coluna: ArrayRef, campo: &Field) -> Result<ArrayRef, ArrowError> {
+    let Some(padrao) = campo.metadata().get(PADRAO) else {
+        return Ok(coluna);
+    };
+    if coluna.null_count() == 0 {
+        return Ok(coluna);
+    }
+    let padrao = cast(&StringArray::from(vec![padrao.as_str()]), campo.data_type())?;
+    zip(&is_null(&coluna)?, &Scalar::new(padrao), &coluna)
+}
+
+fn converter_coluna(
+    coluna: &ArrayRef,
+    campo: &Field,
+    primeira_linha: usize,
+) -> Result<ArrayRef, Box<dyn Error>> {
+    let nome = campo.name();
+    let erro = |linha: usize, mensagem: String| -> Box<dyn Error> {
+        format!(
+            "Linha {}, coluna '{}': {}",
+            primeira_linha + linha,
+            nome,
+            mensagem
+        )
+        .into()
+    };
+    let eh_texto = matches!(
+        coluna.data_type(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    );
+
+    if coluna.data_type() == campo.data_type() {
+        return Ok(coluna.clone());
+    }
+    match campo.data_type() {
+        DataType::Utf8 if eh_texto || matches!(coluna.data_type(), DataType::Null) => {
+            Ok(cast(coluna, &DataType::Utf8)?)
+        }
+        DataType::Utf8 => {
+            let linha = (0..coluna.len()).find(|&i| coluna.is_valid(i)).unwrap_or(0);
+            Err(erro(
+                linha,
+                format!("esperado texto, encontrado {}", coluna.data_type()),
+            ))
+        }
+        DataType::Float64 => Ok(Arc::new(reais(coluna, eh_texto, &erro)?)),
+        DataType::Int32 => {
+            let reais = reais(coluna, eh_texto, &erro)?;
+            let inteiros = reais
+                .iter()
+                .enumerate()
+                .map(|(i, valor)| match valor {
+                    Some(v) if v.fract() == 0.0 && (0.0..=i32::MAX as f64).contains(&v) => {
+                        Ok(Some(v as i32))
+                    }
+                    Some(v) => Err(erro(
+                        i,
+                        format!("esperado inteiro não negativo, encontrado {}", v),
+                    )),
+                    None => Ok(None),
+                })
+                .collect::<Result<Int32Array, _>>()?;
+            Ok(Arc::new(inteiros))
+        }
+        outro => Err(format!("Tipo {} não suportado na coluna '{}'", outro, nome).into()),
+    }
+}
+
+/// Coluna numérica como `Float64`; texto vazio vira nulo e aceita vírgula decimal.
+fn reais(
+    coluna: &ArrayRef,
+    eh_texto: bool,
+    erro: &dyn Fn(usize, String) -> Box<dyn Error>,
+) -> Result<Float64Array, Box<dyn Error>> {
+    if eh_texto {
+        let textos = cast(coluna, &DataType::Utf8)?;
+        return textos
+            .as_string::<i32>()
+            .iter()
+            .enumerate()
+            .map(|(i, texto)| match texto.map(str::trim) {
+                None | Some("") => Ok(None),
+                Some(texto) => texto
+                    .replace(',', ".")
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| erro(i, format!("esperado número, encontrado \"{}\"", texto))),
+            })
+            .collect();
+    }
+    if coluna.data_type().is_numeric() || matches!(coluna.data_type(), DataType::Null) {
+        return Ok(cast(coluna, &DataType::Float64)?
+            .as_primitive::<Float64Type>()
+            .clone());
+    }
+    let linha = (0..coluna.len()).find(|&i| coluna.is_valid(i)).unwrap_or(0);
+    Err(erro(
+        linha,
+        format!("esperado número, encontrado {}", coluna.data_type()),
+    ))
+}
+
+/// Converte um `RecordBatch` (normalizado com [`normalizar`]) em empreendimentos.
+pub fn de_record_batch(
+    batch: &RecordBatch,
+    primeira_linha: usize,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let batch = normalizar(batch, primeira_linha)?;
+    empreendimentos_de(&batch, |i| primeira_linha + i)
+}
+
+/// Converte um `RecordBatch` já normalizado; `numero_da_linha` dá o número de
+/// cada linha nas mensagens de erro.
+pub(crate) fn empreendimentos_de(
+    batch: &RecordBatch,
+    numero_da_linha: impl Fn(usize) -> usize,
+) -> Result<Vec<Empreendimento>, Box<dyn Error>> {
+    let coluna = |nome: &str| batch.column_by_name(nome).expect("coluna do esquema");
+    let texto = |nome: &str| coluna(nome).as_string::<i32>();
+    let real = |nome: &str| coluna(nome).as_primitive::<Float64Type>();
+    let inteiro = |nome: &str| coluna(nome).as_primitive::<Int32Type>();
+
+    let opcional_texto = |nome: &str, i: usize| {
+        let array = texto(nome);
+        array.is_valid(i).then(|| array.value(i).to_string())
+    };
+    let opcional_real = |nome: &str, i: usize| {
+        let array = real(nome);
+        array.is_valid(i).then(|| array.value(i))
+    };
+    let opcional_inteiro = |nome: &str, i: usize| {
+        let array = inteiro(nome);
+        array.is_valid(i).then(|| array.value(i) as u32)
+    };
+    let erro = |i: usize, nome: &str, mensagem: String| -> Box<dyn Error> {
+        format!(
+            "Linha {}, coluna '{}': {}",
+            numero_da_linha(i),
+            nome,
+            mensagem
+        )
+        .into()
+    };
+
+    (0..batch.num_rows())
+        .map(|i| {
+            Ok(Empreendimento {
+                construtora: texto("construtora").value(i).to_string(),
+                cidade: texto("cidade").value(i).to_string(),
+                area_do_terreno: real("area_do_terreno").value(i),
+                numero_de_torres: inteiro("numero_de_torres").value(i) as u32,
+                altura_da_torre: real("altura_da_torre").value(i),
+                area_da_torre: real("area_da_torre").value(i),
+                area_de_lazer: opcional_real("area_de_lazer", i),
+                area_construida: opcional_real("area_construida", i),
+                area_permeavel: opcional_real("area_permeavel", i),
+                area_de_projecao: opcional_real("area_de_projecao", i),
+                uso: texto("uso")
+                    .value(i)
+                    .parse()
+                    .map_err(|e| erro(i, "uso", e))?,
+                zona: opcional_texto("zona", i),
+                latitude: opcional_real("latitude", i),
+                longitude: opcional_real("longitude", i),
+                cota_do_terreno: opcional_real("cota_do_terreno", i),
+                numero_de_unidades: opcional_inteiro("numero_de_unidades", i),
+                unidades_por_torre: opcional_inteiro("unidades_por_torre", i),
+                vagas_de_garagem: opcional_inteiro("vagas_de_garagem", i),
+                vagas_acessiveis: opcional_inteiro("vagas_acessiveis", i),
+                vagas_de_motos: opcional_inteiro("vagas_de_motos", i),
+                vagas_de_bicicletas: opcional_inteiro("vagas_de_bicicletas", i),
+                torres: json_aninhado::de_texto(opcional_texto("torres", i))
+                    .map_err(|e| erro(i, "torres", e.to_string()))?,
+                lote: json_aninhado::de_texto(opcional_texto("lote", i))
+                    .map_err(|e| erro(i, "lote", e.to_string()))?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+
+    #[test]
+    fn test_para_record_batch() {
+        let empreendimentos = vec![
+            Empreendimento {
+                construtora: "Alfa".to_string(),
+                cidade: "Curitiba".to_string(),
+                area_do_terreno: 1000.0,
+                numero_de_torres: 2,
+                area_de_lazer: Some(150.0),
+                vagas_de_garagem: Some(40),
+                ..Default::default()
+            },
+            Empreendimento {
+                construtora: "Beta".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let batch = para_record_batch(&empreendimentos).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema(), schema());
+
+        let lazer = batch.column_by_name("area_de_lazer").unwrap();
+        assert!(lazer.is_valid(0));
+        assert!(lazer.is_null(1), "area_de_lazer ausente deve ser nula");
+        let torres = batch
+            .column_by_name("numero_de_torres")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(torres.value(0), 2);
+        let uso = batch
+            .column_by_name("uso")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(uso.value(1), "residencial");
+    }
+
+    #[test]
+    fn test_contagem_acima_de_int32_e_erro() {
+        let empreendimentos = vec![Empreendimento {
+            numero_de_unidades: Some(u32::MAX),
+            ..Default::default()
+        }];
+        let erro = para_record_batch(&empreendimentos).unwrap_err();
+        assert!(erro.to_string().contains("numero_de_unidades"));
+    }
+
+    #[test]
+    fn test_normalizar_preenche_o_uso_padrao() {
+        let batch =
+            para_record_batch(&[Empreendimento::default(), Empreendimento::default()]).unwrap();
+        let uso = batch.schema().index_of("uso").unwrap();
+        let sem_uso = batch.project(&[0, 1, 2, 3, 4, 5]).unwrap();
+        let mut colunas = batch.columns().to_vec();
+        colunas[uso] = Arc::new(StringArray::from(vec![None, Some("comercial")]));
+        let uso_nulo = RecordBatch::try_new(batch.schema(), colunas).unwrap();
+
+        for (lote, esperado) in [
+            (sem_uso, ["residencial", "residencial"]),
+            (uso_nulo, ["residencial", "comercial"]),
+        ] {
+            let normalizado = normalizar(&lote, 1).unwrap();
+            let usos = normalizado.column(uso).as_string::<i32>();
+            assert_eq!(usos.null_count(), 0);
+            assert_eq!(usos.iter().flatten().collect::<Vec<_>>(), esperado);
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use encoding_rs::WINDOWS_1252;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const BOM_UTF8: &[u8; 3] = b"\xEF\xBB\xBF";
+/// Delimitadores reconhecidos na detecção, em ordem de preferência nos empates.
+const DELIMITADORES: [u8; 4] = [b',', b';', b'\t', b'|'];
+/// Linhas examinadas em busca do separador decimal.
+const LINHAS_AMOSTRADAS: usize = 200;
+
+/// Codificação do arquivo CSV. Latin-1 (ISO-8859-1) é lido como
+/// Windows-1252, que o contém.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codificacao {
+    #[default]
+    Utf8,
+    Windows1252,
+}
+
+impl Codificacao {
+    /// UTF-8 quando os bytes são UTF-8 válido; senão, Windows-1252.
+    pub fn detectar(bytes: &[u8]) -> Self {
+        if std::str::from_utf8(bytes).is_ok() {
+            Codificacao::Utf8
+        } else {
+            Codificacao::Windows1252
+        }
+    }
+}
+
+impl FromStr for Codificacao {
+    type Err = String;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        match texto.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(Codificacao::Utf8),
+            "windows-1252" | "cp1252" | "latin1" | "latin-1" | "iso-8859-1" => {
+                Ok(Codificacao::Windows1252)
+            }
+            _ => Err(format!(
+                "Codificação '{}' desconhecida; use utf-8 ou windows-1252 (latin1)",
+                texto
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Codificacao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Codificacao::Utf8 => "utf-8",
+            Codificacao::Windows1252 => "windows-1252",
+        })
+    }
+}
+
+/// Formato de um arquivo CSV: delimitador, separadores numéricos e codificação.
+///
+/// O padrão é o CSV internacional (`,` e ponto decimal, em UTF-8);
+/// [`DialetoCsv::excel_brasileiro`] é o que o Excel em pt-BR abre e exporta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialetoCsv {
+    pub delimitador: u8,
+    pub decimal: char,
+    /// Separador de milhar aceito na leitura; na gravação os números não são agrupados.
+    pub milhar: Option<char>,
+    pub codificacao: Codificacao,
+    /// Marca de ordem de bytes UTF-8 no início do arquivo.
+    pub bom: bool,
+}
+
+impl Default for DialetoCsv {
+    fn default() -> Self {
+        DialetoCsv {
+            delimitador: b',',
+            decimal: '.',
+            milhar: None,
+            codificacao: Codificacao::Utf8,
+            bom: false,
+        }
+    }
+}
+
+impl DialetoCsv {
+    /// `;` como delimitador e vírgula decimal (`1.200,50`), em UTF-8 com BOM
+    /// para que o Excel reconheça os acentos.
+    pub fn excel_brasileiro() -> Self {
+        DialetoCsv {
+            delimitador: b';',
+            decimal: ',',
+            milhar: Some('.'),
+            codificacao: Codificacao::Utf8,
+            bom: true,
+        }
+    }
+
+    /// Detecta o dialeto pelo conteúdo: BOM e codificação pelos bytes, o
+    /// delimitador pelo cabeçalho e o separador decimal pelos valores das
+    /// colunas cujo nome satisfaz `colunas_numericas`.
+    pub fn detectar(bytes: &[u8], colunas_numericas: impl Fn(&str) -> bool) -> Self {
+        let bom = bytes.starts_with(BOM_UTF8);
+        let codificacao = Codificacao::detectar(bytes);
+        let texto = decodificar(bytes, codificacao);
+        let delimitador = detectar_delimitador(&texto);
+        let decimal = detectar_decimal(&texto, delimitador, colunas_numericas);
+
+        DialetoCsv {
+            delimitador,
+            decimal,
+            milhar: milhar_para(decimal, delimitador),
+            codificacao,
+            bom,
+        }
+    }
+
+    /// Texto do arquivo, sem o BOM.
+    pub fn decodificar(&self, bytes: &[u8]) -> String {
+        decodificar(bytes, self.codificacao)
+    }
+
+    /// Bytes do texto na codificação do dialeto, com o BOM quando pedido.
+    pub fn codificar(&self, texto: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut bytes = Vec::with_capacity(texto.len() + BOM_UTF8.len());
+        match self.codificacao {
+            Codificacao::Utf8 => {
+                if self.bom {
+                    bytes.extend_from_slice(BOM_UTF8);
+                }
+                bytes.extend_from_slice(texto.as_bytes());
+            }
+            Codificacao::Windows1252 => {
+                let (codificado, _, com_erros) = WINDOWS_1252.encode(texto);
+                if com_erros {
+                    let caractere = texto
+                        .chars()
+                        .find(|c| WINDOWS_1252.encode(&c.to_string()).2)
+                        .unwrap_or_default();
+                    return Err(format!(
+                        "O caractere '{}' não pode ser gravado em windows-1252",
+                        caractere
+                    )
+                    .into());
+                }
+                bytes.extend_from_slice(&codificado);
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Converte um número no dialeto (`1.200,50`) para o formato com ponto
+    /// decimal (`1200.50`). Valores vazios voltam como estão; `None` quando o
+    /// valor não é um número com os separadores do dialeto, como `12.50` com
+    /// vírgula decimal, que seria lido como outro número.
+    pub fn normalizar_numero<'a>(&self, valor: &'a str) -> Option<Cow<'a, str>> {
+        let limpo = valor.trim();
+        if limpo.is_empty() || (self.decimal == '.' && self.milhar.is_none()) {
+            return Some(valor.into());
+        }
+        let (inteira, fracao) = match limpo.split_once(self.decimal) {
+            Some((inteira, fracao)) => (inteira, Some(fracao)),
+            None => (limpo, None),
+        };
+        let sem_sinal = inteira.strip_prefix('-').unwrap_or(inteira);
+        let grupos_validos = match self.milhar {
+            Some(milhar) if sem_sinal.contains(milhar) => {
+                let mut grupos = sem_sinal.split(milhar);
+                let primeiro = grupos.next().unwrap_or_default();
+                (1..=3).contains(&primeiro.len()) && grupos.all(|grupo| grupo.len() == 3)
+            }
+            _ => true,
+        };
+        let so_digitos = |parte: &str| parte.chars().all(|c| c.is_ascii_digit());
+        let numero = grupos_validos
+            && sem_sinal
+                .chars()
+                .all(|c| c.is_ascii_digit() || Some(c) == self.milhar)
+            && fracao.is_none_or(so_digitos);
+        if !numero {
+            return None;
+        }
+
+        let mut normalizado: String = inteira
+            .chars()
+            .filter(|c| Some(*c) != self.milhar)
+            .collect();
+        if let Some(fracao) = fracao {
+            normalizado.push('.');
+            normalizado.push_str(fracao);
+        }
+        Some(normalizado.into())
+    }
+
+    /// Número com o separador decimal do dialeto, sem separador de milhar.
+    pub fn formatar_numero(&self, valor: f64) -> String {
+        let texto = valor.to_string();
+        if self.decimal == '.' {
+            texto
+        } else {
+            texto.replace('.', &self.decimal.to_string())
+        }
+    }
+}
+
+fn decodificar(bytes: &[u8], codificacao: Codificacao) -> String {
+    let bytes = bytes.strip_prefix(BOM_UTF8).unwrap_or(bytes);
+    match codificacao {
+        Codificacao::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        Codificacao::Windows1252 => WINDOWS_1252
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned(),
+    }
+}
+
+/// O separador de milhar é o outro entre `.` e `,`, a menos que seja o delimitador.
+pub(crate) fn milhar_para(decimal: char, delimitador: u8) -> Option<char> {
+    let milhar = if decimal == ',' { '.' } else { ',' };
+    (milhar as u32 != delimitador as u32).then_some(milhar)
+}
+
+/// O delimitador mais frequente fora de aspas na primeira linha não vazia.
+fn detectar_delimitador(texto: &str) -> u8 {
+    let cabecalho = texto
+        .lines()
+        .find(|linha| !linha.trim().is_empty())
+        .unwrap_or_default();
+    let mut contagens = [0usize; DELIMITADORES.len()];
+    let mut entre_aspas = false;
+    for byte in cabecalho.bytes() {
+        if byte == b'"' {
+            entre_aspas = !entre_aspas;
+        } else if !entre_aspas && let Some(i) = DELIMITADORES.iter().position(|d| *d == byte) {
+            contagens[i] += 1;
+        }
+    }
+    let (indice, maior) =
+        contagens.iter().enumerate().fold(
+            (0, 0),
+            |melhor, (i, &n)| if n > melhor.1 { (i, n) } else { melhor },
+        );
+    if maior == 0 {
+        b','
+    } else {
+        DELIMITADORES[indice]
+    }
+}
+
+/// Decide o separador decimal pelos números das colunas numéricas: o último
+/// separador de `1.200,50` é o decimal, e `25,5` ou `25.5` indicam o próprio
+/// separador. Valores como `1.200` são ambíguos e não contam; sem indícios,
+/// `;` sugere vírgula decimal.
+fn detectar_decimal(
+    texto: &str,
+    delimitador: u8,
+    colunas_numericas: impl Fn(&str) -> bool,
+) -> char {
+    let mut leitor = csv::ReaderBuilder::new()
+        .delimiter(delimitador)
+        .flexible(true)
+        .from_reader(texto.as_bytes());
+    let numericas: Vec<bool> = match leitor.headers() {
+        Ok(cabecalho) => cabecalho
+            .iter()
+            .map(|c| colunas_numericas(c.trim()))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let (mut virgula, mut ponto) = (0, 0);
+    for registro in leitor.records().take(LINHAS_AMOSTRADAS).flatten() {
+        for (valor, _) in registro.iter().zip(&numericas).filter(|(_, n)| **n) {
+            match indicio_decimal(valor.trim()) {
+                Some(',') => virgula += 1,
+                Some(_) => ponto += 1,
+                None => {}
+            }
+        }
+    }
+
+    match virgula.cmp(&ponto) {
+        std::cmp::Ordering::Greater => ',',
+        std::cmp::Ordering::Less => '.',
+        std::cmp::Ordering::Equal if delimitador == b';' => ',',
+        std::cmp::Ordering::Equal => '.',
+    }
+}
+
+fn indicio_decimal(valor: &str) -> Option<char> {
+    let ultimo = valor.rfind(['.', ','])?;
+    let separador = valor[ultimo..].chars().next()?;
+    let outro = if separador == ',' { '.' } else { ',' };
+    if valor.contains(outro) {
+        return Some(separador);
+    }
+    // `1.200` e `12,000,000` podem ser só agrupamento de milhar.
+    let grupos: Vec<&str> = valor.trim_start_matches('-').split(separador).collect();
+    let parece_milhar =
+        (1..=3).contains(&grupos[0].len()) && grupos[1..].iter().all(|g| g.len() == 3);
+    (!parece_milhar).then_some(separador)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numerica(coluna: &str) -> bool {
+        coluna.starts_with("area")
+    }
+
+    #[test]
+    fn test_detectar_dialeto_do_excel_brasileiro() {
+        let mut bytes = BOM_UTF8.to_vec();
+        bytes.extend_from_slice(
+            "construtora;cidade;area_do_terreno\nAlfa;São Paulo;1.200,50\nBeta;Curitiba;800\n"
+                .as_bytes(),
+        );
+
+        let dialeto = DialetoCsv::detectar(&bytes, numerica);
+
+        assert_eq!(dialeto, DialetoCsv::excel_brasileiro());
+        assert!(dialeto.decodificar(&bytes).starts_with("construtora;"));
+    }
+
+    #[test]
+    fn test_detectar_windows_1252_e_ponto_decimal() {
+        let (bytes, _, _) = WINDOWS_1252
+            .encode("construtora;cidade;area\nAlfa;São Paulo;25.5\nBeta;Jundiaí;1.200\n");
+
+        let dialeto = DialetoCsv::detectar(&bytes, numerica);
+
+        assert_eq!(dialeto.codificacao, Codificacao::Windows1252);
+        assert_eq!(dialeto.delimitador, b';');
+        assert_eq!(dialeto.decimal, '.');
+        assert_eq!(dialeto.milhar, Some(','));
+        assert!(!dialeto.bom);
+        assert!(dialeto.decodificar(&bytes).contains("Jundiaí"));
+    }
+
+    #[test]
+    fn test_detectar_csv_internacional() {
+        let texto = "construtora,\"cidade; estado\",area\nAlfa,\"Curitiba; PR\",1000.0\n";
+        assert_eq!(
+            DialetoCsv::detectar(texto.as_bytes(), numerica),
+            DialetoCsv::default()
+        );
+    }
+
+    #[test]
+    fn test_normalizar_numero() {
+        let brasileiro = DialetoCsv::excel_brasileiro();
+        let normalizar = |valor| brasileiro.normalizar_numero(valor).map(Cow::into_owned);
+        assert_eq!(normalizar("1.200,50").as_deref(), Some("1200.50"));
+        assert_eq!(normalizar(" -25,5 ").as_deref(), Some("-25.5"));
+        assert_eq!(normalizar("1.234.567").as_deref(), Some("1234567"));
+        assert_eq!(normalizar("800").as_deref(), Some("800"));
+        assert_eq!(normalizar("").as_deref(), Some(""));
+        assert_eq!(normalizar("12.50"), None, "Agrupamento inválido");
+        assert_eq!(normalizar("mil"), None);
+
+        let internacional = DialetoCsv::default();
+        assert_eq!(
+            internacional.normalizar_numero("1200.5").as_deref(),
+            Some("1200.5")
+        );
+    }
+
+    #[test]
+    fn test_formatar_e_codificar() {
+        let dialeto = DialetoCsv {
+            codificacao: Codificacao::Windows1252,
+            ..DialetoCsv::excel_brasileiro()
+        };
+        assert_eq!(dialeto.formatar_numero(1200.5), "1200,5");
+        assert_eq!(dialeto.formatar_numero(30.0), "30");
+        assert_eq!(dialeto.codificar("São").unwrap(), b"S\xE3o");
+        assert!(dialeto.codificar("Torre 🏢").is_err());
+
+        let com_bom = DialetoCsv::excel_brasileiro().codificar("a").unwrap();
+        assert_eq!(com_bom, b"\xEF\xBB\xBFa");
+    }
+
+    #[test]
+    fn test_codificacao_pelo_nome() {
+        assert_eq!("latin1".parse(), Ok(Codificacao::Windows1252));
+        assert_eq!("UTF-8".parse(), Ok(Codificacao::Utf8));
+        assert!("ebcdic".parse::<Codificacao>().is_err());
+    }
+}
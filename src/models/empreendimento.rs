@@ -1,3 +1,9 @@
+use crate::models::geometria::{Coordenada, Poligono};
+use crate::models::json_aninhado;
+use crate::models::lote::{Lote, SistemaDeCoordenadas};
+use crate::models::opcional;
+use crate::models::torre::Torre;
+use crate::models::uso::Uso;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Debug, Deserialize, Serialize, Clone)]
@@ -21,6 +27,162 @@ pub struct Empreendimento {
     #[serde(alias = "area_da_torre")]
     pub area_da_torre: f64,
 
-    #[serde(alias = "area_de_lazer")]
+    #[serde(
+        default,
+        alias = "area_de_lazer",
+        deserialize_with = "opcional::deserializar"
+    )]
     pub area_de_lazer: Option<f64>,
+
+    /// Área construída computável somando todos os pavimentos, em m².
+    #[serde(
+        default,
+        alias = "area_construida",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub area_construida: Option<f64>,
+
+    /// Área do terreno livre de pavimentação e edificação, em m².
+    #[serde(
+        default,
+        alias = "area_permeavel",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub area_permeavel: Option<f64>,
+
+    /// Projeção horizontal das edificações sobre o terreno, em m².
+    #[serde(
+        default,
+        alias = "area_de_projecao",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub area_de_projecao: Option<f64>,
+
+    #[serde(default, alias = "uso")]
+    pub uso: Uso,
+
+    #[serde(
+        default,
+        alias = "zona",
+        deserialize_with = "opcional::deserializar_texto"
+    )]
+    pub zona: Option<String>,
+
+    /// Latitude do empreendimento em graus (WGS84).
+    #[serde(
+        default,
+        alias = "latitude",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub latitude: Option<f64>,
+
+    /// Longitude do empreendimento em graus (WGS84).
+    #[serde(
+        default,
+        alias = "longitude",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub longitude: Option<f64>,
+
+    /// Altitude do terreno em metros, para comparar com superfícies de restrição de altura.
+    #[serde(
+        default,
+        alias = "cota_do_terreno",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub cota_do_terreno: Option<f64>,
+
+    /// Total de unidades habitacionais ou comerciais do empreendimento.
+    #[serde(
+        default,
+        alias = "numero_de_unidades",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub numero_de_unidades: Option<u32>,
+
+    /// Unidades de cada torre, usado quando o total não é informado.
+    #[serde(
+        default,
+        alias = "unidades_por_torre",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub unidades_por_torre: Option<u32>,
+
+    /// Total de vagas de automóveis, incluindo as acessíveis.
+    #[serde(
+        default,
+        alias = "vagas_de_garagem",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub vagas_de_garagem: Option<u32>,
+
+    #[serde(
+        default,
+        alias = "vagas_acessiveis",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub vagas_acessiveis: Option<u32>,
+
+    #[serde(
+        default,
+        alias = "vagas_de_motos",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub vagas_de_motos: Option<u32>,
+
+    #[serde(
+        default,
+        alias = "vagas_de_bicicletas",
+        deserialize_with = "opcional::deserializar"
+    )]
+    pub vagas_de_bicicletas: Option<u32>,
+
+    /// Implantação individual das torres, no mesmo sistema de coordenadas do lote.
+    #[serde(default, deserialize_with = "json_aninhado::deserializar")]
+    pub torres: Option<Vec<Torre>>,
+
+    /// Contorno do lote, em coordenadas locais ou GeoJSON; as torres usam o mesmo sistema.
+    #[serde(default, deserialize_with = "json_aninhado::deserializar")]
+    pub lote: Option<Lote>,
+}
+
+impl Empreendimento {
+    /// Área do terreno calculada a partir do contorno do lote, em m².
+    pub fn area_calculada_do_lote(&self) -> Option<f64> {
+        self.lote.as_ref().map(Lote::area)
+    }
+
+    /// Projeção de uma torre em metros, convertida pelo sistema de coordenadas do lote.
+    pub fn projecao_em_metros(&self, torre: &Torre) -> Poligono {
+        match &self.lote {
+            Some(lote) => lote.projetar(&torre.projecao),
+            None => torre.projecao.clone(),
+        }
+    }
+
+    /// Altura de uma torre, usando a altura geral do empreendimento quando a torre não informa.
+    pub fn altura_de(&self, torre: &Torre) -> f64 {
+        torre.altura.unwrap_or(self.altura_da_torre)
+    }
+
+    /// Localização como `[longitude, latitude]`: a informada diretamente ou,
+    /// na falta dela, o centroide de um lote em coordenadas geográficas.
+    pub fn coordenadas(&self) -> Option<Coordenada> {
+        match (self.longitude, self.latitude, &self.lote) {
+            (Some(longitude), Some(latitude), _) => Some([longitude, latitude]),
+            (_, _, Some(lote)) if lote.sistema == SistemaDeCoordenadas::Geografico => {
+                Some(lote.centroide())
+            }
+            _ => None,
+        }
+    }
+
+    /// Total de unidades, informado diretamente ou calculado a partir das
+    /// unidades por torre; a soma é feita em `u64`, que comporta qualquer
+    /// produto de contagens `u32`.
+    pub fn total_de_unidades(&self) -> Option<u64> {
+        self.numero_de_unidades.map(u64::from).or_else(|| {
+            Some(u64::from(self.unidades_por_torre?) * u64::from(self.numero_de_torres))
+        })
+    }
 }
@@ -9,6 +9,16 @@ lazy_static! {
         m.insert("altura-da-torre", "altura_da_torre");
         m.insert("area-da-torre", "area_da_torre");
         m.insert("area-de-lazer", "area_de_lazer");
+        m.insert("area-construida", "area_construida");
+        m.insert("area-permeavel", "area_permeavel");
+        m.insert("area-de-projecao", "area_de_projecao");
+        m.insert("cota-do-terreno", "cota_do_terreno");
+        m.insert("numero-de-unidades", "numero_de_unidades");
+        m.insert("unidades-por-torre", "unidades_por_torre");
+        m.insert("vagas-de-garagem", "vagas_de_garagem");
+        m.insert("vagas-acessiveis", "vagas_acessiveis");
+        m.insert("vagas-de-motos", "vagas_de_motos");
+        m.insert("vagas-de-bicicletas", "vagas_de_bicicletas");
         m
     };
 }
@@ -28,8 +38,26 @@ mod tests {
         assert_eq!(normalize_field_name("altura-da-torre"), "altura_da_torre");
         assert_eq!(normalize_field_name("area-da-torre"), "area_da_torre");
         assert_eq!(normalize_field_name("area-de-lazer"), "area_de_lazer");
+        assert_eq!(normalize_field_name("area-construida"), "area_construida");
+        assert_eq!(normalize_field_name("area-permeavel"), "area_permeavel");
+        assert_eq!(normalize_field_name("area-de-projecao"), "area_de_projecao");
+        assert_eq!(normalize_field_name("cota-do-terreno"), "cota_do_terreno");
+        assert_eq!(
+            normalize_field_name("numero-de-unidades"),
+            "numero_de_unidades"
+        );
+        assert_eq!(
+            normalize_field_name("unidades-por-torre"),
+            "unidades_por_torre"
+        );
+        assert_eq!(normalize_field_name("vagas-de-garagem"), "vagas_de_garagem");
+        assert_eq!(normalize_field_name("vagas-acessiveis"), "vagas_acessiveis");
+        assert_eq!(normalize_field_name("vagas-de-motos"), "vagas_de_motos");
+        assert_eq!(
+            normalize_field_name("vagas-de-bicicletas"),
+            "vagas_de_bicicletas"
+        );
         assert_eq!(normalize_field_name("construtora"), "construtora");
         assert_eq!(normalize_field_name("cidade"), "cidade");
     }
 }
-
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+
+/// Par `[x, y]` em metros, no sistema local do lote.
+pub type Coordenada = [f64; 2];
+
+/// Polígono simples descrito pelo anel externo. O fechamento do anel
+/// (repetir o primeiro vértice no final) é opcional.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Poligono(pub Vec<Coordenada>);
+
+impl Poligono {
+    fn vertices(&self) -> &[Coordenada] {
+        match self.0.as_slice() {
+            [primeiro, .., ultimo] if primeiro == ultimo => &self.0[..self.0.len() - 1],
+            vertices => vertices,
+        }
+    }
+
+    pub fn arestas(&self) -> impl Iterator<Item = (Coordenada, Coordenada)> + '_ {
+        let vertices = self.vertices();
+        (0..vertices.len()).map(move |i| (vertices[i], vertices[(i + 1) % vertices.len()]))
+    }
+
+    pub fn area(&self) -> f64 {
+        let soma: f64 = self.arestas().map(|(a, b)| a[0] * b[1] - b[0] * a[1]).sum();
+        soma.abs() / 2.0
+    }
+
+    /// Cantos mínimo e máximo do retângulo que envolve o polígono.
+    pub fn limites(&self) -> (Coordenada, Coordenada) {
+        self.0.iter().fold(
+            ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+            |(min, max), p| {
+                (
+                    [min[0].min(p[0]), min[1].min(p[1])],
+                    [max[0].max(p[0]), max[1].max(p[1])],
+                )
+            },
+        )
+    }
+
+    /// Centroide da área do polígono (média dos vértices se a área for nula).
+    ///
+    /// As contas são feitas relativas ao primeiro vértice, para não perder
+    /// precisão com coordenadas grandes, como longitude e latitude.
+    pub fn centroide(&self) -> Coordenada {
+        let vertices = self.vertices();
+        let origem = vertices.first().copied().unwrap_or_default();
+        let (mut cx, mut cy, mut soma) = (0.0, 0.0, 0.0);
+        for (a, b) in self.arestas() {
+            let (a, b) = (subtrair(a, origem), subtrair(b, origem));
+            let fator = a[0] * b[1] - b[0] * a[1];
+            cx += (a[0] + b[0]) * fator;
+            cy += (a[1] + b[1]) * fator;
+            soma += fator;
+        }
+        if soma == 0.0 {
+            let n = vertices.len().max(1) as f64;
+            return [
+                vertices.iter().map(|v| v[0]).sum::<f64>() / n,
+                vertices.iter().map(|v| v[1]).sum::<f64>() / n,
+            ];
+        }
+        [origem[0] + cx / (3.0 * soma), origem[1] + cy / (3.0 * soma)]
+    }
+
+    /// Menor distância entre duas retas paralelas que envolvem o polígono.
+    pub fn largura_minima(&self) -> Option<f64> {
+        let casca = envoltoria_convexa(self.vertices());
+        if casca.len() < 3 {
+            return None;
+        }
+        (0..casca.len())
+            .map(|i| {
+                let a = casca[i];
+                let b = casca[(i + 1) % casca.len()];
+                let direcao = subtrair(b, a);
+                let comprimento = (direcao[0].powi(2) + direcao[1].powi(2)).sqrt();
+                casca
+                    .iter()
+                    .map(|&p| produto_vetorial(direcao, subtrair(p, a)).abs() / comprimento)
+                    .fold(0.0, f64::max)
+            })
+            .reduce(f64::min)
+    }
+
+    pub fn contem(&self, p: Coordenada) -> bool {
+        let mut dentro = false;
+        for (a, b) in self.arestas() {
+            if (a[1] > p[1]) != (b[1] > p[1]) {
+                let x = a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+                if p[0] < x {
+                    dentro = !dentro;
+                }
+            }
+        }
+        dentro
+    }
+
+    pub fn contem_poligono(&self, outro: &Poligono) -> bool {
+        outro.vertices().iter().all(|&v| self.contem(v)) && !self.cruza_bordas(outro)
+    }
+
+    /// Menor distância entre os dois polígonos; zero quando se tocam ou se sobrepõem.
+    pub fn distancia_ate(&self, outro: &Poligono) -> f64 {
+        let sobrepostos = self.cruza_bordas(outro)
+            || outro.vertices().first().is_some_and(|&v| self.contem(v))
+            || self.vertices().first().is_some_and(|&v| outro.contem(v));
+        if sobrepostos {
+            0.0
+        } else {
+            self.distancia_entre_bordas(outro)
+        }
+    }
+
+    /// Menor distância entre as bordas dos dois polígonos, mesmo quando um
+    /// está contido no outro (por exemplo, uma torre dentro do lote).
+    pub fn distancia_entre_bordas(&self, outro: &Poligono) -> f64 {
+        self.arestas()
+            .flat_map(|a| {
+                outro
+                    .arestas()
+                    .map(move |b| distancia_entre_segmentos(a, b))
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn cruza_bordas(&self, outro: &Poligono) -> bool {
+        self.arestas()
+            .any(|a| outro.arestas().any(|b| segmentos_se_cruzam(a, b)))
+    }
+}
+
+/// Envoltória convexa pelo algoritmo da cadeia monotônica, em sentido anti-horário.
+fn envoltoria_convexa(pontos: &[Coordenada]) -> Vec<Coordenada> {
+    let mut pontos = pontos.to_vec();
+    pontos.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    pontos.dedup();
+    if pontos.len() < 3 {
+        return pontos;
+    }
+
+    let mut casca: Vec<Coordenada> = Vec::with_capacity(pontos.len() * 2);
+    for passo in [pontos.clone(), pontos.into_iter().rev().collect()] {
+        let inicio = casca.len();
+        for p in passo {
+            while casca.len() >= inicio + 2 {
+                let n = casca.len();
+                if produto_vetorial(
+                    subtrair(casca[n - 1], casca[n - 2]),
+                    subtrair(p, casca[n - 2]),
+                ) > 0.0
+                {
+                    break;
+                }
+                casca.pop();
+            }
+            casca.push(p);
+        }
+        casca.pop();
+    }
+    casca
+}
+
+fn subtrair(a: Coordenada, b: Coordenada) -> Coordenada {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn produto_vetorial(a: Coordenada, b: Coordenada) -> f64 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+fn distancia_ponto_segmento(p: Coordenada, (a, b): (Coordenada, Coordenada)) -> f64 {
+    let ab = subtrair(b, a);
+    let ap = subtrair(p, a);
+    let comprimento = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if comprimento == 0.0 {
+        0.0
+    } else {
+        ((ap[0] * ab[0] + ap[1] * ab[1]) / comprimento).clamp(0.0, 1.0)
+    };
+    let projecao = [a[0] + t * ab[0], a[1] + t * ab[1]];
+    let d = subtrair(p, projecao);
+    (d[0] * d[0] + d[1] * d[1]).sqrt()
+}
+
+fn segmentos_se_cruzam((a, b): (Coordenada, Coordenada), (c, d): (Coordenada, Coordenada)) -> bool {
+    let d1 = produto_vetorial(subtrair(b, a), subtrair(c, a));
+    let d2 = produto_vetorial(subtrair(b, a), subtrair(d, a));
+    let d3 = produto_vetorial(subtrair(d, c), subtrair(a, c));
+    let d4 = produto_vetorial(subtrair(d, c), subtrair(b, c));
+    ((d1 > 0.0) != (d2 > 0.0)) && ((d3 > 0.0) != (d4 > 0.0)) && d1 != 0.0 && d2 != 0.0
+}
+
+pub fn distancia_entre_segmentos(
+    s1: (Coordenada, Coordenada),
+    s2: (Coordenada, Coordenada),
+) -> f64 {
+    if segmentos_se_cruzam(s1, s2) {
+        return 0.0;
+    }
+    [
+        distancia_ponto_segmento(s1.0, s2),
+        distancia_ponto_segmento(s1.1, s2),
+        distancia_ponto_segmento(s2.0, s1),
+        distancia_ponto_segmento(s2.1, s1),
+    ]
+    .into_iter()
+    .fold(f64::INFINITY, f64::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retangulo(x: f64, y: f64, largura: f64, profundidade: f64) -> Poligono {
+        Poligono(vec![
+            [x, y],
+            [x + largura, y],
+            [x + largura, y + profundidade],
+            [x, y + profundidade],
+        ])
+    }
+
+    #[test]
+    fn test_area_com_e_sem_fechamento() {
+        let aberto = retangulo(0.0, 0.0, 20.0, 10.0);
+        let mut fechado = aberto.clone();
+        fechado.0.push([0.0, 0.0]);
+
+        assert_eq!(aberto.area(), 200.0);
+        assert_eq!(fechado.area(), 200.0);
+    }
+
+    #[test]
+    fn test_centroide_e_largura_minima() {
+        let lote = retangulo(10.0, 20.0, 40.0, 25.0);
+        assert_eq!(lote.centroide(), [30.0, 32.5]);
+        assert_eq!(lote.largura_minima(), Some(25.0));
+
+        let triangulo = Poligono(vec![[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]]);
+        let largura = triangulo.largura_minima().unwrap();
+        assert!((largura - 50f64.sqrt()).abs() < 1e-9);
+
+        assert_eq!(
+            Poligono(vec![[0.0, 0.0], [1.0, 1.0]]).largura_minima(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_contem() {
+        let lote = retangulo(0.0, 0.0, 50.0, 40.0);
+
+        assert!(lote.contem([10.0, 10.0]));
+        assert!(!lote.contem([60.0, 10.0]));
+        assert!(lote.contem_poligono(&retangulo(5.0, 5.0, 10.0, 10.0)));
+        assert!(!lote.contem_poligono(&retangulo(45.0, 5.0, 10.0, 10.0)));
+    }
+
+    #[test]
+    fn test_distancia_entre_poligonos() {
+        let a = retangulo(0.0, 0.0, 10.0, 10.0);
+        let b = retangulo(16.0, 0.0, 10.0, 10.0);
+        let c = retangulo(13.0, 14.0, 10.0, 10.0);
+        let sobreposto = retangulo(5.0, 5.0, 10.0, 10.0);
+
+        assert_eq!(a.distancia_ate(&b), 6.0);
+        assert_eq!(a.distancia_ate(&c), 5.0);
+        assert_eq!(a.distancia_ate(&sobreposto), 0.0);
+        assert_eq!(retangulo(0.0, 0.0, 50.0, 50.0).distancia_ate(&a), 0.0);
+    }
+
+    #[test]
+    fn test_distancia_entre_bordas_com_poligono_contido() {
+        let lote = retangulo(0.0, 0.0, 50.0, 40.0);
+        let torre = retangulo(8.0, 5.0, 20.0, 20.0);
+
+        assert_eq!(lote.distancia_entre_bordas(&torre), 5.0);
+    }
+}
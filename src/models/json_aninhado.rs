@@ -0,0 +1,44 @@
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer};
+
+/// Desserializa um campo estruturado que pode vir como valor (JSON) ou como
+/// texto JSON em uma célula (CSV, XML, Parquet). Texto vazio vira `None`.
+pub fn deserializar<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Campo<T> {
+        Texto(String),
+        // Texto de um elemento XML, como entregue pelo quick-xml.
+        Elemento {
+            #[serde(rename = "$text")]
+            texto: String,
+        },
+        Valor(T),
+    }
+
+    let texto = match Option::<Campo<T>>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(Campo::Valor(valor)) => return Ok(Some(valor)),
+        Some(Campo::Texto(texto)) | Some(Campo::Elemento { texto }) => texto,
+    };
+    de_texto(Some(texto)).map_err(D::Error::custom)
+}
+
+/// Converte um campo estruturado em texto JSON, para formatos tabulares.
+pub fn para_texto<T: serde::Serialize>(valor: &Option<T>) -> Option<String> {
+    valor.as_ref().and_then(|v| serde_json::to_string(v).ok())
+}
+
+/// Lê um campo estruturado a partir de texto JSON, tratando texto vazio como ausente.
+pub fn de_texto<T: DeserializeOwned>(
+    texto: Option<String>,
+) -> Result<Option<T>, serde_json::Error> {
+    match texto {
+        Some(texto) if !texto.trim().is_empty() => serde_json::from_str(&texto).map(Some),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,293 @@
+use crate::models::geometria::{Coordenada, Poligono};
+use geographiclib_rs::{Geodesic, PolygonArea, Winding};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Value, json};
+
+/// Sistema de coordenadas em que o contorno do lote foi informado.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SistemaDeCoordenadas {
+    /// Coordenadas projetadas ou locais, em metros.
+    #[default]
+    Projetado,
+    /// Longitude e latitude em graus (WGS84), padrão do GeoJSON.
+    Geografico,
+}
+
+/// Contorno do lote, informado como lista de `[x, y]` em metros ou como
+/// geometria GeoJSON (`Polygon`, `MultiPolygon` com um polígono ou `Feature`).
+///
+/// O GeoJSON é interpretado como longitude/latitude, a menos que declare um
+/// `crs` projetado (por exemplo `EPSG:31983`).
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Lote {
+    pub contorno: Poligono,
+    pub sistema: SistemaDeCoordenadas,
+}
+
+impl Lote {
+    pub fn local(contorno: Poligono) -> Self {
+        Self {
+            contorno,
+            sistema: SistemaDeCoordenadas::Projetado,
+        }
+    }
+
+    pub fn geografico(contorno: Poligono) -> Self {
+        Self {
+            contorno,
+            sistema: SistemaDeCoordenadas::Geografico,
+        }
+    }
+
+    /// Área do lote em m²; geodésica (elipsoide WGS84) para coordenadas
+    /// geográficas, em qualquer sentido de percurso do contorno.
+    pub fn area(&self) -> f64 {
+        match self.sistema {
+            SistemaDeCoordenadas::Projetado => self.contorno.area(),
+            SistemaDeCoordenadas::Geografico => {
+                let geodesica = Geodesic::wgs84();
+                let mut poligono = PolygonArea::new(&geodesica, Winding::CounterClockwise);
+                for (a, _) in self.contorno.arestas() {
+                    poligono.add_point(a[1], a[0]);
+                }
+                // Com sinal, o anel no sentido horário tem área negativa, em vez
+                // da área da Terra menos a do lote.
+                let (_, area, _) = poligono.compute(true);
+                area.abs()
+            }
+        }
+    }
+
+    /// Ponto central do contorno, no sistema de coordenadas do lote.
+    pub fn centroide(&self) -> Coordenada {
+        self.contorno.centroide()
+    }
+
+    /// Converte um polígono no sistema do lote para metros, com origem no
+    /// centroide do lote quando as coordenadas são geográficas.
+    pub fn projetar(&self, poligono: &Poligono) -> Poligono {
+        match self.sistema {
+            SistemaDeCoordenadas::Projetado => poligono.clone(),
+            SistemaDeCoordenadas::Geografico => {
+                let origem = self.centroide();
+                Poligono(
+                    poligono
+                        .0
+                        .iter()
+                        .map(|&p| projetar_ponto(origem, p))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// Contorno do lote em metros.
+    pub fn contorno_em_metros(&self) -> Poligono {
+        self.projetar(&self.contorno)
+    }
+
+    /// Comprimento da testada, por convenção a primeira aresta do contorno.
+    pub fn testada(&self) -> Option<f64> {
+        let contorno = self.contorno_em_metros();
+        contorno
+            .arestas()
+            .next()
+            .map(|(a, b)| ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt())
+    }
+
+    /// Menor largura do lote, medida entre retas paralelas que o envolvem.
+    pub fn largura_minima(&self) -> Option<f64> {
+        self.contorno_em_metros().largura_minima()
+    }
+
+    fn de_geojson(valor: &Value) -> Result<Self, String> {
+        let sistema = match valor
+            .pointer("/crs/properties/name")
+            .and_then(Value::as_str)
+        {
+            Some(nome) if !crs_geografico(nome) => SistemaDeCoordenadas::Projetado,
+            _ => SistemaDeCoordenadas::Geografico,
+        };
+
+        let tipo = valor
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let anel = match tipo {
+            "Feature" => {
+                let geometria = valor
+                    .get("geometry")
+                    .ok_or("Feature do lote sem 'geometry'")?;
+                let mut lote = Self::de_geojson(geometria)?;
+                if valor.get("crs").is_some() {
+                    lote.sistema = sistema;
+                }
+                return Ok(lote);
+            }
+            "Polygon" => valor.pointer("/coordinates/0"),
+            "MultiPolygon" => {
+                let poligonos = valor
+                    .get("coordinates")
+                    .and_then(Value::as_array)
+                    .map(Vec::len)
+                    .unwrap_or_default();
+                if poligonos != 1 {
+                    return Err(format!(
+                        "Lote em MultiPolygon deve ter exatamente um polígono, encontrou {}",
+                        poligonos
+                    ));
+                }
+                valor.pointer("/coordinates/0/0")
+            }
+            outro => return Err(format!("Geometria '{}' não suportada para o lote", outro)),
+        }
+        .ok_or("Geometria do lote sem coordenadas")?;
+
+        let contorno: Poligono = serde_json::from_value(anel.clone()).map_err(|e| e.to_string())?;
+        Ok(Self { contorno, sistema })
+    }
+}
+
+fn crs_geografico(nome: &str) -> bool {
+    let nome = nome.to_uppercase();
+    nome.ends_with("CRS84") || nome.ends_with("4326")
+}
+
+/// Projeção equiretangular local, adequada às dimensões de um lote.
+fn projetar_ponto(origem: Coordenada, ponto: Coordenada) -> Coordenada {
+    let latitude = origem[1].to_radians();
+    let metros_por_grau_lat =
+        111_132.954 - 559.822 * (2.0 * latitude).cos() + 1.175 * (4.0 * latitude).cos();
+    let metros_por_grau_lon = 111_412.84 * latitude.cos() - 93.5 * (3.0 * latitude).cos();
+    [
+        (ponto[0] - origem[0]) * metros_por_grau_lon,
+        (ponto[1] - origem[1]) * metros_por_grau_lat,
+    ]
+}
+
+impl<'de> Deserialize<'de> for Lote {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let valor = Value::deserialize(deserializer)?;
+        if valor.is_array() {
+            let contorno = serde_json::from_value(valor).map_err(D::Error::custom)?;
+            return Ok(Lote::local(contorno));
+        }
+        Lote::de_geojson(&valor).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Lote {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.sistema {
+            SistemaDeCoordenadas::Projetado => self.contorno.serialize(serializer),
+            SistemaDeCoordenadas::Geografico => {
+                let mut anel = self.contorno.0.clone();
+                if anel.first() != anel.last() {
+                    anel.extend(anel.first().copied());
+                }
+                json!({ "type": "Polygon", "coordinates": [anel] }).serialize(serializer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lote_local_a_partir_de_lista() {
+        let lote: Lote = serde_json::from_str("[[0,0],[20,0],[20,30],[0,30]]").unwrap();
+
+        assert_eq!(lote.sistema, SistemaDeCoordenadas::Projetado);
+        assert_eq!(lote.area(), 600.0);
+        assert_eq!(lote.testada(), Some(20.0));
+        assert_eq!(lote.largura_minima(), Some(20.0));
+    }
+
+    #[test]
+    fn test_lote_geojson_geografico() {
+        // Quadrado de aproximadamente 102m x 111m em São Paulo
+        let lote: Lote = serde_json::from_str(
+            r#"{
+                "type": "Polygon",
+                "coordinates": [[[-46.6, -23.5], [-46.599, -23.5], [-46.599, -23.499], [-46.6, -23.499], [-46.6, -23.5]]]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(lote.sistema, SistemaDeCoordenadas::Geografico);
+        assert!((lote.area() - 11312.27).abs() < 0.1, "área {}", lote.area());
+
+        let testada = lote.testada().unwrap();
+        assert!((testada - 102.1).abs() < 0.5, "testada {}", testada);
+        let largura = lote.largura_minima().unwrap();
+        assert!((largura - 102.1).abs() < 0.5, "largura {}", largura);
+    }
+
+    #[test]
+    fn test_lote_geojson_geografico_em_sentido_horario() {
+        let anti_horario = Lote::geografico(Poligono(vec![
+            [-46.6, -23.5],
+            [-46.599, -23.5],
+            [-46.599, -23.499],
+            [-46.6, -23.499],
+        ]));
+        let mut horario = anti_horario.clone();
+        horario.contorno.0.reverse();
+
+        assert!(
+            (horario.area() - 11312.27).abs() < 0.1,
+            "área {}",
+            horario.area()
+        );
+        assert_eq!(horario.area(), anti_horario.area());
+    }
+
+    #[test]
+    fn test_lote_geojson_projetado_em_feature() {
+        let lote: Lote = serde_json::from_str(
+            r#"{
+                "type": "Feature",
+                "crs": { "type": "name", "properties": { "name": "EPSG:31983" } },
+                "properties": {},
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [[[333000, 7400000], [333040, 7400000], [333040, 7400025], [333000, 7400025], [333000, 7400000]]]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(lote.sistema, SistemaDeCoordenadas::Projetado);
+        assert_eq!(lote.area(), 1000.0);
+        assert_eq!(lote.testada(), Some(40.0));
+    }
+
+    #[test]
+    fn test_geometria_nao_suportada() {
+        let resultado = serde_json::from_str::<Lote>(r#"{"type": "Point", "coordinates": [0, 0]}"#);
+        assert!(resultado.is_err());
+    }
+
+    #[test]
+    fn test_serializacao_preserva_sistema() {
+        let geografico = Lote::geografico(Poligono(vec![
+            [-46.6, -23.5],
+            [-46.599, -23.5],
+            [-46.599, -23.499],
+        ]));
+        let json = serde_json::to_string(&geografico).unwrap();
+        assert!(json.contains("\"Polygon\""));
+        let relido: Lote = serde_json::from_str(&json).unwrap();
+        assert_eq!(relido.sistema, SistemaDeCoordenadas::Geografico);
+        assert_eq!(relido.area(), geografico.area());
+
+        let local = Lote::local(Poligono(vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]]));
+        assert_eq!(
+            serde_json::to_string(&local).unwrap(),
+            "[[0.0,0.0],[10.0,0.0],[10.0,10.0]]"
+        );
+    }
+}
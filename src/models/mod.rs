@@ -1,2 +1,12 @@
+pub mod arrow_converter;
+pub mod dialeto_csv;
 pub mod empreendimento;
 pub mod field_converter;
+pub mod geometria;
+pub mod json_aninhado;
+pub mod lote;
+pub mod opcional;
+pub mod restricao_altura;
+pub mod torre;
+pub mod uso;
+pub mod zoneamento;
@@ -0,0 +1,212 @@
+use serde::Deserializer;
+use serde::de::{Error, MapAccess, Visitor};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Desserializa um campo opcional simples (número ou texto) que pode vir como
+/// valor (JSON, CSV) ou como texto de um elemento (XML). Texto vazio ou só com
+/// espaços, como uma célula vazia do CSV ou `<area-de-lazer/>` no XML, vira
+/// `None`, assim como `null` e o campo ausente.
+///
+/// Os números são lidos pelo texto, para que o CSV, que entrega inteiros
+/// grandes como `u128`, e o JSON cheguem ao mesmo valor. Por isso o JSON
+/// também aceita números entre aspas nos campos opcionais
+/// (`"vagas-de-garagem": "30"`), como os gravados por planilhas exportadas;
+/// os campos obrigatórios continuam exigindo números.
+pub fn deserializar<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    deserializer.deserialize_option(Campo(PhantomData))
+}
+
+/// Como [`deserializar`], mas para campos de texto: o valor é pedido como
+/// texto, para que o CSV não o interprete como número e `0.` não volte `0`.
+pub fn deserializar_texto<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(Texto)
+}
+
+struct Texto;
+
+impl<'de> Visitor<'de> for Texto {
+    type Value = Option<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("texto ou valor ausente")
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_string(self)
+    }
+
+    fn visit_str<E: Error>(self, texto: &str) -> Result<Self::Value, E> {
+        de_texto(texto).map_err(E::custom)
+    }
+}
+
+struct Campo<T>(PhantomData<T>);
+
+impl<T> Campo<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    fn numero<E: Error>(numero: impl Display) -> Result<Option<T>, E> {
+        de_texto(&numero.to_string()).map_err(E::custom)
+    }
+}
+
+impl<'de, T> Visitor<'de> for Campo<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("número, texto ou valor ausente")
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_str<E: Error>(self, texto: &str) -> Result<Self::Value, E> {
+        de_texto(texto).map_err(E::custom)
+    }
+
+    fn visit_i64<E: Error>(self, numero: i64) -> Result<Self::Value, E> {
+        Self::numero(numero)
+    }
+
+    fn visit_u64<E: Error>(self, numero: u64) -> Result<Self::Value, E> {
+        Self::numero(numero)
+    }
+
+    fn visit_i128<E: Error>(self, numero: i128) -> Result<Self::Value, E> {
+        Self::numero(numero)
+    }
+
+    fn visit_u128<E: Error>(self, numero: u128) -> Result<Self::Value, E> {
+        Self::numero(numero)
+    }
+
+    fn visit_f64<E: Error>(self, numero: f64) -> Result<Self::Value, E> {
+        Self::numero(numero)
+    }
+
+    /// Elemento XML, como entregue pelo quick-xml: o valor está em `$text`.
+    fn visit_map<M: MapAccess<'de>>(self, mut mapa: M) -> Result<Self::Value, M::Error> {
+        let mut valor = None;
+        while let Some(chave) = mapa.next_key::<String>()? {
+            let texto = mapa.next_value::<String>()?;
+            if chave == "$text" {
+                valor = de_texto(&texto).map_err(M::Error::custom)?;
+            }
+        }
+        Ok(valor)
+    }
+}
+
+/// Lê um campo opcional a partir de texto, tratando texto vazio como ausente.
+pub fn de_texto<T>(texto: &str) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if texto.trim().is_empty() {
+        return Ok(None);
+    }
+    texto
+        .parse()
+        .map(Some)
+        .map_err(|e| format!("valor inválido \"{}\": {}", texto, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Registro {
+        #[serde(default, deserialize_with = "deserializar")]
+        area: Option<f64>,
+        #[serde(default, deserialize_with = "deserializar")]
+        vagas: Option<u32>,
+        #[serde(default, deserialize_with = "deserializar_texto")]
+        zona: Option<String>,
+    }
+
+    fn json(texto: &str) -> Registro {
+        serde_json::from_str(texto).unwrap()
+    }
+
+    #[test]
+    fn test_json_aceita_valor_texto_e_nulo() {
+        let registro = json(r#"{"area": 12.5, "vagas": "3", "zona": "ZR"}"#);
+        assert_eq!(registro.area, Some(12.5));
+        assert_eq!(registro.vagas, Some(3));
+        assert_eq!(registro.zona.as_deref(), Some("ZR"));
+
+        let registro = json(r#"{"area": null, "vagas": "", "zona": " "}"#);
+        assert_eq!(registro.area, None);
+        assert_eq!(registro.vagas, None);
+        assert_eq!(registro.zona, None);
+
+        assert_eq!(json("{}").area, None);
+    }
+
+    #[test]
+    fn test_xml_elemento_vazio_e_ausente() {
+        let registro: Registro =
+            quick_xml::de::from_str("<r><area/><vagas> </vagas><zona>ZR</zona></r>").unwrap();
+        assert_eq!(registro.area, None);
+        assert_eq!(registro.vagas, None);
+        assert_eq!(registro.zona.as_deref(), Some("ZR"));
+
+        let registro: Registro =
+            quick_xml::de::from_str("<r><area>1e3</area><vagas>7</vagas></r>").unwrap();
+        assert_eq!(registro.area, Some(1000.0));
+        assert_eq!(registro.vagas, Some(7));
+    }
+
+    #[test]
+    fn test_texto_numerico_no_csv_continua_texto() {
+        let mut leitor = csv::Reader::from_reader("area,vagas,zona\n,,0.\n".as_bytes());
+        let registro: Registro = leitor.deserialize().next().unwrap().unwrap();
+        assert_eq!(registro.area, None);
+        assert_eq!(registro.zona.as_deref(), Some("0."));
+    }
+
+    #[test]
+    fn test_valor_invalido_informa_o_texto() {
+        let erro = serde_json::from_str::<Registro>(r#"{"vagas": "-2"}"#)
+            .err()
+            .unwrap();
+        assert!(erro.to_string().contains("\"-2\""), "{}", erro);
+        assert!(serde_json::from_str::<Registro>(r#"{"vagas": 1.5}"#).is_err());
+    }
+}
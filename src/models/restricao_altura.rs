@@ -0,0 +1,95 @@
+use crate::models::geometria::{Coordenada, Poligono};
+use geographiclib_rs::{Geodesic, InverseGeodesic};
+
+/// Forma de uma superfície de restrição de altura, em longitude/latitude.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormaDaSuperficie {
+    /// Área com altitude máxima constante, como o entorno de um bem tombado.
+    Area {
+        contorno: Poligono,
+        furos: Vec<Poligono>,
+    },
+    /// Cone a partir de uma origem: a altitude permitida cresce com a distância
+    /// à origem na razão `inclinacao` (m/m), até o `raio` em metros.
+    Cone {
+        origem: Coordenada,
+        inclinacao: f64,
+        raio: f64,
+    },
+}
+
+/// Superfície que limita a altitude das edificações, como as de aeródromos
+/// e de proteção ao patrimônio histórico.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuperficieDeRestricao {
+    pub nome: String,
+    /// Altitude máxima em metros; no cone, a altitude na origem.
+    pub altitude_maxima: f64,
+    pub forma: FormaDaSuperficie,
+}
+
+impl SuperficieDeRestricao {
+    /// Altitude permitida no ponto `[longitude, latitude]`, ou `None` quando o
+    /// ponto está fora da superfície.
+    pub fn altitude_permitida_em(&self, ponto: Coordenada) -> Option<f64> {
+        match &self.forma {
+            FormaDaSuperficie::Area { contorno, furos } => {
+                let dentro = contorno.contem(ponto) && !furos.iter().any(|f| f.contem(ponto));
+                dentro.then_some(self.altitude_maxima)
+            }
+            FormaDaSuperficie::Cone {
+                origem,
+                inclinacao,
+                raio,
+            } => {
+                let distancia: f64 =
+                    Geodesic::wgs84().inverse(origem[1], origem[0], ponto[1], ponto[0]);
+                (distancia <= *raio).then_some(self.altitude_maxima + inclinacao * distancia)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_altitude_permitida_em_area() {
+        let superficie = SuperficieDeRestricao {
+            nome: "Entorno do Theatro".to_string(),
+            altitude_maxima: 20.0,
+            forma: FormaDaSuperficie::Area {
+                contorno: Poligono(vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]),
+                furos: Vec::new(),
+            },
+        };
+
+        assert_eq!(superficie.altitude_permitida_em([0.5, 0.5]), Some(20.0));
+        assert_eq!(superficie.altitude_permitida_em([1.5, 0.5]), None);
+    }
+
+    #[test]
+    fn test_altitude_permitida_em_cone() {
+        let superficie = SuperficieDeRestricao {
+            nome: "Aproximação".to_string(),
+            altitude_maxima: 10.0,
+            forma: FormaDaSuperficie::Cone {
+                origem: [-46.65, -23.62],
+                inclinacao: 0.05,
+                raio: 4000.0,
+            },
+        };
+
+        assert_eq!(
+            superficie.altitude_permitida_em([-46.65, -23.62]),
+            Some(10.0)
+        );
+
+        // Aproximadamente 1106m ao norte da origem
+        let altitude = superficie.altitude_permitida_em([-46.65, -23.61]).unwrap();
+        assert!((altitude - 65.3).abs() < 0.5, "altitude {}", altitude);
+
+        assert_eq!(superficie.altitude_permitida_em([-46.65, -23.5]), None);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::models::geometria::Poligono;
+use serde::{Deserialize, Serialize};
+
+/// Implantação de uma torre no lote.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Torre {
+    #[serde(default)]
+    pub nome: Option<String>,
+
+    /// Projeção da torre, no mesmo sistema de coordenadas do lote.
+    pub projecao: Poligono,
+
+    /// Altura da torre; quando ausente vale `altura-da-torre` do empreendimento.
+    #[serde(default)]
+    pub altura: Option<f64>,
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Categoria de uso do solo do empreendimento.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Uso {
+    #[default]
+    Residencial,
+    Comercial,
+    Misto,
+}
+
+impl Uso {
+    pub fn como_str(&self) -> &'static str {
+        match self {
+            Uso::Residencial => "residencial",
+            Uso::Comercial => "comercial",
+            Uso::Misto => "misto",
+        }
+    }
+}
+
+impl fmt::Display for Uso {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.como_str())
+    }
+}
+
+impl FromStr for Uso {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "residencial" => Ok(Uso::Residencial),
+            "comercial" => Ok(Uso::Comercial),
+            "misto" => Ok(Uso::Misto),
+            outro => Err(format!(
+                "Uso '{}' inválido. Use residencial, comercial ou misto.",
+                outro
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Uso {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Uso> for String {
+    fn from(uso: Uso) -> Self {
+        uso.como_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_uso() {
+        assert_eq!("residencial".parse::<Uso>().unwrap(), Uso::Residencial);
+        assert_eq!("Comercial".parse::<Uso>().unwrap(), Uso::Comercial);
+        assert_eq!(" MISTO ".parse::<Uso>().unwrap(), Uso::Misto);
+        assert_eq!("".parse::<Uso>().unwrap(), Uso::Residencial);
+        assert!("industrial".parse::<Uso>().is_err());
+    }
+
+    #[test]
+    fn test_uso_serde() {
+        let uso: Uso = serde_json::from_str("\"comercial\"").unwrap();
+        assert_eq!(uso, Uso::Comercial);
+        assert_eq!(serde_json::to_string(&Uso::Misto).unwrap(), "\"misto\"");
+        assert!(serde_json::from_str::<Uso>("\"industrial\"").is_err());
+    }
+}
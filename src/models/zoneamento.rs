@@ -0,0 +1,19 @@
+use crate::models::geometria::{Coordenada, Poligono};
+
+/// Polígono de uma camada de zoneamento com a zona que ele define.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AreaDeZoneamento {
+    pub zona: String,
+    pub contorno: Poligono,
+    pub furos: Vec<Poligono>,
+}
+
+impl AreaDeZoneamento {
+    pub fn contem(&self, ponto: Coordenada) -> bool {
+        self.contorno.contem(ponto) && !self.furos.iter().any(|furo| furo.contem(ponto))
+    }
+
+    pub fn limites(&self) -> (Coordenada, Coordenada) {
+        self.contorno.limites()
+    }
+}
@@ -0,0 +1,144 @@
+use arqgen::file_generator::{FormatoSaida, OpcoesConversao, Saida, converter, write_json};
+use arqgen::file_reader::{
+    Aba, Compressao, Entrada, FileType, OpcoesPlanilha, detectar_tipo, read_file,
+    read_ods_com_opcoes,
+};
+use arqgen::models::empreendimento::Empreendimento;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::fs;
+use std::io::Write;
+use tempfile::TempDir;
+
+fn empreendimentos() -> Vec<Empreendimento> {
+    vec![Empreendimento {
+        construtora: "Silva, Souza & Filhos".to_string(),
+        cidade: "São Paulo".to_string(),
+        area_do_terreno: 1000.0,
+        numero_de_torres: 2,
+        altura_da_torre: 25.0,
+        area_da_torre: 300.0,
+        area_de_lazer: Some(150.0),
+        zona: Some("ZM".to_string()),
+        vagas_de_garagem: Some(30),
+        ..Default::default()
+    }]
+}
+
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut compactador = GzEncoder::new(Vec::new(), Compression::default());
+    compactador.write_all(bytes).unwrap();
+    compactador.finish().unwrap()
+}
+
+fn fixture(caminho: &str) -> String {
+    format!("{}/tests/{}", env!("CARGO_MANIFEST_DIR"), caminho)
+}
+
+#[test]
+fn test_saidas_compactadas_pela_extensao_sao_lidas_de_volta() {
+    let pasta = TempDir::new().unwrap();
+    let entrada = pasta.path().join("dados.json");
+    let entrada = entrada.to_str().unwrap();
+    write_json(entrada, &empreendimentos()).unwrap();
+
+    let casos = [
+        ("dados.csv.gz", FileType::Csv, Compressao::Gzip),
+        ("dados.json.zst", FileType::Json, Compressao::Zstd),
+        ("dados.xml.bz2", FileType::Xml, Compressao::Bzip2),
+        ("dados.parquet.gz", FileType::Parquet, Compressao::Gzip),
+        ("dados.arrow.zst", FileType::Arrow, Compressao::Zstd),
+        ("dados.arrows.bz2", FileType::Arrow, Compressao::Bzip2),
+    ];
+    for (nome, tipo, compressao) in casos {
+        let saida = pasta.path().join(nome);
+        let saida = saida.to_str().unwrap();
+        converter(entrada, saida, &OpcoesConversao::default()).unwrap();
+
+        let gravado = fs::read(saida).unwrap();
+        assert_eq!(
+            Compressao::pelo_conteudo(&gravado),
+            Some(compressao),
+            "{}",
+            nome
+        );
+        assert_eq!(detectar_tipo(saida).unwrap(), tipo, "{}", nome);
+        assert_eq!(
+            serde_json::to_value(read_file(tipo, saida).unwrap()).unwrap(),
+            serde_json::to_value(empreendimentos()).unwrap(),
+            "{}",
+            nome
+        );
+    }
+}
+
+#[test]
+fn test_compressao_pela_assinatura_sem_extensao() {
+    let pasta = TempDir::new().unwrap();
+    let csv = "construtora;cidade;area_do_terreno;numero_de_torres;altura_da_torre;area_da_torre\nAlfa;Curitiba;1.000,5;2;25;300\n";
+    let download = pasta.path().join("download");
+    let download = download.to_str().unwrap();
+    fs::write(download, gzip(csv.as_bytes())).unwrap();
+
+    assert_eq!(detectar_tipo(download).unwrap(), FileType::Csv);
+    assert_eq!(
+        read_file(FileType::Csv, download).unwrap()[0].area_do_terreno,
+        1000.5
+    );
+
+    let compactado = gzip(csv.as_bytes());
+    let lidos = read_file(FileType::Csv, Entrada::leitor(&compactado[..])).unwrap();
+    assert_eq!(lidos[0].cidade, "Curitiba");
+}
+
+#[test]
+fn test_formatos_binarios_compactados() {
+    let pasta = TempDir::new().unwrap();
+
+    let original = fixture("ifc/residencial_duas_torres.ifc");
+    let modelo = pasta.path().join("modelo.ifc.zst");
+    let modelo = modelo.to_str().unwrap();
+    let bytes = fs::read(&original).unwrap();
+    fs::write(modelo, zstd::encode_all(&bytes[..], 0).unwrap()).unwrap();
+
+    assert_eq!(detectar_tipo(modelo).unwrap(), FileType::Ifc);
+    assert_eq!(
+        serde_json::to_value(read_file(FileType::Ifc, modelo).unwrap()).unwrap(),
+        serde_json::to_value(read_file(FileType::Ifc, &original).unwrap()).unwrap()
+    );
+
+    let original = fixture("planilhas/empreendimentos.ods");
+    let planilha = pasta.path().join("empreendimentos.ods.gz");
+    let planilha = planilha.to_str().unwrap();
+    fs::write(planilha, gzip(&fs::read(&original).unwrap())).unwrap();
+
+    let opcoes = OpcoesPlanilha {
+        aba: Aba::from("Empreendimentos"),
+    };
+    assert_eq!(detectar_tipo(planilha).unwrap(), FileType::Ods);
+    assert_eq!(
+        serde_json::to_value(read_ods_com_opcoes(planilha, &opcoes).unwrap()).unwrap(),
+        serde_json::to_value(read_ods_com_opcoes(&original, &opcoes).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn test_saida_em_escritor_nao_e_compactada() {
+    let pasta = TempDir::new().unwrap();
+    let entrada = pasta.path().join("dados.json.gz");
+    let entrada = entrada.to_str().unwrap();
+    write_json(entrada, &empreendimentos()).unwrap();
+
+    let mut csv = Vec::new();
+    let opcoes = OpcoesConversao {
+        para: Some(FormatoSaida::Csv),
+        ..Default::default()
+    };
+    converter(
+        Entrada::leitor(fs::File::open(entrada).unwrap()),
+        Saida::escritor(&mut csv),
+        &opcoes,
+    )
+    .unwrap();
+    assert!(csv.starts_with(b"construtora,"));
+}
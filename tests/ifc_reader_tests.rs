@@ -0,0 +1,84 @@
+use arqgen::business_logic::validar_empreendimentos;
+use arqgen::file_reader::{FileType, read_file, read_ifc_com_avisos};
+
+fn amostra(nome: &str) -> String {
+    format!("{}/tests/ifc/{}", env!("CARGO_MANIFEST_DIR"), nome)
+}
+
+#[test]
+fn deve_extrair_empreendimento_de_modelo_com_quantidades() {
+    let leitura = read_ifc_com_avisos(&amostra("residencial_duas_torres.ifc")).unwrap();
+
+    assert_eq!(leitura.empreendimentos.len(), 1);
+    let e = &leitura.empreendimentos[0];
+    assert_eq!(e.construtora, "Construtora Horizonte");
+    assert_eq!(e.cidade, "Curitiba");
+    assert_eq!(e.area_do_terreno, 2500.0);
+    assert_eq!(e.numero_de_torres, 2);
+    assert_eq!(e.altura_da_torre, 10.5, "Torre A: 9m + 1,5m da cobertura");
+    assert_eq!(
+        e.area_construida,
+        Some(2100.0),
+        "Laje de cobertura não conta"
+    );
+    assert_eq!(e.area_da_torre, 350.0);
+    assert_eq!(e.area_de_projecao, Some(700.0));
+    assert_eq!(e.cota_do_terreno, Some(934.5));
+    assert!((e.latitude.unwrap() + 25.42867).abs() < 1e-5);
+    assert!((e.longitude.unwrap() + 49.27717).abs() < 1e-5);
+    assert!(
+        e.torres.is_none(),
+        "Sem geometria não há projeção por torre"
+    );
+
+    assert_eq!(leitura.avisos.len(), 1, "{:?}", leitura.avisos);
+    assert!(leitura.avisos[0].contains("Laje #203 de 'Torre B' sem área"));
+}
+
+#[test]
+fn deve_extrair_geometria_em_milimetros_e_completar_com_arquivo_auxiliar() {
+    let leitura = read_ifc_com_avisos(&amostra("torre_em_milimetros.ifc")).unwrap();
+
+    let e = &leitura.empreendimentos[0];
+    assert_eq!(e.construtora, "Construtora Vertical");
+    assert_eq!(e.cidade, "São Paulo");
+    assert_eq!(e.area_do_terreno, 1200.0);
+    assert_eq!(e.area_de_lazer, Some(180.0));
+    assert_eq!(e.zona.as_deref(), Some("ZM"));
+    assert_eq!(e.numero_de_torres, 1);
+    assert_eq!(e.altura_da_torre, 9.0);
+    assert_eq!(e.area_construida, Some(600.0));
+    assert_eq!(e.area_da_torre, 300.0);
+    assert!(e.latitude.is_none());
+
+    let torres = e.torres.as_ref().unwrap();
+    assert_eq!(torres[0].nome.as_deref(), Some("Torre Única"));
+    assert_eq!(torres[0].altura, Some(9.0));
+    assert_eq!(torres[0].projecao.area(), 300.0);
+    assert_eq!(torres[0].projecao.0[0], [100.0, 50.0]);
+    assert_eq!(torres[0].projecao.0[2], [120.0, 65.0]);
+
+    assert_eq!(leitura.avisos.len(), 3, "{:?}", leitura.avisos);
+    assert!(leitura.avisos.iter().any(|a| a.contains("#900=IFCDOOR")));
+    assert!(
+        leitura
+            .avisos
+            .iter()
+            .any(|a| a.contains("Geometria da laje #120 não suportada: IFCFACETEDBREP"))
+    );
+    assert!(leitura.avisos.iter().any(|a| a.contains("Laje #120")));
+}
+
+#[test]
+fn deve_validar_modelo_ifc_diretamente() {
+    let empreendimentos = read_file(FileType::Ifc, &amostra("torre_em_milimetros.ifc")).unwrap();
+
+    let resultados = validar_empreendimentos(&empreendimentos);
+
+    assert_eq!(resultados[0].empreendimento, "Construtora Vertical");
+    assert_eq!(
+        resultados[0].indices.coeficiente_de_aproveitamento,
+        Some(0.5)
+    );
+    assert!(resultados[0].regras_ok, "{:?}", resultados[0].mensagens);
+}
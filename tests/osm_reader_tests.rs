@@ -0,0 +1,304 @@
+use arqgen::business_logic::validar_empreendimentos;
+use arqgen::file_reader::{FileType, read_file, read_osm_com_avisos};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+type Tags = Vec<(&'static str, &'static str)>;
+
+/// Extrato OpenStreetMap mínimo, gravado tanto em XML quanto em PBF.
+#[derive(Default)]
+struct Extrato {
+    nos: Vec<(i64, f64, f64)>,
+    caminhos: Vec<(i64, Vec<i64>, Tags)>,
+}
+
+impl Extrato {
+    /// Acrescenta um retângulo fechado de `largura` x `altura` graus a partir de `origem` (lon, lat).
+    fn retangulo(
+        &mut self,
+        id: i64,
+        origem: [f64; 2],
+        largura: f64,
+        altura: f64,
+        tags: Tags,
+    ) -> &mut Self {
+        let primeiro = id * 10;
+        let cantos = [
+            origem,
+            [origem[0] + largura, origem[1]],
+            [origem[0] + largura, origem[1] + altura],
+            [origem[0], origem[1] + altura],
+        ];
+        for (i, [lon, lat]) in cantos.into_iter().enumerate() {
+            self.nos.push((primeiro + i as i64, lat, lon));
+        }
+        let mut refs: Vec<i64> = (primeiro..primeiro + 4).collect();
+        refs.push(primeiro);
+        self.caminhos.push((id, refs, tags));
+        self
+    }
+
+    fn para_xml(&self) -> String {
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?><osm version="0.6">"#);
+        for (id, lat, lon) in &self.nos {
+            xml.push_str(&format!(
+                r#"<node id="{}" lat="{}" lon="{}"/>"#,
+                id, lat, lon
+            ));
+        }
+        for (id, refs, tags) in &self.caminhos {
+            xml.push_str(&format!(r#"<way id="{}">"#, id));
+            for r in refs {
+                xml.push_str(&format!(r#"<nd ref="{}"/>"#, r));
+            }
+            for (k, v) in tags {
+                xml.push_str(&format!(r#"<tag k="{}" v="{}"/>"#, k, v));
+            }
+            xml.push_str("</way>");
+        }
+        xml.push_str("</osm>");
+        xml
+    }
+
+    /// Codifica o extrato em PBF com blocos sem compressão.
+    fn para_pbf(&self) -> Vec<u8> {
+        let mut textos = vec![""];
+        let mut indice = |texto: &'static str| match textos.iter().position(|t| *t == texto) {
+            Some(i) => i as u64,
+            None => {
+                textos.push(texto);
+                textos.len() as u64 - 1
+            }
+        };
+
+        let mut grupo_nos = Vec::new();
+        for (id, lat, lon) in &self.nos {
+            let mut no = Vec::new();
+            varint_campo(&mut no, 1, zigzag(*id));
+            varint_campo(&mut no, 8, zigzag((lat * 1e7).round() as i64));
+            varint_campo(&mut no, 9, zigzag((lon * 1e7).round() as i64));
+            bytes_campo(&mut grupo_nos, 1, &no);
+        }
+
+        let mut grupo_caminhos = Vec::new();
+        for (id, refs, tags) in &self.caminhos {
+            let mut caminho = Vec::new();
+            varint_campo(&mut caminho, 1, *id as u64);
+            let chaves: Vec<u64> = tags.iter().map(|(k, _)| indice(k)).collect();
+            let valores: Vec<u64> = tags.iter().map(|(_, v)| indice(v)).collect();
+            bytes_campo(&mut caminho, 2, &compactar(&chaves));
+            bytes_campo(&mut caminho, 3, &compactar(&valores));
+            let deltas: Vec<u64> = refs
+                .iter()
+                .scan(0, |anterior, r| {
+                    let delta = r - *anterior;
+                    *anterior = *r;
+                    Some(zigzag(delta))
+                })
+                .collect();
+            bytes_campo(&mut caminho, 8, &compactar(&deltas));
+            bytes_campo(&mut grupo_caminhos, 3, &caminho);
+        }
+
+        let mut tabela = Vec::new();
+        for texto in &textos {
+            bytes_campo(&mut tabela, 1, texto.as_bytes());
+        }
+        let mut bloco = Vec::new();
+        bytes_campo(&mut bloco, 1, &tabela);
+        bytes_campo(&mut bloco, 2, &grupo_nos);
+        bytes_campo(&mut bloco, 2, &grupo_caminhos);
+
+        let mut cabecalho = Vec::new();
+        bytes_campo(&mut cabecalho, 4, b"OsmSchema-V0.6");
+
+        let mut pbf = Vec::new();
+        blob(&mut pbf, "OSMHeader", &cabecalho);
+        blob(&mut pbf, "OSMData", &bloco);
+        pbf
+    }
+}
+
+fn varint(saida: &mut Vec<u8>, mut valor: u64) {
+    while valor >= 0x80 {
+        saida.push((valor as u8) | 0x80);
+        valor >>= 7;
+    }
+    saida.push(valor as u8);
+}
+
+fn zigzag(valor: i64) -> u64 {
+    ((valor << 1) ^ (valor >> 63)) as u64
+}
+
+fn varint_campo(saida: &mut Vec<u8>, campo: u64, valor: u64) {
+    varint(saida, campo << 3);
+    varint(saida, valor);
+}
+
+fn bytes_campo(saida: &mut Vec<u8>, campo: u64, dados: &[u8]) {
+    varint(saida, (campo << 3) | 2);
+    varint(saida, dados.len() as u64);
+    saida.extend_from_slice(dados);
+}
+
+fn compactar(valores: &[u64]) -> Vec<u8> {
+    let mut saida = Vec::new();
+    for valor in valores {
+        varint(&mut saida, *valor);
+    }
+    saida
+}
+
+fn blob(saida: &mut Vec<u8>, tipo: &str, dados: &[u8]) {
+    let mut conteudo = Vec::new();
+    bytes_campo(&mut conteudo, 1, dados);
+    varint_campo(&mut conteudo, 2, dados.len() as u64);
+    let mut cabecalho = Vec::new();
+    bytes_campo(&mut cabecalho, 1, tipo.as_bytes());
+    varint_campo(&mut cabecalho, 3, conteudo.len() as u64);
+    saida.extend_from_slice(&(cabecalho.len() as u32).to_be_bytes());
+    saida.extend_from_slice(&cabecalho);
+    saida.extend_from_slice(&conteudo);
+}
+
+/// Duas quadras: uma residencial com um edifício alto e um
+/// prédio de quatro pavimentos, e uma comercial com um sobrado.
+fn bairro() -> Extrato {
+    let mut extrato = Extrato::default();
+    extrato
+        .retangulo(
+            1,
+            [-46.6400, -23.5600],
+            0.0010,
+            0.0008,
+            vec![("landuse", "residential"), ("name", "Quadra Jardim")],
+        )
+        .retangulo(
+            2,
+            [-46.6380, -23.5600],
+            0.0006,
+            0.0006,
+            vec![("landuse", "retail"), ("addr:city", "São Paulo")],
+        )
+        .retangulo(
+            3,
+            [-46.6398, -23.5598],
+            0.0002,
+            0.0002,
+            vec![("building", "apartments"), ("height", "52 m")],
+        )
+        .retangulo(
+            4,
+            [-46.6394, -23.5598],
+            0.0002,
+            0.0001,
+            vec![("building", "yes"), ("building:levels", "4")],
+        )
+        .retangulo(
+            5,
+            [-46.6378, -23.5598],
+            0.0001,
+            0.0001,
+            vec![("building", "retail"), ("building:levels", "2")],
+        )
+        .retangulo(
+            6,
+            [-46.6500, -23.5700],
+            0.0001,
+            0.0001,
+            vec![("building", "yes")],
+        );
+    extrato
+}
+
+#[test]
+fn deve_ler_o_mesmo_extrato_em_xml_e_pbf() {
+    let dir = tempfile::tempdir().unwrap();
+    let xml = dir.path().join("bairro.osm");
+    let pbf = dir.path().join("bairro.osm.pbf");
+    let extrato = bairro();
+    fs::write(&xml, extrato.para_xml()).unwrap();
+    fs::write(&pbf, extrato.para_pbf()).unwrap();
+
+    let do_xml = read_osm_com_avisos(xml.to_str().unwrap()).unwrap();
+    let do_pbf = read_osm_com_avisos(pbf.to_str().unwrap()).unwrap();
+
+    // O PBF guarda as coordenadas com 7 casas decimais; as áreas variam só no arredondamento.
+    assert_eq!(do_xml.empreendimentos.len(), do_pbf.empreendimentos.len());
+    for (a, b) in do_xml.empreendimentos.iter().zip(&do_pbf.empreendimentos) {
+        assert_eq!(a.construtora, b.construtora);
+        assert_eq!(a.cidade, b.cidade);
+        assert_eq!(a.numero_de_torres, b.numero_de_torres);
+        assert_eq!(a.altura_da_torre, b.altura_da_torre);
+        assert!((a.area_do_terreno - b.area_do_terreno).abs() < 1e-3);
+        assert!((a.area_construida.unwrap() - b.area_construida.unwrap()).abs() < 1e-3);
+    }
+    assert_eq!(do_xml.avisos, do_pbf.avisos);
+    assert_eq!(
+        do_pbf.avisos,
+        vec!["1 edificações sem 'height' nem 'building:levels' ignoradas"]
+    );
+
+    let [jardim, comercial] = do_pbf.empreendimentos.as_slice() else {
+        panic!("esperava dois empreendimentos");
+    };
+    assert_eq!(jardim.construtora, "Quadra Jardim");
+    assert_eq!(jardim.numero_de_torres, 2);
+    assert_eq!(jardim.altura_da_torre, 52.0);
+    assert!(jardim.lote.is_some() && jardim.coordenadas().is_some());
+    assert_eq!(comercial.cidade, "São Paulo");
+    assert_eq!(comercial.uso.como_str(), "comercial");
+    assert_eq!(comercial.altura_da_torre, 6.0);
+}
+
+#[test]
+fn deve_ler_extratos_compactados() {
+    let dir = tempfile::tempdir().unwrap();
+    let xml = dir.path().join("bairro.osm");
+    let bz2 = dir.path().join("bairro.osm.bz2");
+    let zst = dir.path().join("bairro.osm.pbf.zst");
+    let extrato = bairro();
+    fs::write(&xml, extrato.para_xml()).unwrap();
+    let mut compactador = bzip2::write::BzEncoder::new(Vec::new(), Default::default());
+    compactador
+        .write_all(extrato.para_xml().as_bytes())
+        .unwrap();
+    fs::write(&bz2, compactador.finish().unwrap()).unwrap();
+    fs::write(&zst, zstd::encode_all(&extrato.para_pbf()[..], 0).unwrap()).unwrap();
+
+    let do_xml = read_osm_com_avisos(xml.to_str().unwrap()).unwrap();
+    let do_bz2 = read_osm_com_avisos(bz2.to_str().unwrap()).unwrap();
+    let do_zst = read_osm_com_avisos(zst.to_str().unwrap()).unwrap();
+
+    assert_eq!(do_bz2.empreendimentos.len(), do_xml.empreendimentos.len());
+    assert_eq!(do_zst.empreendimentos.len(), do_xml.empreendimentos.len());
+    assert_eq!(do_bz2.avisos, do_xml.avisos);
+    assert_eq!(do_zst.avisos, do_xml.avisos);
+}
+
+#[test]
+fn deve_apontar_edificacoes_existentes_que_nao_seriam_aprovadas() {
+    let dir = tempfile::tempdir().unwrap();
+    let pbf = dir.path().join("bairro.osm.pbf");
+    fs::write(&pbf, bairro().para_pbf()).unwrap();
+    fs::write(
+        Path::new(&format!("{}.json", pbf.display())),
+        r#"{"cidade": "Guaratinguetá"}"#,
+    )
+    .unwrap();
+
+    let empreendimentos = read_file(FileType::Osm, pbf.to_str().unwrap()).unwrap();
+    assert!(empreendimentos.iter().all(|e| e.cidade == "Guaratinguetá"));
+
+    let resultados = validar_empreendimentos(&empreendimentos);
+    let violou_altura = |i: usize| {
+        resultados[i]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("Altura da torre"))
+    };
+    assert!(violou_altura(0), "{:?}", resultados[0].mensagens);
+    assert!(!violou_altura(1), "{:?}", resultados[1].mensagens);
+}
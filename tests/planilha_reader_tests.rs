@@ -0,0 +1,183 @@
+use arqgen::file_reader::{
+    Aba, FileType, OpcoesPlanilha, read_file, read_ods, read_ods_com_opcoes, read_xlsx,
+    read_xlsx_com_opcoes,
+};
+use arqgen::models::uso::Uso;
+use rust_xlsxwriter::Workbook;
+use tempfile::NamedTempFile;
+
+fn planilha(nome: &str) -> String {
+    format!("{}/tests/planilhas/{}", env!("CARGO_MANIFEST_DIR"), nome)
+}
+
+const CABECALHO: [&str; 10] = [
+    "Construtora",
+    "Cidade",
+    "Área do Terreno",
+    "Número de Torres",
+    "Altura da Torre",
+    "Área da Torre",
+    "Área de Lazer",
+    "Uso",
+    "Zona",
+    "Vagas de Garagem",
+];
+
+/// Pasta de trabalho com uma aba de notas, a aba de empreendimentos (título,
+/// linha em branco e cabeçalho a partir da coluna B) e uma aba com erro.
+fn gravar_xlsx() -> NamedTempFile {
+    let mut workbook = Workbook::new();
+
+    workbook
+        .add_worksheet()
+        .set_name("Notas")
+        .unwrap()
+        .write_string(0, 0, "Enviado pelo comercial")
+        .unwrap();
+
+    let aba = workbook
+        .add_worksheet()
+        .set_name("Empreendimentos")
+        .unwrap();
+    aba.write_string(0, 1, "Construtora Exemplo — lote 2024")
+        .unwrap();
+    for (coluna, titulo) in CABECALHO.iter().enumerate() {
+        aba.write_string(2, 1 + coluna as u16, *titulo).unwrap();
+    }
+    aba.write_string(3, 1, "Alfa").unwrap();
+    aba.write_string(3, 2, "Curitiba").unwrap();
+    aba.write_number(3, 3, 1000.0).unwrap();
+    aba.write_number(3, 4, 2.0).unwrap();
+    aba.write_number(3, 5, 25.5).unwrap();
+    aba.write_number(3, 6, 300.0).unwrap();
+    aba.write_number(3, 7, 150.0).unwrap();
+    aba.write_string(3, 8, "Residencial").unwrap();
+    aba.write_string(3, 9, "ZR1").unwrap();
+    aba.write_number(3, 10, 40.0).unwrap();
+    aba.write_string(5, 1, "Beta").unwrap();
+    aba.write_string(5, 2, "São Paulo").unwrap();
+    aba.write_string(5, 3, "1200,5").unwrap();
+    aba.write_number(5, 4, 1.0).unwrap();
+    aba.write_string(5, 5, "18").unwrap();
+    aba.write_number(5, 6, 250.0).unwrap();
+    aba.write_string(5, 8, "comercial").unwrap();
+
+    let aba = workbook.add_worksheet().set_name("Com erro").unwrap();
+    for (coluna, titulo) in CABECALHO.iter().enumerate() {
+        aba.write_string(0, coluna as u16, *titulo).unwrap();
+    }
+    aba.write_string(1, 0, "Gama").unwrap();
+    aba.write_string(1, 1, "Curitiba").unwrap();
+    aba.write_number(1, 2, 900.0).unwrap();
+    aba.write_number(1, 3, 2.5).unwrap();
+
+    let arquivo = NamedTempFile::with_suffix(".xlsx").unwrap();
+    workbook.save(arquivo.path()).unwrap();
+    arquivo
+}
+
+fn aba(nome: &str) -> OpcoesPlanilha {
+    OpcoesPlanilha {
+        aba: Aba::Nome(nome.to_string()),
+    }
+}
+
+#[test]
+fn deve_ler_planilha_xlsx_pela_aba_com_cabecalho_em_portugues() {
+    let arquivo = gravar_xlsx();
+    let path = arquivo.path().to_str().unwrap();
+
+    let empreendimentos = read_xlsx_com_opcoes(path, &aba("Empreendimentos")).unwrap();
+
+    assert_eq!(empreendimentos.len(), 2, "Linhas em branco são ignoradas");
+    let alfa = &empreendimentos[0];
+    assert_eq!(alfa.construtora, "Alfa");
+    assert_eq!(alfa.area_do_terreno, 1000.0);
+    assert_eq!(alfa.numero_de_torres, 2);
+    assert_eq!(alfa.altura_da_torre, 25.5);
+    assert_eq!(alfa.area_de_lazer, Some(150.0));
+    assert_eq!(alfa.zona.as_deref(), Some("ZR1"));
+    assert_eq!(alfa.vagas_de_garagem, Some(40));
+
+    let beta = &empreendimentos[1];
+    assert_eq!(beta.cidade, "São Paulo");
+    assert_eq!(beta.area_do_terreno, 1200.5, "Texto com vírgula decimal");
+    assert_eq!(beta.altura_da_torre, 18.0);
+    assert_eq!(beta.area_de_lazer, None, "Célula vazia vira None");
+    assert_eq!(beta.uso, Uso::Comercial);
+    assert_eq!(beta.zona, None);
+
+    let pela_posicao = read_xlsx_com_opcoes(
+        path,
+        &OpcoesPlanilha {
+            aba: Aba::Indice(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(pela_posicao.len(), 2);
+}
+
+#[test]
+fn deve_informar_aba_e_celula_nos_erros_do_xlsx() {
+    let arquivo = gravar_xlsx();
+    let path = arquivo.path().to_str().unwrap();
+
+    let erro = read_xlsx_com_opcoes(path, &aba("Com erro"))
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        erro,
+        "Aba 'Com erro', célula D2 (numero_de_torres): esperado inteiro não negativo, encontrado 2.5"
+    );
+
+    let erro = read_xlsx(path).unwrap_err().to_string();
+    assert_eq!(
+        erro,
+        "Aba 'Notas': cabeçalho com a coluna 'construtora' não encontrado"
+    );
+
+    let erro = read_xlsx_com_opcoes(path, &aba("Plan9"))
+        .unwrap_err()
+        .to_string();
+    assert!(erro.contains("Empreendimentos, Com erro"), "{}", erro);
+}
+
+#[test]
+fn deve_ler_planilha_ods() {
+    let opcoes = aba("Empreendimentos");
+    let empreendimentos = read_ods_com_opcoes(&planilha("empreendimentos.ods"), &opcoes).unwrap();
+
+    assert_eq!(empreendimentos.len(), 2);
+    assert_eq!(empreendimentos[0].construtora, "Alfa");
+    assert_eq!(empreendimentos[0].altura_da_torre, 25.5);
+    assert_eq!(empreendimentos[0].vagas_de_garagem, Some(40));
+    assert_eq!(empreendimentos[1].area_do_terreno, 1200.5);
+    assert_eq!(empreendimentos[1].area_de_lazer, None);
+    assert_eq!(empreendimentos[1].uso, Uso::Comercial);
+}
+
+#[test]
+fn deve_informar_aba_e_celula_nos_erros_do_ods() {
+    let erro = read_ods_com_opcoes(&planilha("empreendimentos.ods"), &aba("Com erro"))
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        erro,
+        "Aba 'Com erro', célula D2 (numero_de_torres): esperado número, encontrado \"duas\""
+    );
+    assert!(read_ods(&planilha("empreendimentos.ods")).is_err());
+}
+
+#[test]
+fn deve_ler_planilhas_pelo_tipo_de_arquivo() {
+    let arquivo = gravar_xlsx();
+    let path = arquivo.path().to_str().unwrap();
+
+    // Sem opções, vale a primeira aba, que nestes arquivos não tem empreendimentos.
+    let erro = read_file(FileType::Xlsx, path).unwrap_err().to_string();
+    assert!(erro.starts_with("Aba 'Notas'"), "{}", erro);
+    let erro = read_file(FileType::Ods, &planilha("empreendimentos.ods"))
+        .unwrap_err()
+        .to_string();
+    assert!(erro.starts_with("Aba 'Resumo'"), "{}", erro);
+}
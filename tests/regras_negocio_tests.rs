@@ -1,5 +1,6 @@
 use arqgen::business_logic::validar_empreendimentos;
 use arqgen::models::empreendimento::Empreendimento;
+use arqgen::models::uso::Uso;
 
 #[test]
 fn deve_validar_multiplos_empreendimentos_com_diferentes_regras() {
@@ -12,6 +13,7 @@ fn deve_validar_multiplos_empreendimentos_com_diferentes_regras() {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(150.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Beta".to_string(),
@@ -21,6 +23,7 @@ fn deve_validar_multiplos_empreendimentos_com_diferentes_regras() {
             altura_da_torre: 35.0,
             area_da_torre: 200.0,
             area_de_lazer: Some(50.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Alpha".to_string(),
@@ -30,6 +33,7 @@ fn deve_validar_multiplos_empreendimentos_com_diferentes_regras() {
             altura_da_torre: 28.0,
             area_da_torre: 250.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
     ];
 
@@ -87,6 +91,7 @@ fn deve_aplicar_regras_especificas_por_cidade() {
             altura_da_torre: 20.0,
             area_da_torre: 100.0,
             area_de_lazer: Some(100.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Delta".to_string(),
@@ -96,6 +101,7 @@ fn deve_aplicar_regras_especificas_por_cidade() {
             altura_da_torre: 40.0,
             area_da_torre: 150.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
     ];
 
@@ -134,6 +140,7 @@ fn deve_aplicar_regras_especificas_por_construtora() {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Beta".to_string(),
@@ -143,6 +150,7 @@ fn deve_aplicar_regras_especificas_por_construtora() {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
     ];
 
@@ -184,6 +192,7 @@ fn deve_tratar_cenarios_limite_e_edge_cases() {
             altura_da_torre: 1.0,
             area_da_torre: 50.0,
             area_de_lazer: None,
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Eta".to_string(),
@@ -193,6 +202,7 @@ fn deve_tratar_cenarios_limite_e_edge_cases() {
             altura_da_torre: 30.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(100.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Theta".to_string(),
@@ -202,6 +212,7 @@ fn deve_tratar_cenarios_limite_e_edge_cases() {
             altura_da_torre: 25.0,
             area_da_torre: 200.0,
             area_de_lazer: Some(100.0),
+            ..Default::default()
         },
     ];
 
@@ -238,6 +249,7 @@ fn deve_ignorar_regras_em_cidades_especificas() {
         altura_da_torre: 35.0,
         area_da_torre: 300.0,
         area_de_lazer: Some(100.0),
+        ..Default::default()
     }];
 
     let resultados = validar_empreendimentos(&empreendimentos);
@@ -251,3 +263,348 @@ fn deve_ignorar_regras_em_cidades_especificas() {
         "Não deve ter mensagens de erro"
     );
 }
+
+#[test]
+fn deve_aplicar_indices_urbanisticos_por_zona() {
+    let base = Empreendimento {
+        construtora: "Kappa".to_string(),
+        cidade: "São Paulo".to_string(),
+        area_do_terreno: 1000.0,
+        numero_de_torres: 2,
+        altura_da_torre: 25.0,
+        area_da_torre: 300.0,
+        area_de_lazer: Some(150.0),
+        area_construida: Some(3000.0),
+        area_permeavel: Some(100.0),
+        area_de_projecao: Some(600.0),
+        ..Default::default()
+    };
+
+    let sem_zona = base.clone();
+    let zona_mista = Empreendimento {
+        zona: Some("ZM".to_string()),
+        ..base.clone()
+    };
+    let zona_eixo = Empreendimento {
+        zona: Some("ZEU".to_string()),
+        area_permeavel: Some(200.0),
+        ..base
+    };
+
+    let resultados = validar_empreendimentos(&[sem_zona, zona_mista, zona_eixo]);
+
+    assert!(
+        resultados[0].regras_ok,
+        "Sem zona não há limites de índices a aplicar"
+    );
+    assert_eq!(
+        resultados[0].indices.coeficiente_de_aproveitamento,
+        Some(3.0)
+    );
+
+    assert!(!resultados[1].regras_ok);
+    assert!(
+        resultados[1]
+            .mensagens
+            .iter()
+            .any(|m| m
+                .contains("Coeficiente de aproveitamento (3.00) excede o máximo permitido (2.00)")),
+        "Deve reportar o CA calculado e o limite da zona"
+    );
+    assert!(
+        resultados[1]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("Taxa de permeabilidade (10%)")),
+        "Deve reportar a TP calculada"
+    );
+
+    assert!(
+        resultados[2].regras_ok,
+        "ZEU permite CA 4 e a TP atende o mínimo: {:?}",
+        resultados[2].mensagens
+    );
+}
+
+#[test]
+fn deve_validar_vagas_de_garagem_por_cidade() {
+    let base = Empreendimento {
+        construtora: "Lambda".to_string(),
+        cidade: "Rio de Janeiro".to_string(),
+        area_do_terreno: 1000.0,
+        numero_de_torres: 2,
+        altura_da_torre: 25.0,
+        area_da_torre: 300.0,
+        area_de_lazer: Some(150.0),
+        numero_de_unidades: Some(80),
+        vagas_de_garagem: Some(60),
+        vagas_acessiveis: Some(2),
+        ..Default::default()
+    };
+
+    let rio = base.clone();
+    let sao_paulo = Empreendimento {
+        cidade: "São Paulo".to_string(),
+        vagas_de_motos: Some(3),
+        vagas_de_bicicletas: Some(6),
+        ..base
+    };
+
+    let resultados = validar_empreendimentos(&[rio, sao_paulo]);
+
+    assert!(!resultados[0].regras_ok);
+    assert!(
+        resultados[0]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("Vagas de garagem (60) abaixo do mínimo para 80 unidades (80)")),
+        "Rio de Janeiro exige uma vaga por unidade"
+    );
+
+    assert!(
+        resultados[1].regras_ok,
+        "São Paulo aceita até 2 vagas por unidade: {:?}",
+        resultados[1].mensagens
+    );
+}
+
+#[test]
+fn deve_aplicar_regras_especificas_por_uso() {
+    let residencial = Empreendimento {
+        construtora: "Mu".to_string(),
+        cidade: "Rio de Janeiro".to_string(),
+        area_do_terreno: 1000.0,
+        numero_de_torres: 2,
+        altura_da_torre: 40.0,
+        area_da_torre: 300.0,
+        area_de_lazer: None,
+        ..Default::default()
+    };
+    let comercial = Empreendimento {
+        uso: Uso::Comercial,
+        ..residencial.clone()
+    };
+    let comercial_alto = Empreendimento {
+        uso: Uso::Comercial,
+        altura_da_torre: 50.0,
+        ..residencial.clone()
+    };
+    let comercial_sem_lazer_sp = Empreendimento {
+        uso: Uso::Comercial,
+        cidade: "São Paulo".to_string(),
+        ..residencial.clone()
+    };
+
+    let resultados = validar_empreendimentos(&[
+        residencial,
+        comercial,
+        comercial_alto,
+        comercial_sem_lazer_sp,
+    ]);
+
+    assert!(
+        resultados[0]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("Altura da torre deve ser inferior a 30m")),
+        "Residencial no Rio segue a altura padrão"
+    );
+    assert!(
+        resultados[1].regras_ok,
+        "Comercial no Rio pode chegar a 45m: {:?}",
+        resultados[1].mensagens
+    );
+    assert!(
+        resultados[2]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("Altura da torre deve ser inferior a 45m")),
+        "Comercial no Rio acima de 45m deve violar"
+    );
+    assert!(
+        resultados[3].regras_ok,
+        "Área de lazer só é exigida para uso residencial: {:?}",
+        resultados[3].mensagens
+    );
+}
+
+#[test]
+fn deve_limitar_densidade_populacional_por_zona() {
+    let empreendimento = Empreendimento {
+        construtora: "Nu".to_string(),
+        cidade: "São Paulo".to_string(),
+        area_do_terreno: 2000.0,
+        numero_de_torres: 2,
+        altura_da_torre: 25.0,
+        area_da_torre: 300.0,
+        area_de_lazer: Some(300.0),
+        unidades_por_torre: Some(30),
+        zona: Some("ZER".to_string()),
+        ..Default::default()
+    };
+
+    let resultados = validar_empreendimentos(&[empreendimento]);
+
+    assert!(!resultados[0].regras_ok);
+    assert!(
+        resultados[0].mensagens.iter().any(|m| m.contains(
+            "Densidade populacional (900.0 hab/ha) excede o máximo permitido (300.0 hab/ha)"
+        )),
+        "Deve reportar a densidade calculada e o limite: {:?}",
+        resultados[0].mensagens
+    );
+}
+
+#[test]
+fn deve_comparar_area_declarada_com_o_lote_em_geojson() {
+    let lote = serde_json::from_str(
+        r#"{
+            "type": "Polygon",
+            "coordinates": [[[-46.6, -23.5], [-46.599, -23.5], [-46.599, -23.499], [-46.6, -23.499], [-46.6, -23.5]]]
+        }"#,
+    )
+    .unwrap();
+
+    let honesto = Empreendimento {
+        construtora: "Xi".to_string(),
+        cidade: "São Paulo".to_string(),
+        area_do_terreno: 11300.0,
+        numero_de_torres: 1,
+        altura_da_torre: 25.0,
+        area_da_torre: 300.0,
+        lote: Some(lote),
+        ..Default::default()
+    };
+    let inflado = Empreendimento {
+        area_do_terreno: 15000.0,
+        ..honesto.clone()
+    };
+
+    let resultados = validar_empreendimentos(&[honesto, inflado]);
+
+    assert!(
+        resultados[0].regras_ok,
+        "Área declarada compatível com o lote: {:?}",
+        resultados[0].mensagens
+    );
+    assert!(
+        resultados[1]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("difere da área calculada do lote (11312.27 m²)")),
+        "Deve reportar a área geodésica calculada: {:?}",
+        resultados[1].mensagens
+    );
+}
+
+#[test]
+fn deve_obter_a_zona_pela_camada_de_zoneamento() {
+    use arqgen::business_logic::validar_empreendimentos_com_zoneamento;
+    use arqgen::business_logic::zoneamento::MapaZoneamento;
+    use arqgen::models::geometria::Poligono;
+    use arqgen::models::zoneamento::AreaDeZoneamento;
+
+    let mapa = MapaZoneamento::new(vec![AreaDeZoneamento {
+        zona: "ZER".to_string(),
+        contorno: Poligono(vec![
+            [-46.7, -23.6],
+            [-46.6, -23.6],
+            [-46.6, -23.5],
+            [-46.7, -23.5],
+        ]),
+        furos: Vec::new(),
+    }]);
+
+    let dentro = Empreendimento {
+        construtora: "Omicron".to_string(),
+        cidade: "São Paulo".to_string(),
+        area_do_terreno: 2000.0,
+        numero_de_torres: 1,
+        altura_da_torre: 25.0,
+        area_da_torre: 300.0,
+        area_de_lazer: Some(300.0),
+        area_construida: Some(3000.0),
+        latitude: Some(-23.55),
+        longitude: Some(-46.65),
+        zona: Some("ZEU".to_string()),
+        ..Default::default()
+    };
+    let fora = Empreendimento {
+        latitude: Some(-22.9),
+        longitude: Some(-43.2),
+        ..dentro.clone()
+    };
+
+    let resultados = validar_empreendimentos_com_zoneamento(&[dentro, fora], &mapa);
+
+    assert_eq!(resultados[0].zona.as_deref(), Some("ZER"));
+    assert!(
+        resultados[0]
+            .mensagens
+            .iter()
+            .any(|m| m.contains("Coeficiente de aproveitamento (1.50)")),
+        "A zona da camada prevalece sobre a informada: {:?}",
+        resultados[0].mensagens
+    );
+    assert_eq!(
+        resultados[1].zona.as_deref(),
+        Some("ZEU"),
+        "Fora da camada vale a zona informada"
+    );
+    assert!(resultados[1].regras_ok, "{:?}", resultados[1].mensagens);
+}
+
+#[test]
+fn deve_limitar_altura_pelas_superficies_de_restricao() {
+    use arqgen::business_logic::restricoes_altura::MapaRestricoesAltura;
+    use arqgen::business_logic::{CamadasGeograficas, validar_empreendimentos_com_camadas};
+    use arqgen::models::restricao_altura::{FormaDaSuperficie, SuperficieDeRestricao};
+
+    let mapa = MapaRestricoesAltura::new(vec![SuperficieDeRestricao {
+        nome: "Cone SBRJ".to_string(),
+        altitude_maxima: 25.0,
+        forma: FormaDaSuperficie::Cone {
+            origem: [-43.17, -22.91],
+            inclinacao: 0.05,
+            raio: 4000.0,
+        },
+    }]);
+
+    let proximo = Empreendimento {
+        construtora: "Pi".to_string(),
+        cidade: "Rio de Janeiro".to_string(),
+        area_do_terreno: 2000.0,
+        numero_de_torres: 1,
+        altura_da_torre: 28.0,
+        area_da_torre: 300.0,
+        area_de_lazer: Some(300.0),
+        latitude: Some(-22.91),
+        longitude: Some(-43.17),
+        cota_do_terreno: Some(5.0),
+        ..Default::default()
+    };
+    let distante = Empreendimento {
+        latitude: Some(-22.89),
+        ..proximo.clone()
+    };
+
+    let resultados = validar_empreendimentos_com_camadas(
+        &[proximo, distante],
+        CamadasGeograficas {
+            restricoes_altura: Some(&mapa),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(resultados[0].altura_permitida, Some(20.0));
+    assert!(
+        resultados[0].mensagens.contains(
+            &"Altura da torre (28.0m) excede a altura permitida (20.0m) pela superfície 'Cone SBRJ'"
+                .to_string()
+        ),
+        "{:?}",
+        resultados[0].mensagens
+    );
+    assert!(resultados[1].altura_permitida.unwrap() > 28.0);
+    assert!(resultados[1].regras_ok, "{:?}", resultados[1].mensagens);
+}
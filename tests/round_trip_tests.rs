@@ -0,0 +1,244 @@
+//! Gravar e ler de volta cada formato preserva qualquer empreendimento.
+//!
+//! Valores ausentes têm uma representação por formato: chave ausente ou
+//! `null` no JSON, célula vazia no CSV e nas planilhas, elemento omitido no
+//! XML e `null` no Parquet e no Arrow. DXF, IFC, OSM e ODS são só de leitura
+//! e ficam fora destes testes; o XLSX é gravado pela planilha anotada, e o
+//! leitor de planilhas remove os espaços nas bordas dos textos das células.
+
+use arqgen::business_logic::validar_empreendimentos;
+use arqgen::file_generator::{
+    EstiloNomes, FormatoIpc, OpcoesGravacaoCsv, OpcoesXml, write_arrow_com_formato,
+    write_csv_com_opcoes, write_json, write_parquet, write_xlsx_anotado, write_xml_com_opcoes,
+};
+use arqgen::file_reader::{FileType, read_file};
+use arqgen::models::dialeto_csv::DialetoCsv;
+use arqgen::models::empreendimento::Empreendimento;
+use arqgen::models::geometria::Poligono;
+use arqgen::models::lote::Lote;
+use arqgen::models::torre::Torre;
+use arqgen::models::uso::Uso;
+use proptest::prelude::*;
+use std::error::Error;
+use tempfile::NamedTempFile;
+
+fn real() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        -1e6..1e6f64,
+        (-1_000_000i32..1_000_000).prop_map(f64::from),
+        prop::num::f64::NORMAL | prop::num::f64::ZERO,
+    ]
+}
+
+/// Contagens acima de `i32::MAX` não cabem no `Int32` do Parquet e do Arrow.
+fn inteiro() -> impl Strategy<Value = u32> {
+    prop_oneof![0..1000u32, 0..=i32::MAX as u32]
+}
+
+/// Texto com acentos, delimitadores, aspas, marcação e espaços nas bordas.
+fn texto() -> impl Strategy<Value = String> {
+    "[ a-zA-Z0-9çãéÍü,;.\t\"'&<>/|-]{1,16}"
+        .prop_filter("texto só com espaços", |t| !t.trim().is_empty())
+}
+
+fn poligono() -> impl Strategy<Value = Poligono> {
+    prop::collection::vec([real(), real()], 3..6).prop_map(Poligono)
+}
+
+fn torre() -> impl Strategy<Value = Torre> {
+    (
+        prop::option::of(texto()),
+        poligono(),
+        prop::option::of(real()),
+    )
+        .prop_map(|(nome, projecao, altura)| Torre {
+            nome,
+            projecao,
+            altura,
+        })
+}
+
+fn lote() -> impl Strategy<Value = Lote> {
+    prop_oneof![
+        poligono().prop_map(Lote::local),
+        poligono().prop_map(Lote::geografico),
+    ]
+}
+
+prop_compose! {
+    fn medidas()(
+        area_de_lazer in prop::option::of(real()),
+        area_construida in prop::option::of(real()),
+        area_permeavel in prop::option::of(real()),
+        area_de_projecao in prop::option::of(real()),
+        latitude in prop::option::of(real()),
+        longitude in prop::option::of(real()),
+        cota_do_terreno in prop::option::of(real()),
+    ) -> Empreendimento {
+        Empreendimento {
+            area_de_lazer,
+            area_construida,
+            area_permeavel,
+            area_de_projecao,
+            latitude,
+            longitude,
+            cota_do_terreno,
+            ..Default::default()
+        }
+    }
+}
+
+prop_compose! {
+    fn contagens()(
+        numero_de_unidades in prop::option::of(inteiro()),
+        unidades_por_torre in prop::option::of(inteiro()),
+        vagas_de_garagem in prop::option::of(inteiro()),
+        vagas_acessiveis in prop::option::of(inteiro()),
+        vagas_de_motos in prop::option::of(inteiro()),
+        vagas_de_bicicletas in prop::option::of(inteiro()),
+    ) -> Empreendimento {
+        Empreendimento {
+            numero_de_unidades,
+            unidades_por_torre,
+            vagas_de_garagem,
+            vagas_acessiveis,
+            vagas_de_motos,
+            vagas_de_bicicletas,
+            ..Default::default()
+        }
+    }
+}
+
+prop_compose! {
+    fn empreendimento()(
+        construtora in texto(),
+        cidade in texto(),
+        area_do_terreno in real(),
+        numero_de_torres in inteiro(),
+        altura_da_torre in real(),
+        area_da_torre in real(),
+        uso in prop_oneof![Just(Uso::Residencial), Just(Uso::Comercial), Just(Uso::Misto)],
+        zona in prop::option::of(texto()),
+        medidas in medidas(),
+        contagens in contagens(),
+        torres in prop::option::of(prop::collection::vec(torre(), 0..3)),
+        lote in prop::option::of(lote()),
+    ) -> Empreendimento {
+        Empreendimento {
+            construtora,
+            cidade,
+            area_do_terreno,
+            numero_de_torres,
+            altura_da_torre,
+            area_da_torre,
+            uso,
+            zona,
+            torres,
+            lote,
+            ..Empreendimento {
+                numero_de_unidades: contagens.numero_de_unidades,
+                unidades_por_torre: contagens.unidades_por_torre,
+                vagas_de_garagem: contagens.vagas_de_garagem,
+                vagas_acessiveis: contagens.vagas_acessiveis,
+                vagas_de_motos: contagens.vagas_de_motos,
+                vagas_de_bicicletas: contagens.vagas_de_bicicletas,
+                ..medidas
+            }
+        }
+    }
+}
+
+fn empreendimentos() -> impl Strategy<Value = Vec<Empreendimento>> {
+    prop::collection::vec(empreendimento(), 0..4)
+}
+
+/// Textos das células como o leitor de planilhas os entrega.
+fn aparado(e: &Empreendimento) -> Empreendimento {
+    Empreendimento {
+        construtora: e.construtora.trim().to_string(),
+        cidade: e.cidade.trim().to_string(),
+        zona: e.zona.as_deref().map(|zona| zona.trim().to_string()),
+        ..e.clone()
+    }
+}
+
+/// Grava `originais` com `gravar`, lê de volta como `tipo` e compara campo a
+/// campo com `esperados`.
+fn ida_e_volta(
+    originais: &[Empreendimento],
+    esperados: &[Empreendimento],
+    extensao: &str,
+    tipo: FileType,
+    gravar: impl Fn(&str, &[Empreendimento]) -> Result<(), Box<dyn Error>>,
+) -> Result<(), TestCaseError> {
+    let arquivo = NamedTempFile::with_suffix(extensao).unwrap();
+    let path = arquivo.path().to_str().unwrap();
+
+    gravar(path, originais).map_err(|e| TestCaseError::fail(format!("gravar: {}", e)))?;
+    let lidos = read_file(tipo, path).map_err(|e| TestCaseError::fail(format!("ler: {}", e)))?;
+
+    prop_assert_eq!(
+        serde_json::to_value(&lidos).unwrap(),
+        serde_json::to_value(esperados).unwrap()
+    );
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn csv_ida_e_volta(
+        originais in empreendimentos(),
+        excel in any::<bool>(),
+        kebab in any::<bool>(),
+    ) {
+        let opcoes = OpcoesGravacaoCsv {
+            dialeto: if excel { DialetoCsv::excel_brasileiro() } else { DialetoCsv::default() },
+            nomes: if kebab { EstiloNomes::Kebab } else { EstiloNomes::Snake },
+        };
+        ida_e_volta(&originais, &originais, ".csv", FileType::Csv, |path, e| {
+            write_csv_com_opcoes(path, e, &opcoes)
+        })?;
+    }
+
+    #[test]
+    fn json_ida_e_volta(originais in empreendimentos()) {
+        ida_e_volta(&originais, &originais, ".json", FileType::Json, |path, e| {
+            write_json(path, e)
+        })?;
+    }
+
+    #[test]
+    fn xml_ida_e_volta(originais in empreendimentos(), kebab in any::<bool>()) {
+        let opcoes = OpcoesXml {
+            nomes: if kebab { EstiloNomes::Kebab } else { EstiloNomes::Snake },
+        };
+        ida_e_volta(&originais, &originais, ".xml", FileType::Xml, |path, e| {
+            write_xml_com_opcoes(path, e, &opcoes)
+        })?;
+    }
+
+    #[test]
+    fn parquet_ida_e_volta(originais in empreendimentos()) {
+        ida_e_volta(&originais, &originais, ".parquet", FileType::Parquet, |path, e| {
+            write_parquet(path, e)
+        })?;
+    }
+
+    #[test]
+    fn arrow_ida_e_volta(originais in empreendimentos(), stream in any::<bool>()) {
+        let formato = if stream { FormatoIpc::Fluxo } else { FormatoIpc::Arquivo };
+        ida_e_volta(&originais, &originais, ".arrow", FileType::Arrow, |path, e| {
+            write_arrow_com_formato(path, e, formato)
+        })?;
+    }
+
+    #[test]
+    fn xlsx_ida_e_volta(originais in empreendimentos()) {
+        let esperados: Vec<_> = originais.iter().map(aparado).collect();
+        ida_e_volta(&originais, &esperados, ".xlsx", FileType::Xlsx, |path, e| {
+            write_xlsx_anotado(path, e, &validar_empreendimentos(e))
+        })?;
+    }
+}
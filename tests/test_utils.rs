@@ -1,5 +1,5 @@
-use arqgen::models::empreendimento::Empreendimento;
 use arqgen::business_logic::validator::ValidationResult;
+use arqgen::models::empreendimento::Empreendimento;
 
 pub fn criar_empreendimento_valido() -> Empreendimento {
     Empreendimento {
@@ -10,6 +10,7 @@ pub fn criar_empreendimento_valido() -> Empreendimento {
         altura_da_torre: 25.0,
         area_da_torre: 300.0,
         area_de_lazer: Some(150.0),
+        ..Default::default()
     }
 }
 
@@ -22,6 +23,7 @@ pub fn criar_empreendimento_com_violacoes() -> Empreendimento {
         altura_da_torre: 35.0,
         area_da_torre: 200.0,
         area_de_lazer: Some(50.0),
+        ..Default::default()
     }
 }
 
@@ -34,6 +36,7 @@ pub fn criar_empreendimento_por_cidade(cidade: &str) -> Empreendimento {
         altura_da_torre: 25.0,
         area_da_torre: 300.0,
         area_de_lazer: Some(150.0),
+        ..Default::default()
     }
 }
 
@@ -49,6 +52,7 @@ pub fn gerar_empreendimentos_teste() -> Vec<Empreendimento> {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Teste".to_string(),
@@ -58,6 +62,7 @@ pub fn gerar_empreendimentos_teste() -> Vec<Empreendimento> {
             altura_da_torre: 40.0,
             area_da_torre: 150.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
     ]
 }
@@ -0,0 +1,245 @@
+use arqgen::business_logic::{
+    ValidationResult, validar_empreendimentos, validar_record_batch, validar_record_batches,
+    validar_record_batches_em_fluxo,
+};
+use arqgen::file_generator::{
+    FormatoIpc, OpcoesParquet, write_arrow_com_formato, write_parquet_com_opcoes,
+};
+use arqgen::file_reader::{read_arrow_em_lotes, read_parquet, read_parquet_em_lotes};
+use arqgen::models::arrow_converter::para_record_batch;
+use arqgen::models::empreendimento::Empreendimento;
+use arqgen::models::geometria::Poligono;
+use arqgen::models::lote::Lote;
+use arqgen::models::torre::Torre;
+use arqgen::models::uso::Uso;
+use arrow::array::StringArray;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+/// Gerador congruencial linear, para massas de teste reprodutíveis.
+struct Gerador(u64);
+
+impl Gerador {
+    fn proximo(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+
+    fn escolher<T: Clone>(&mut self, itens: &[T]) -> T {
+        itens[self.proximo() as usize % itens.len()].clone()
+    }
+
+    fn real(&mut self, min: f64, max: f64) -> f64 {
+        let fracao = (self.proximo() % 10_000) as f64 / 10_000.0;
+        ((min + fracao * (max - min)) * 100.0).round() / 100.0
+    }
+
+    fn talvez<T>(&mut self, valor: impl FnOnce(&mut Self) -> T) -> Option<T> {
+        (!self.proximo().is_multiple_of(3)).then(|| valor(self))
+    }
+}
+
+fn retangulo(x: f64, y: f64, largura: f64, profundidade: f64) -> Poligono {
+    Poligono(vec![
+        [x, y],
+        [x + largura, y],
+        [x + largura, y + profundidade],
+        [x, y + profundidade],
+    ])
+}
+
+/// Empreendimentos variados cobrindo as cidades, zonas, construtoras e usos
+/// com regras específicas, campos ausentes, lotes e implantação de torres.
+fn massa_de_teste(quantidade: usize) -> Vec<Empreendimento> {
+    let mut gerador = Gerador(40);
+    let cidades = [
+        "São Paulo",
+        "Rio de Janeiro",
+        "Curitiba",
+        "Boituva",
+        "Guaratinguetá",
+        "Campinas",
+    ];
+    let zonas = [None, Some("ZER"), Some("ZM"), Some("ZEU"), Some("ZR")];
+
+    (0..quantidade)
+        .map(|_| {
+            let numero_de_torres = gerador.proximo() as u32 % 7;
+            let area_do_terreno = match gerador.proximo() % 20 {
+                0 => 0.0,
+                _ => gerador.real(200.0, 5000.0),
+            };
+            let lote = gerador.proximo().is_multiple_of(4).then(|| {
+                let largura = gerador.real(4.0, 60.0);
+                Lote::local(retangulo(0.0, 0.0, largura, area_do_terreno / largura))
+            });
+            let torres = gerador.proximo().is_multiple_of(4).then(|| {
+                (0..numero_de_torres)
+                    .map(|i| Torre {
+                        nome: Some(format!("T{}", i)),
+                        projecao: retangulo(
+                            5.0 + i as f64 * gerador.real(8.0, 30.0),
+                            5.0,
+                            10.0,
+                            10.0,
+                        ),
+                        altura: gerador.talvez(|g| g.real(10.0, 50.0)),
+                    })
+                    .collect()
+            });
+
+            Empreendimento {
+                construtora: gerador.escolher(&["Alpha", "Beta", "Gama"]).to_string(),
+                cidade: gerador.escolher(&cidades).to_string(),
+                area_do_terreno,
+                numero_de_torres,
+                altura_da_torre: gerador.real(5.0, 50.0),
+                area_da_torre: gerador.real(50.0, 600.0),
+                area_de_lazer: gerador.talvez(|g| g.real(0.0, 500.0)),
+                area_construida: gerador.talvez(|g| g.real(500.0, 15000.0)),
+                area_permeavel: gerador.talvez(|g| g.real(0.0, 1500.0)),
+                area_de_projecao: gerador.talvez(|g| g.real(100.0, 3000.0)),
+                uso: gerador.escolher(&[Uso::Residencial, Uso::Comercial, Uso::Misto]),
+                zona: gerador.escolher(&zonas).map(str::to_string),
+                numero_de_unidades: gerador.talvez(|g| g.proximo() as u32 % 400),
+                unidades_por_torre: gerador.talvez(|g| g.proximo() as u32 % 80),
+                vagas_de_garagem: gerador.talvez(|g| g.proximo() as u32 % 500),
+                vagas_acessiveis: gerador.talvez(|g| g.proximo() as u32 % 20),
+                vagas_de_motos: gerador.talvez(|g| g.proximo() as u32 % 40),
+                vagas_de_bicicletas: gerador.talvez(|g| g.proximo() as u32 % 80),
+                torres,
+                lote,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn comparar(colunares: &[ValidationResult], por_linha: &[ValidationResult]) {
+    assert_eq!(colunares.len(), por_linha.len());
+    for (i, (colunar, linha)) in colunares.iter().zip(por_linha).enumerate() {
+        assert_eq!(
+            colunar.empreendimento,
+            linha.empreendimento,
+            "Linha {}",
+            i + 1
+        );
+        assert_eq!(colunar.mensagens, linha.mensagens, "Linha {}", i + 1);
+        assert_eq!(colunar.violacoes, linha.violacoes, "Linha {}", i + 1);
+        assert_eq!(colunar.regras_ok, linha.regras_ok, "Linha {}", i + 1);
+        assert_eq!(colunar.zona, linha.zona, "Linha {}", i + 1);
+        assert_eq!(colunar.altura_permitida, linha.altura_permitida);
+        assert_eq!(colunar.indices, linha.indices, "Linha {}", i + 1);
+    }
+}
+
+#[test]
+fn motor_colunar_deve_produzir_os_mesmos_resultados_do_motor_por_linha() {
+    let empreendimentos = massa_de_teste(600);
+    let por_linha = validar_empreendimentos(&empreendimentos);
+
+    let colunares = validar_record_batch(&para_record_batch(&empreendimentos).unwrap()).unwrap();
+
+    comparar(&colunares, &por_linha);
+    let violacoes: usize = por_linha.iter().map(|r| r.mensagens.len()).sum();
+    assert!(
+        por_linha.iter().any(|r| r.regras_ok) && violacoes > 600,
+        "A massa de teste deve ter aprovações e muitas violações"
+    );
+}
+
+#[test]
+fn motor_colunar_deve_validar_varios_lotes_em_ordem() {
+    let empreendimentos = massa_de_teste(300);
+    let batch = para_record_batch(&empreendimentos).unwrap();
+    let lotes = [
+        batch.slice(0, 120),
+        batch.slice(120, 7),
+        batch.slice(127, 173),
+    ];
+
+    let colunares = validar_record_batches(&lotes).unwrap();
+
+    comparar(&colunares, &validar_empreendimentos(&empreendimentos));
+}
+
+#[test]
+fn motor_colunar_deve_validar_arquivo_parquet() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+    let opcoes = OpcoesParquet {
+        linhas_por_grupo: 64,
+        ..Default::default()
+    };
+    write_parquet_com_opcoes(path, &massa_de_teste(400), &opcoes).unwrap();
+
+    let colunares = validar_record_batches_em_fluxo(read_parquet_em_lotes(path).unwrap()).unwrap();
+    let por_linha = validar_empreendimentos(&read_parquet(path).unwrap());
+
+    comparar(&colunares, &por_linha);
+}
+
+#[test]
+fn motor_colunar_deve_usar_o_uso_padrao_sem_a_coluna_ou_com_nulos() {
+    let batch = para_record_batch(&massa_de_teste(300)).unwrap();
+    let uso = batch.schema().index_of("uso").unwrap();
+    let sem_uso = batch
+        .project(
+            &(0..batch.num_columns())
+                .filter(|&i| i != uso)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+    let mut colunas = batch.columns().to_vec();
+    colunas[uso] = Arc::new(
+        batch
+            .column(uso)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, valor)| valor.filter(|_| i % 2 == 0))
+            .collect::<StringArray>(),
+    );
+    let uso_nulo = RecordBatch::try_new(batch.schema(), colunas).unwrap();
+
+    for lote in [sem_uso, uso_nulo] {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap();
+        let mut writer =
+            ArrowWriter::try_new(File::create(path).unwrap(), lote.schema(), None).unwrap();
+        writer.write(&lote).unwrap();
+        writer.close().unwrap();
+
+        let colunares =
+            validar_record_batches_em_fluxo(read_parquet_em_lotes(path).unwrap()).unwrap();
+        let por_linha = validar_empreendimentos(&read_parquet(path).unwrap());
+
+        comparar(&colunares, &por_linha);
+    }
+}
+
+#[test]
+fn motor_colunar_deve_validar_arquivo_arrow() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let path = temp_file.path().to_str().unwrap();
+    let empreendimentos = massa_de_teste(200);
+    write_arrow_com_formato(path, &empreendimentos, FormatoIpc::Fluxo).unwrap();
+
+    let colunares = validar_record_batches(&read_arrow_em_lotes(path).unwrap()).unwrap();
+
+    comparar(&colunares, &validar_empreendimentos(&empreendimentos));
+}
+
+#[test]
+fn motor_colunar_sem_linhas_nao_gera_resultados() {
+    let batch = para_record_batch(&[]).unwrap();
+    assert!(validar_record_batch(&batch).unwrap().is_empty());
+}
@@ -1,4 +1,4 @@
-use arqgen::business_logic::validar_empreendimentos;
+use arqgen::business_logic::{Severidade, validar_empreendimentos};
 use arqgen::models::empreendimento::Empreendimento;
 mod test_utils;
 use test_utils::*;
@@ -43,6 +43,7 @@ fn deve_aplicar_regras_padrao_e_especificas_corretamente() {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(120.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Alpha".to_string(),
@@ -52,6 +53,7 @@ fn deve_aplicar_regras_padrao_e_especificas_corretamente() {
             altura_da_torre: 25.0,
             area_da_torre: 300.0,
             area_de_lazer: Some(80.0),
+            ..Default::default()
         },
     ];
 
@@ -134,6 +136,7 @@ fn deve_tratar_cenarios_extremos_e_limites() {
             altura_da_torre: 0.1,
             area_da_torre: 0.05,
             area_de_lazer: Some(0.01),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Mega".to_string(),
@@ -143,6 +146,7 @@ fn deve_tratar_cenarios_extremos_e_limites() {
             altura_da_torre: 100.0,
             area_da_torre: 1000.0,
             area_de_lazer: Some(500.0),
+            ..Default::default()
         },
         Empreendimento {
             construtora: "Limite".to_string(),
@@ -152,6 +156,7 @@ fn deve_tratar_cenarios_extremos_e_limites() {
             altura_da_torre: 29.999,
             area_da_torre: 399.999,
             area_de_lazer: Some(99.999),
+            ..Default::default()
         },
     ];
 
@@ -183,6 +188,7 @@ fn deve_aplicar_regras_especificas_em_combinacoes_complexas() {
         altura_da_torre: 25.0,
         area_da_torre: 300.0,
         area_de_lazer: Some(80.0),
+        ..Default::default()
     };
 
     let resultado = validar_empreendimentos(&[empreendimento_especial]);
@@ -209,3 +215,19 @@ fn deve_aplicar_regras_especificas_em_combinacoes_complexas() {
         "Deve aplicar pelo menos uma regra específica"
     );
 }
+
+#[test]
+fn deve_classificar_a_severidade_pela_quantidade_de_violacoes() {
+    let mut uma_violacao = criar_empreendimento_valido();
+    uma_violacao.area_de_lazer = Some(50.0);
+
+    let resultados = validar_empreendimentos(&[
+        criar_empreendimento_valido(),
+        uma_violacao,
+        criar_empreendimento_com_violacoes(),
+    ]);
+
+    assert_eq!(resultados[0].severidade(), Severidade::Nenhuma);
+    assert_eq!(resultados[1].severidade(), Severidade::Moderada);
+    assert_eq!(resultados[2].severidade(), Severidade::Grave);
+}